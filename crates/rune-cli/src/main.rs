@@ -133,6 +133,7 @@ async fn main() -> Result<()> {
         println!("Available <option> arguments:");
         println!("  memoize-instance-fn[=<true/false>] - Inline the lookup of an instance function where appropriate.");
         println!("  link-checks[=<true/false>] - Perform linker checks which makes sure that called functions exist.");
+        println!("  big-integers[=<true/false>] - Compile integer literals which are out of bounds as big integers.");
         return Ok(());
     }
 
@@ -20,6 +20,52 @@ fn break_outside_of_loop() {
     };
 }
 
+#[test]
+fn continue_outside_of_loop() {
+    assert_compile_error! {
+        r#"fn main() { continue; }"#,
+        ContinueOutsideOfLoop { span } => {
+            assert_eq!(span, Span::new(12, 20));
+        }
+    };
+}
+
+#[test]
+fn test_inconsistent_or_pattern_bindings() {
+    assert_compile_error! {
+        r#"fn main(v) { match v { Some(a) | None => 1, _ => 2 } }"#,
+        InconsistentOrPatternBindings { span, name } => {
+            assert_eq!(span, Span::new(33, 37));
+            assert_eq!(name, "a");
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main(v) { match v { (a, 0) | (b, 1) => 1, _ => 2 } }"#,
+        InconsistentOrPatternBindings { span, name } => {
+            assert_eq!(span, Span::new(32, 38));
+            assert_eq!(name, "b");
+        }
+    };
+}
+
+#[test]
+fn test_missing_loop_label() {
+    assert_compile_error! {
+        r#"fn main() { 'a: loop { break 'b; } }"#,
+        MissingLabel { span } => {
+            assert_eq!(span, Span::new(29, 31));
+        }
+    };
+
+    assert_compile_error! {
+        r#"fn main() { 'a: loop { continue 'b; } }"#,
+        MissingLabel { span } => {
+            assert_eq!(span, Span::new(32, 34));
+        }
+    };
+}
+
 #[test]
 fn test_pointers() {
     assert_compile_error! {
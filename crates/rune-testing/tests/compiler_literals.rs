@@ -39,3 +39,64 @@ fn test_number_literals() {
         }
     };
 }
+
+#[test]
+fn test_number_suffixes() {
+    assert_eq!(rune!(i64 => r#"fn main() { 42i64 }"#), 42);
+    assert_eq!(rune!(u8 => r#"fn main() { 255u8 }"#), 255);
+    assert_eq!(rune!(f64 => r#"fn main() { 3f64 }"#), 3.0);
+
+    assert_eq!(
+        rune!(bool => r#"fn main() { match b'a' { 97u8 => true, _ => false } }"#),
+        true
+    );
+
+    assert_compile_error! {
+        r#"fn main() { 256u8 }"#,
+        ParseError { error: BadNumberOutOfBoundsForSuffix { span, .. }} => {
+            assert_eq!(span, Span::new(12, 17));
+        }
+    };
+}
+
+#[test]
+fn test_raw_string_literals() {
+    assert_eq!(
+        rune!(String => r#"fn main() { r"C:\Users\rune" }"#),
+        r"C:\Users\rune"
+    );
+
+    assert_eq!(
+        rune!(String => r###"fn main() { r#"a "quote" b"# }"###),
+        r#"a "quote" b"#
+    );
+
+    assert_eq!(
+        rune!(bool => r###"fn main() { match "a\\d" { r"a\d" => true, _ => false } }"###),
+        true
+    );
+}
+
+#[test]
+fn test_byte_string_literals() {
+    let bytes = rune!(Value => r#"fn main() { b"\x00\xFF" }"#);
+    assert_eq!(
+        bytes.into_bytes().unwrap().take().unwrap().into_vec(),
+        vec![0x00, 0xff]
+    );
+
+    let bytes = rune!(Value => r#"fn main() { b"ab\n" }"#);
+    assert_eq!(
+        bytes.into_bytes().unwrap().take().unwrap().into_vec(),
+        b"ab\n".to_vec()
+    );
+
+    assert_eq!(rune!(u8 => r#"fn main() { b'\xFF' }"#), 0xff);
+
+    assert_compile_error! {
+        r#"fn main() { b"aé" }"#,
+        ParseError { error: NonAsciiByteStr { span, .. }} => {
+            assert_eq!(span, Span::new(15, 17));
+        }
+    };
+}
@@ -0,0 +1,121 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Hash, Inst, Item, LinkError, Module, Unit, Vm};
+use std::sync::Arc;
+
+/// Construct a context which declares the functions which are provided by
+/// other units, so that they can be called across units.
+fn declarations() -> Result<Context> {
+    let mut module = Module::default();
+    module.function(&["greeting"], |_: String| String::new())?;
+    module.function(&["punctuation"], || String::new())?;
+    module.function(&["length"], |_: String| 0i64)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(context)
+}
+
+#[test]
+fn test_link_units() -> Result<()> {
+    let declarations = declarations()?;
+
+    let (greeting, _) = compile_source(
+        &declarations,
+        r#"
+        fn greeting(name) {
+            let prefix = "hello";
+            `{prefix} {name}{punctuation()}`
+        }
+        "#,
+    )?;
+
+    let (main, _) = compile_source(
+        &declarations,
+        r#"
+        fn punctuation() {
+            "!"
+        }
+
+        fn main() {
+            let object = #{"hello": greeting("world")};
+            object.hello
+        }
+        "#,
+    )?;
+
+    let unit = Unit::link(vec![greeting, main])?;
+
+    // NB: the string "hello" is shared by both units.
+    let strings = unit
+        .iter_static_strings()
+        .filter(|s| s.as_str() == "hello")
+        .count();
+    assert_eq!(strings, 1);
+
+    let debug = unit.debug_info().expect("debug info");
+    assert_eq!(debug.instructions.len(), unit.iter_instructions().count());
+
+    // NB: the linked unit runs without the declarations.
+    let context = Arc::new(Context::with_default_modules()?);
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+    assert_eq!(String::from_value(output)?, "hello world!");
+    Ok(())
+}
+
+#[test]
+fn test_link_instance_call_slots() -> Result<()> {
+    let declarations = declarations()?;
+
+    let (length, _) = compile_source(&declarations, r#"fn length(s) { s.len() }"#)?;
+
+    let (main, _) = compile_source(
+        &declarations,
+        r#"
+        fn main() {
+            let v = [1, 2];
+            v.len() * 10 + length("abc")
+        }
+        "#,
+    )?;
+
+    assert_eq!(length.instance_call_slots(), 1);
+    assert_eq!(main.instance_call_slots(), 1);
+
+    let unit = Unit::link(vec![length, main])?;
+
+    // NB: every call site keeps a slot of its own.
+    let slots = unit
+        .iter_instructions()
+        .filter_map(|inst| match inst {
+            Inst::CallInstance { slot, .. } => Some(slot),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(slots, vec![0, 1]);
+    assert_eq!(unit.instance_call_slots(), 2);
+
+    let context = Arc::new(Context::with_default_modules()?);
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+    assert_eq!(i64::from_value(output)?, 23);
+    Ok(())
+}
+
+#[test]
+fn test_link_conflicting_functions() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (a, _) = compile_source(&context, r#"fn main() { 1 }"#)?;
+    let (b, _) = compile_source(&context, r#"fn main() { 2 }"#)?;
+
+    match Unit::link(vec![a, b]) {
+        Err(LinkError::ConflictingFunction { hash }) => {
+            assert_eq!(hash, Hash::type_hash(Item::of(&["main"])));
+        }
+        result => panic!("expected conflicting function but got: {:?}", result),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Unit, Vm};
+use std::sync::Arc;
+
+/// Compile the given source, and return the unoptimized and the optimized
+/// unit.
+fn compile(context: &Context, source: &str) -> Result<(Arc<Unit>, Arc<Unit>)> {
+    let (unit, _) = compile_source(context, source)?;
+    let (mut optimized, _) = compile_source(context, source)?;
+    optimized.optimize();
+    Ok((Arc::new(unit), Arc::new(optimized)))
+}
+
+/// Run the main function in the given unit.
+fn run_main(context: &Arc<Context>, unit: &Arc<Unit>) -> Result<i64> {
+    let vm = Vm::new(context.clone(), unit.clone());
+    let output = vm.call(&["main"], ())?.complete()?;
+    Ok(i64::from_value(output)?)
+}
+
+#[test]
+fn test_optimize_redundant_pops() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, optimized) = compile(
+        &context,
+        r#"
+        fn main() {
+            let a = 1;
+            match a { _ => () };
+            a + 2
+        }
+        "#,
+    )?;
+
+    let before = unit.iter_instructions().count();
+    let after = optimized.iter_instructions().count();
+    assert!(after < before, "expected {} < {}", after, before);
+
+    let debug = optimized.debug_info().expect("debug info");
+    assert_eq!(debug.instructions.len(), after);
+
+    assert_eq!(run_main(&context, &unit)?, 3);
+    assert_eq!(run_main(&context, &optimized)?, 3);
+    Ok(())
+}
+
+#[test]
+fn test_optimize_preserves_behavior() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let sources = &[
+        r#"
+        fn main() {
+            let n = 0;
+            let out = 0;
+
+            while n < 10 {
+                match n { 5 => (), _ => { out += n; } };
+                n += 1;
+            }
+
+            out
+        }
+        "#,
+        r#"
+        fn add(a, b) { match a { _ => () }; a + b }
+
+        fn main() {
+            let out = 0;
+
+            for n in [1, 2, 3] {
+                if n > 1 { out = add(out, n); } else { match n { _ => () }; }
+            }
+
+            out
+        }
+        "#,
+    ];
+
+    for source in sources {
+        let (unit, optimized) = compile(&context, source)?;
+        assert!(optimized.iter_instructions().count() <= unit.iter_instructions().count());
+        assert_eq!(run_main(&context, &unit)?, run_main(&context, &optimized)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_constants() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let source = r#"fn main() { 2 + 3 * 4 - (10 >> 1) }"#;
+    let (unit, _) = compile_source(&context, source)?;
+    let (mut folded, _) = compile_source(&context, source)?;
+    folded.fold_constants();
+
+    assert_eq!(
+        folded
+            .iter_instructions()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>(),
+        vec!["integer 9", "return"],
+    );
+
+    let (unit, folded) = (Arc::new(unit), Arc::new(folded));
+    assert_eq!(run_main(&context, &unit)?, 9);
+    assert_eq!(run_main(&context, &folded)?, 9);
+
+    let (mut unit, _) = compile_source(&context, r#"fn main() { 1.5 * 2.0 < 3.5 && !false }"#)?;
+    unit.fold_constants();
+
+    assert_eq!(
+        unit.iter_instructions()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>(),
+        vec!["bool true", "return"],
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fold_constants_overflow() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(&context, r#"fn main() { 9223372036854775807 + 1 }"#)?;
+    let before = unit.iter_instructions().count();
+    let mut folded = unit;
+    folded.fold_constants();
+    assert_eq!(folded.iter_instructions().count(), before);
+
+    let vm = Vm::new(context.clone(), Arc::new(folded));
+    let error = vm.call(&["main"], ())?.complete().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        runestick::VmErrorKind::Overflow => (),
+        kind => panic!("expected overflow but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_eliminate_dead_code() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let source = r#"
+    fn foo(n) {
+        return n + 1;
+        let a = n * 2;
+        a
+    }
+
+    fn main() {
+        let n = foo(1);
+
+        loop {
+            break;
+            n = 10;
+        }
+
+        return n;
+        n + 10
+    }
+    "#;
+
+    let (unit, _) = compile_source(&context, source)?;
+    let (mut optimized, _) = compile_source(&context, source)?;
+    optimized.eliminate_dead_code();
+
+    let before = unit.iter_instructions().count();
+    let after = optimized.iter_instructions().count();
+    assert!(after < before, "expected {} < {}", after, before);
+
+    let debug = optimized.debug_info().expect("debug info");
+    assert_eq!(debug.instructions.len(), after);
+
+    let (unit, optimized) = (Arc::new(unit), Arc::new(optimized));
+    assert_eq!(run_main(&context, &unit)?, 2);
+    assert_eq!(run_main(&context, &optimized)?, 2);
+    Ok(())
+}
+
+#[test]
+fn test_fuse_moves() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let source = r#"
+    fn main() {
+        let a = 1;
+        let b = 2;
+        a = b;
+        b = 3;
+        a + b
+    }
+    "#;
+
+    let (unit, optimized) = compile(&context, source)?;
+
+    let instructions = |unit: &Unit| {
+        unit.iter_instructions()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        instructions(&unit),
+        vec![
+            "integer 1",
+            "integer 2",
+            "copy 1",
+            "replace 0",
+            "integer 3",
+            "replace 1",
+            "copy 0",
+            "copy 1",
+            "add",
+            "clean 2",
+            "return"
+        ],
+    );
+
+    assert_eq!(
+        instructions(&optimized),
+        vec![
+            "integer 1",
+            "integer 2",
+            "move 1, 0",
+            "integer 3",
+            "replace 1",
+            "copy 0",
+            "copy 1",
+            "add",
+            "clean 2",
+            "return"
+        ],
+    );
+
+    assert_eq!(run_main(&context, &unit)?, 5);
+    assert_eq!(run_main(&context, &optimized)?, 5);
+    Ok(())
+}
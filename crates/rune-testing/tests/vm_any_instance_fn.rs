@@ -0,0 +1,49 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Value, Vm, VmError};
+use std::sync::Arc;
+
+/// A single instance function which works on both vectors and strings.
+fn size(value: Value) -> Result<usize, VmError> {
+    Ok(match value {
+        Value::Vec(vec) => vec.borrow_ref()?.len(),
+        Value::String(string) => string.borrow_ref()?.len(),
+        Value::StaticString(string) => string.len(),
+        _ => 0,
+    })
+}
+
+/// Overrides `len` for all types which don't register it themselves.
+fn len(_: Value) -> usize {
+    42
+}
+
+#[test]
+fn test_any_instance_fn() -> Result<()> {
+    let mut module = Module::default();
+    module.inst_fn("size", size)?;
+    module.inst_fn("len", len)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let s = `hello`;
+            ([1, 2, 3].size(), "hi".size(), s.size(), [1, 2].len(), 'a'.len())
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    assert_eq!(
+        <(usize, usize, usize, usize, usize)>::from_value(output)?,
+        (3, 2, 5, 2, 42)
+    );
+
+    Ok(())
+}
@@ -237,6 +237,19 @@ fn test_rem() {
         1,
     };
 
+    assert_eq! {
+        rune! {
+            f64 => r#"
+            fn main() {
+                let a = 10.5;
+                let b = 3.0;
+                a % b
+            }
+            "#
+        },
+        1.5,
+    };
+
     assert_vm_error!(
         r#"
         fn main() {
@@ -317,6 +330,46 @@ fn test_bit_ops() {
     };
 }
 
+#[test]
+fn test_bool_bit_ops() {
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = true; let b = false; a & b }"#),
+        false,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = true; let b = false; a | b }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = true; let b = true; a ^ b }"#),
+        false,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = true; a &= false; a }"#),
+        false,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = false; a |= true; a }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = false; a ^= true; a }"#),
+        true,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { let a = true; let b = 1; a & b }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(*op, "&");
+        }
+    );
+}
+
 #[test]
 fn test_shift_ops() {
     assert_eq! {
@@ -356,6 +409,31 @@ fn test_shift_ops() {
     };
 }
 
+#[test]
+fn test_shift_out_of_range() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { let a = 1; let b = 63; a << b }"#),
+        i64::MIN,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { let a = -1; let b = 63; a >> b }"#),
+        -1,
+    };
+
+    for op in &["<<", ">>", "<<=", ">>="] {
+        for count in &["64", "-1"] {
+            let source = if op.ends_with('=') {
+                format!("fn main() {{ let a = 1; a {} {}; a }}", op, count)
+            } else {
+                format!("fn main() {{ let a = 1; let b = {}; a {} b }}", count, op)
+            };
+
+            assert_vm_error!(&source, Overflow => {});
+        }
+    }
+}
+
 #[test]
 fn test_bitwise_not() {
     assert_eq! {
@@ -366,4 +444,157 @@ fn test_bitwise_not() {
         },
         !0b10100,
     };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = true; !a }"#),
+        false,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { let a = "hello"; !a }"#,
+        UnsupportedUnaryOperation { op, .. } => {
+            assert_eq!(*op, "!");
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { let a = [1, 2]; !a }"#,
+        UnsupportedUnaryOperation { op, .. } => {
+            assert_eq!(*op, "!");
+        }
+    );
+}
+
+#[test]
+fn test_to_radix() {
+    assert_eq! {
+        rune!(String => r#"fn main() { let n = 255; n.to_radix(16) }"#),
+        "ff",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let n = 255; n.to_radix(2) }"#),
+        "11111111",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let n = 255; n.to_radix(36) }"#),
+        "73",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let n = -255; n.to_radix(16) }"#),
+        "-ff",
+    };
+
+    assert_vm_error!(
+        r#"fn main() { let n = 255; n.to_radix(37); }"#,
+        Panic { reason } => {
+            assert_eq!(
+                reason.to_string(),
+                "invalid radix `37`, expected a radix between 2 and 36"
+            );
+        }
+    );
+}
+
+#[test]
+fn test_float_literals() {
+    assert_eq!(rune!(f64 => r#"fn main() { inf }"#), f64::INFINITY);
+    assert_eq!(rune!(f64 => r#"fn main() { -inf }"#), f64::NEG_INFINITY);
+    assert!(rune!(f64 => r#"fn main() { nan }"#).is_nan());
+    assert_eq!(rune!(f64 => r#"fn main() { -1.5 }"#), -1.5);
+    assert!(rune!(bool => r#"fn main() { let n = nan; n != n }"#));
+}
+
+#[test]
+fn test_neg() {
+    assert_eq!(
+        rune!(i64 => r#"fn main() { let x = 5; let y = -x; y }"#),
+        -5
+    );
+    assert_eq!(rune!(i64 => r#"fn main() { let x = 5; -(-x) }"#), 5);
+    assert_eq!(rune!(i64 => r#"fn main() { let x = 5; 10 - -x }"#), 15);
+    assert_eq!(rune!(f64 => r#"fn main() { let x = 1.5; -x }"#), -1.5);
+
+    assert_vm_error!(
+        r#"fn main() { let x = -9223372036854775808; -x }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"fn main() { let x = "hello"; -x }"#,
+        UnsupportedUnaryOperation { op, .. } => {
+            assert_eq!(*op, "-");
+        }
+    );
+}
+
+#[test]
+fn test_parse_radix() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match int::parse_radix("ff", 16) { Ok(n) => n, Err(_) => 0 } }"#),
+        255,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match int::parse_radix("-101", 2) { Ok(n) => n, Err(_) => 0 } }"#),
+        -5,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match int::parse_radix("102", 2) { Err(_) => true, _ => false } }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match int::parse_radix("ff", 37) { Err(_) => true, _ => false } }"#),
+        true,
+    };
+}
+
+#[test]
+fn test_int_to_char() {
+    assert_eq! {
+        rune!(char => r#"fn main() { match 97.to_char() { Ok(c) => c, Err(_) => '?' } }"#),
+        'a',
+    };
+
+    assert_eq! {
+        rune!(char => r#"fn main() { match 0x1F600.to_char() { Ok(c) => c, Err(_) => '?' } }"#),
+        '😀',
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match 0xD800.to_char() { Err(_) => true, _ => false } }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match (-1).to_char() { Err(_) => true, _ => false } }"#),
+        true,
+    };
+}
+
+#[test]
+fn test_float_try_to_integer() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match 42.0.try_to_integer() { Ok(n) => n, Err(_) => 0 } }"#),
+        42,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match nan.try_to_integer() { Err(_) => true, _ => false } }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match inf.try_to_integer() { Err(_) => true, _ => false } }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let n = 10000000000000000000.0; match n.try_to_integer() { Err(_) => true, _ => false } }"#),
+        true,
+    };
 }
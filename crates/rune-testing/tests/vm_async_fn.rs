@@ -0,0 +1,47 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Vm};
+use std::sync::Arc;
+
+async fn fetch() -> i64 {
+    42
+}
+
+async fn greet(name: String) -> String {
+    format!("hello {}", name)
+}
+
+fn context() -> Result<Arc<Context>> {
+    let mut module = Module::default();
+    module.async_function(&["fetch"], fetch)?;
+    module.async_function(&["greet"], greet)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(Arc::new(context))
+}
+
+#[test]
+fn test_async_fn() -> Result<()> {
+    let context = context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn main() {
+            let name = "world";
+            let greeting = greet(name);
+            // NB: the argument is owned by the future, so the original value
+            // can be modified before it is awaited.
+            name = "moon";
+            (fetch().await + 1, greeting.await)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = block_on(vm.call(&["main"], ())?.async_complete())?;
+    let (a, b) = <(i64, String)>::from_value(output)?;
+    assert_eq!(a, 43);
+    assert_eq!(b, "hello world");
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use rune::{Options, Sources, Warnings};
+use rune_testing::*;
+use runestick::{BigInt, Context, FromValue, Source, Vm, VmError};
+use std::sync::Arc;
+
+/// Run the given source with big integers enabled.
+fn run_big_int(source: &str) -> Result<Value, VmError> {
+    let context = Context::with_default_modules().expect("default context");
+
+    let mut options = Options::default();
+    options
+        .parse_option("big-integers")
+        .expect("a supported option");
+
+    let mut sources = Sources::new();
+    sources.insert_default(Source::new("main", source.to_owned()));
+
+    let unit = rune::load_sources(&context, &options, &mut sources, &mut Warnings::disabled())
+        .expect("source to compile");
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    vm.call(&["main"], ())?.complete()
+}
+
+#[test]
+fn test_big_int_literal() -> Result<()> {
+    let output = run_big_int(r#"fn main() { 36893488147419103232 }"#)?;
+    let expected = "36893488147419103232".parse::<BigInt>()?;
+    assert_eq!(BigInt::from_value(output)?, expected);
+
+    let output = run_big_int(r#"fn main() { -36893488147419103232 }"#)?;
+    assert_eq!(BigInt::from_value(output)?, -expected);
+
+    // NB: integers which are in bounds are still integers.
+    let output = run_big_int(r#"fn main() { 9223372036854775807 }"#)?;
+    assert!(matches!(output, Value::Integer(9223372036854775807)));
+    Ok(())
+}
+
+#[test]
+fn test_big_int_comparison() -> Result<()> {
+    let output = run_big_int(
+        r#"
+        fn main() {
+            let n = 36893488147419103232;
+
+            [
+                n == 36893488147419103232,
+                n != 36893488147419103233,
+                n > 9223372036854775807,
+                -36893488147419103232 < -9223372036854775808,
+                n >= n,
+                1 < n,
+                n == 1,
+            ]
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        Vec::<bool>::from_value(output)?,
+        vec![true, true, true, true, true, true, false]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_big_int_pattern() -> Result<()> {
+    let output = run_big_int(
+        r#"
+        fn main() {
+            match 36893488147419103232 {
+                36893488147419103233 => 1,
+                36893488147419103232 => 2,
+                _ => 3,
+            }
+        }
+        "#,
+    )?;
+
+    assert_eq!(i64::from_value(output)?, 2);
+    Ok(())
+}
+
+#[test]
+fn test_big_int_format() -> Result<()> {
+    let output = run_big_int(
+        r#"
+        fn main() {
+            let n = 36893488147419103232;
+            `n = {n}`
+        }
+        "#,
+    )?;
+
+    assert_eq!(String::from_value(output)?, "n = 36893488147419103232");
+    Ok(())
+}
+
+#[test]
+fn test_big_int_arithmetic_unsupported() {
+    let error = run_big_int(r#"fn main() { 36893488147419103232 + 1 }"#).unwrap_err();
+
+    let (kind, _) = error.kind().into_unwound_ref();
+
+    match kind {
+        UnsupportedBinaryOperation { op, .. } => assert_eq!(*op, "+"),
+        kind => panic!("expected unsupported binary operation but got: {:?}", kind),
+    }
+}
+
+#[test]
+fn test_big_int_disabled() {
+    assert_compile_error! {
+        r#"fn main() { 36893488147419103232 }"#,
+        ParseError { error: BadNumberOutOfBounds { .. } } => {}
+    };
+}
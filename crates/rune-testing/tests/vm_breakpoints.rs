@@ -0,0 +1,142 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Inst, Vm, VmExecutionState};
+use std::sync::Arc;
+
+#[test]
+fn test_breakpoint_in_loop() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(n) {
+            let out = 0;
+
+            while n > 0 {
+                out = out + n;
+                n = n - 1;
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    // NB: the first addition is the one inside of the loop.
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Add))
+        .expect("an add instruction");
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_breakpoint(ip);
+
+    let mut execution = vm.call(&["main"], (3i64,))?;
+    let mut observed = Vec::new();
+
+    let output = loop {
+        match execution.run_to_breakpoint()? {
+            VmExecutionState::Breakpoint { ip: at } => {
+                assert_eq!(at, ip);
+                let vm = execution.vm()?;
+                assert_eq!(vm.ip(), ip);
+
+                let n = vm.stack().iter().next().expect("the argument").clone();
+                observed.push(i64::from_value(n)?);
+            }
+            VmExecutionState::Complete(output) => break output,
+        }
+    };
+
+    assert_eq!(observed, vec![3, 2, 1]);
+    assert_eq!(i64::from_value(output)?, 6);
+    Ok(())
+}
+
+#[test]
+fn test_clear_breakpoint() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let out = 0;
+
+            for n in [1, 2, 3, 4] {
+                out = out + n;
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Add))
+        .expect("an add instruction");
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_breakpoint(ip);
+
+    let mut execution = vm.call(&["main"], ())?;
+    assert!(execution.run_to_breakpoint()?.is_breakpoint());
+
+    execution.vm_mut()?.clear_breakpoint(ip);
+
+    match execution.run_to_breakpoint()? {
+        VmExecutionState::Complete(output) => assert_eq!(i64::from_value(output)?, 10),
+        state => panic!("expected the execution to complete but got: {:?}", state),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_breakpoint_in_closure() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let f = |n| n * 2;
+            let out = f(1);
+
+            for n in [2, 3].iter().map(f) {
+                out = out + n;
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Mul))
+        .expect("a mul instruction");
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_breakpoint(ip);
+
+    let mut execution = vm.call(&["main"], ())?;
+    let mut hits = 0;
+
+    // NB: only the direct call halts, calls made from native code run in a
+    // child virtual machine which doesn't inherit the breakpoint.
+    let output = loop {
+        match execution.run_to_breakpoint()? {
+            VmExecutionState::Breakpoint { ip: at } => {
+                assert_eq!(at, ip);
+                hits += 1;
+            }
+            VmExecutionState::Complete(output) => break output,
+        }
+    };
+
+    assert_eq!(hits, 1);
+    assert_eq!(i64::from_value(output)?, 12);
+    Ok(())
+}
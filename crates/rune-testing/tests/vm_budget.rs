@@ -0,0 +1,74 @@
+use rune_testing::*;
+use runestick::{BudgetOutcome, Context, FromValue, Vm};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn add(a, b) { a + b }
+
+fn main(n) {
+    let out = 0;
+
+    while n > 0 {
+        out = add(out, n);
+        n = n - 1;
+    }
+
+    out
+}
+"#;
+
+#[test]
+fn test_budget_suspend_and_resume() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (100i64,))?;
+    let mut suspensions = 0;
+
+    let output = loop {
+        match execution.run_with_budget(10)? {
+            BudgetOutcome::Completed(output) => break output,
+            BudgetOutcome::Suspended => suspensions += 1,
+        }
+    };
+
+    assert_eq!(i64::from_value(output)?, 5050);
+    assert!(suspensions > 0);
+    Ok(())
+}
+
+#[test]
+fn test_budget_exact() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+    let unit = Arc::new(unit);
+
+    // Count the number of instructions the execution requires.
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+    execution.record();
+    execution.complete()?;
+    let instructions = execution.take_recording().expect("a recording").len();
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+
+    match execution.run_with_budget(instructions)? {
+        BudgetOutcome::Completed(output) => assert_eq!(i64::from_value(output)?, 10),
+        BudgetOutcome::Suspended => panic!("expected the execution to complete"),
+    }
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+
+    assert!(execution.run_with_budget(instructions - 1)?.is_suspended());
+
+    match execution.run_with_budget(1)? {
+        BudgetOutcome::Completed(output) => assert_eq!(i64::from_value(output)?, 10),
+        BudgetOutcome::Suspended => panic!("expected the execution to complete"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Inst, Vm, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_call_frame_overflow() {
+    assert_vm_error!(
+        r#"
+        fn f() { f() }
+        fn main() { f() }
+        "#,
+        CallFrameOverflow { depth } => {
+            assert_eq!(*depth, 512);
+        }
+    );
+}
+
+#[test]
+fn test_max_call_frames() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn count(n) { if n == 0 { 0 } else { 1 + count(n - 1) } }
+        fn main(n) { count(n) }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_max_call_frames(1000);
+    let output = vm.call(&["main"], (900i64,))?.complete()?;
+    assert_eq!(i64::from_value(output)?, 900);
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_max_call_frames(10);
+    let error = vm.call(&["main"], (10i64,))?.complete().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::CallFrameOverflow { depth } => assert_eq!(*depth, 10),
+        kind => panic!("expected call frame overflow but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_error_trace() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn b(n) { n / 0 }
+        fn a(n) { b(n) + 1 }
+        fn main(n) { a(n) + 1 }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let error = vm.call(&["main"], (1i64,))?.complete().unwrap_err();
+    let trace = error.trace().expect("error to have a trace").to_vec();
+    let (error, unwound) = error.into_unwound();
+    let (_, ip) = unwound.expect("error to be unwound");
+
+    match error.kind() {
+        VmErrorKind::DivideByZero => (),
+        kind => panic!("expected divide by zero but got: {:?}", kind),
+    }
+
+    // NB: the trace survives unwrapping the error.
+    assert_eq!(error.trace(), Some(&trace[..]));
+
+    // The innermost frame is where the error happened, followed by the calls
+    // in `a` and `main`.
+    assert_eq!(trace.len(), 3);
+    assert_eq!(trace[0].ip(), ip);
+
+    for frame in &trace[1..] {
+        match unit.instruction_at(frame.ip()) {
+            Some(Inst::Call { .. }) => (),
+            inst => panic!("expected a call instruction but got: {:?}", inst),
+        }
+    }
+
+    Ok(())
+}
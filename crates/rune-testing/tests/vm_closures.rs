@@ -165,3 +165,35 @@ fn test_nested_async_closure() {
         }
     };
 }
+
+#[test]
+fn test_closures_in_receiver_and_arguments() {
+    // NB: the closure in the receiver is indexed and compiled before the one
+    // in the arguments, so each is compiled with its own body.
+    assert_eq! {
+        vec![3, 5],
+        rune! {
+            Vec<i64> => r#"
+            fn main() {
+                let offset = 1;
+                [1, 2, 3, 4].iter().map(|n| n + offset).filter(|n| n % 2 == 1).collect()
+            }
+            "#
+        }
+    };
+
+    assert_eq! {
+        (1, 2),
+        rune! {
+            (i64, i64) => r#"
+            fn main() {
+                let a = 1;
+                let b = 2;
+                let f = Some(|| a).unwrap_or_else(|| || b);
+                let g = None.unwrap_or_else(|| || b);
+                (f(), g())
+            }
+            "#
+        }
+    };
+}
@@ -0,0 +1,411 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Value, Vm};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy)]
+struct Version(i64);
+
+runestick::impl_external!(Version);
+
+impl Version {
+    fn gt(&self, other: &Self) -> bool {
+        self.0 > other.0
+    }
+
+    fn gte(&self, other: &Self) -> bool {
+        self.0 >= other.0
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        self.0 < other.0
+    }
+
+    fn lte(&self, other: &Self) -> bool {
+        self.0 <= other.0
+    }
+}
+
+#[test]
+fn test_string_comparison() {
+    assert_eq! {
+        rune!((bool, bool, bool, bool) => r#"
+        fn main() {
+            let a = "apple";
+            let b = `banana`;
+            (a < b, a <= a, b > a, b >= `banana`)
+        }
+        "#),
+        (true, true, true, true),
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { let a = "b"; let b = "abc"; a < b }"#),
+        false,
+    };
+}
+
+#[test]
+fn test_external_comparison() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Version"]).build::<Version>()?;
+    module.inst_fn(runestick::GT, Version::gt)?;
+    module.inst_fn(runestick::GTE, Version::gte)?;
+    module.inst_fn(runestick::LT, Version::lt)?;
+    module.inst_fn(runestick::LTE, Version::lte)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(a, b) {
+            (a < b, a <= b, a > b, a >= b)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], (Version(1), Version(2)))?.complete()?;
+
+    assert_eq!(
+        <(bool, bool, bool, bool)>::from_value(output)?,
+        (true, true, false, false)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_static_string_eq() {
+    let (a, b) = rune!((Value, Value) => r#"fn main() { ("hello", "hello") }"#);
+
+    // NB: identical literals are interned into the same static slot.
+    match (a, b) {
+        (Value::StaticString(a), Value::StaticString(b)) => assert!(Arc::ptr_eq(&a, &b)),
+        values => panic!("expected static strings but got: {:?}", values),
+    }
+
+    assert_eq! {
+        rune!((bool, bool, bool, bool) => r#"
+        fn main() {
+            let a = "hello";
+            (a == "hello", a == "world", a == `hello`, `hello` != a)
+        }
+        "#),
+        (true, false, true, false),
+    };
+}
+
+#[test]
+fn test_structural_eq() {
+    assert_eq! {
+        rune!((bool, bool, bool) => r#"
+        fn main() {
+            let a = (1, "two", [3]);
+            (a == (1, "two", [3]), a == (1, "two", [4]), (1, 2) == (1, 2, 3))
+        }
+        "#),
+        (true, false, false),
+    };
+
+    assert_eq! {
+        rune!((bool, bool, bool) => r#"
+        struct Point { x, y }
+        struct Other { x, y }
+
+        fn main() {
+            let a = Point { x: 1, y: 2 };
+            (a == Point { x: 1, y: 2 }, a == Point { x: 1, y: 3 }, a == Other { x: 1, y: 2 })
+        }
+        "#),
+        (true, false, false),
+    };
+
+    assert_eq! {
+        rune!((bool, bool, bool) => r#"
+        struct Point(x, y);
+        struct Other(x, y);
+
+        fn main() {
+            let a = Point(1, 2);
+            (a == Point(1, 2), a != Point(2, 1), a == Other(1, 2))
+        }
+        "#),
+        (true, true, false),
+    };
+
+    assert_eq! {
+        rune!((bool, bool) => r#"
+        enum Shape { Circle(r), Square { side } }
+
+        fn main() {
+            let a = Shape::Circle(1);
+            let b = Shape::Square { side: 1 };
+            (a == Shape::Circle(1), b == Shape::Square { side: 2 })
+        }
+        "#),
+        (true, false),
+    };
+}
+
+#[test]
+fn test_unsupported_comparison() {
+    assert_vm_error!(
+        r#"fn main() { let a = "a"; let b = 1; a < b }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(*op, "<");
+        }
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point(i64, i64);
+
+runestick::impl_external!(Point);
+
+impl Point {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Opaque;
+
+runestick::impl_external!(Opaque);
+
+/// Construct a context where `Point` is comparable through the `EQ` protocol,
+/// while `Opaque` isn't.
+fn point_context() -> Result<Arc<Context>> {
+    let mut module = Module::default();
+    module.ty(&["Point"]).build::<Point>()?;
+    module.ty(&["Opaque"]).build::<Opaque>()?;
+    module.inst_fn(runestick::EQ, Point::eq)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(Arc::new(context))
+}
+
+#[test]
+fn test_external_eq() -> Result<()> {
+    let context = point_context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(a, b, c, opaque) {
+            (a == b, a != c, [a] == [b], opaque == opaque, a == opaque)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm
+        .call(&["main"], (Point(1, 2), Point(1, 2), Point(2, 1), Opaque))?
+        .complete()?;
+
+    assert_eq!(
+        <(bool, bool, bool, bool, bool)>::from_value(output)?,
+        (true, true, true, true, false)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_external_eq_in_vec_functions() -> Result<()> {
+    let context = point_context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(a, b, c) {
+            ([a, c].contains_all([b]), [a, b, c].dedup().len())
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm
+        .call(&["main"], (Point(1, 2), Point(1, 2), Point(2, 1)))?
+        .complete()?;
+
+    assert_eq!(<(bool, usize)>::from_value(output)?, (true, 2));
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Release(i64, i64);
+
+runestick::impl_external!(Release);
+
+impl Release {
+    fn cmp(&self, other: &Self) -> i64 {
+        (self.0, self.1).cmp(&(other.0, other.1)) as i64
+    }
+}
+
+/// Construct a context where `Release` is comparable through the `CMP`
+/// protocol, while `Opaque` isn't.
+fn release_context() -> Result<Arc<Context>> {
+    let mut module = Module::default();
+    module.ty(&["Release"]).build::<Release>()?;
+    module.ty(&["Opaque"]).build::<Opaque>()?;
+    module.inst_fn(runestick::CMP, Release::cmp)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(Arc::new(context))
+}
+
+#[test]
+fn test_external_cmp_fallback() -> Result<()> {
+    let context = release_context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(a, b) {
+            (a < b, a <= b, a > b, a >= b, a <= a)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm
+        .call(&["main"], (Release(1, 2), Release(1, 10)))?
+        .complete()?;
+
+    assert_eq!(
+        <(bool, bool, bool, bool, bool)>::from_value(output)?,
+        (true, true, false, false, true)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_vec_sort() -> Result<()> {
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { let v = [3, 1, 2]; v.sort(); v }"#),
+        vec![1, 2, 3],
+    };
+
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { let v = ["b", `c`, "a"]; v.sort(); v }"#),
+        vec![String::from("a"), String::from("b"), String::from("c")],
+    };
+
+    assert_eq! {
+        rune!(Vec<(i64, i64)> => r#"fn main() { let v = [(2, 1), (1, 2), (1, 1)]; v.sort(); v }"#),
+        vec![(1, 1), (1, 2), (2, 1)],
+    };
+
+    assert_vm_error!(
+        r#"fn main() { let v = [1, "a"]; v.sort(); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            UnsupportedBinaryOperation { op, .. } => assert_eq!(*op, "cmp"),
+            kind => panic!("expected unsupported binary operation but got: {:?}", kind),
+        }
+    );
+
+    let context = release_context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(releases) {
+            releases.sort();
+            releases
+        }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let releases = vec![Release(1, 2), Release(0, 9), Release(1, 0)];
+    let output = vm.call(&["main"], (releases,))?.complete()?;
+
+    let releases = Vec::<Release>::from_value(output)?
+        .into_iter()
+        .map(|r| (r.0, r.1))
+        .collect::<Vec<_>>();
+    assert_eq!(releases, vec![(0, 9), (1, 0), (1, 2)]);
+
+    let vm = Vm::new(context, unit);
+    let error = vm
+        .call(&["main"], (vec![Opaque, Opaque],))?
+        .complete()
+        .unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        BadReturn { error, .. } => match error.kind() {
+            MissingProtocol { protocol, .. } => assert_eq!(protocol.name, "cmp"),
+            kind => panic!("expected missing protocol but got: {:?}", kind),
+        },
+        kind => panic!("expected missing protocol but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_vec_sort_nan() {
+    assert_vm_error!(
+        r#"fn main() { let v = [1.0, nan, 0.0]; v.sort(); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            UnsupportedBinaryOperation { op, .. } => assert_eq!(*op, "cmp"),
+            kind => panic!("expected unsupported binary operation but got: {:?}", kind),
+        }
+    );
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Unordered(i64);
+
+runestick::impl_external!(Unordered);
+
+impl Unordered {
+    /// A comparison which isn't a total order, since everything is less than
+    /// everything else.
+    fn cmp(&self, _: &Self) -> i64 {
+        -1
+    }
+}
+
+#[test]
+fn test_vec_sort_unordered() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Unordered"]).build::<Unordered>()?;
+    module.inst_fn(runestick::CMP, Unordered::cmp)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(values) {
+            values.sort();
+            values
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let values = (0..64).map(Unordered).collect::<Vec<_>>();
+    let output = vm.call(&["main"], (values,))?.complete()?;
+
+    let mut values = Vec::<Unordered>::from_value(output)?
+        .into_iter()
+        .map(|v| v.0)
+        .collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, (0..64).collect::<Vec<_>>());
+    Ok(())
+}
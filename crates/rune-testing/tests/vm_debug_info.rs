@@ -0,0 +1,119 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Inst, Vm, VmErrorKind};
+use std::sync::Arc;
+
+#[test]
+fn test_strip_debug() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (mut unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(n) {
+            if n > 0 {
+                n * 2
+            } else {
+                n / 0
+            }
+        }
+        "#,
+    )?;
+
+    assert!(unit.debug_info().is_some());
+    unit.strip_debug();
+    assert!(unit.debug_info().is_none());
+
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let output = vm.call(&["main"], (21i64,))?.complete()?;
+    assert_eq!(i64::from_value(output)?, 42);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let error = vm.call(&["main"], (0i64,))?.complete().unwrap_err();
+    let (error, unwound) = error.into_unwound();
+
+    // NB: the error still knows where it happened, but there's no debug info
+    // to map it back to a span.
+    let (unit, ip) = unwound.expect("error to be unwound");
+    assert!(unit.debug_info().is_none());
+    assert!(unit.instruction_at(ip).is_some());
+
+    match error.kind() {
+        VmErrorKind::DivideByZero => (),
+        kind => panic!("expected divide by zero but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_span_at() -> Result<()> {
+    const SOURCE: &str = "fn main(n) {\n    n / 0\n}";
+
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Div))
+        .expect("unit to contain a division");
+
+    let span = unit.span_at(ip).expect("instruction to have a span");
+    assert_eq!(&SOURCE[span.start..span.end], "n / 0");
+
+    // The division is on the second line.
+    assert_eq!(SOURCE[..span.start].matches('\n').count() + 1, 2);
+
+    assert!(unit.span_at(usize::MAX).is_none());
+
+    // The innermost frame of an error trace points to the same span.
+    let unit = Arc::new(unit);
+    let vm = Vm::new(context.clone(), unit.clone());
+    let error = vm.call(&["main"], (1i64,))?.complete().unwrap_err();
+    let trace = error.trace().expect("error to have a trace");
+    assert_eq!(unit.span_at(trace[0].ip()), Some(span));
+
+    let (mut unit, _) = compile_source(&context, SOURCE)?;
+    unit.strip_debug();
+    assert!(unit.span_at(ip).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_function_at() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn foo(n) {
+            n * 2
+        }
+
+        fn main(n) {
+            foo(n) + 1
+        }
+        "#,
+    )?;
+
+    let mul = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Mul))
+        .expect("unit to contain a multiplication");
+
+    let add = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Add))
+        .expect("unit to contain an addition");
+
+    let (_, signature) = unit.function_at(mul).expect("ip to be in a function");
+    assert_eq!(signature.path.to_string(), "foo");
+
+    let (_, signature) = unit.function_at(add).expect("ip to be in a function");
+    assert_eq!(signature.path.to_string(), "main");
+
+    // NB: every instruction belongs to some function, but nothing is past the
+    // last instruction.
+    let len = unit.iter_instructions().count();
+    assert!(unit.function_at(len).is_none());
+    Ok(())
+}
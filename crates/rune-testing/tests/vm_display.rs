@@ -0,0 +1,59 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, ToValue, Vm};
+use std::fmt;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy)]
+struct Point(i64, i64);
+
+runestick::impl_external!(Point);
+
+impl Point {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "({}, {})", self.0, self.1)
+    }
+}
+
+fn context() -> Result<Arc<Context>> {
+    let mut module = Module::default();
+    module.ty(&["Point"]).build::<Point>()?;
+    module.inst_fn(runestick::STRING_DISPLAY, Point::display)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(Arc::new(context))
+}
+
+#[test]
+fn test_external_display() -> Result<()> {
+    let context = context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(p, n) {
+            `point: {p}, n: {n}`
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let output = vm.call(&["main"], (Point(1, 2), 3))?.complete()?;
+    assert_eq!(String::from_value(output)?, "point: (1, 2), n: 3");
+
+    let value = Point(3, 4).to_value()?;
+    assert_eq!(value.format_display(&context)?, "(3, 4)");
+    Ok(())
+}
+
+#[test]
+fn test_missing_display() {
+    assert_vm_error!(
+        r#"fn main() { let v = [1, 2]; `v: {v}` }"#,
+        MissingProtocol { protocol, actual } => {
+            assert_eq!(protocol.name, "string_display");
+            assert_eq!(actual.to_string(), "Vec");
+        }
+    );
+}
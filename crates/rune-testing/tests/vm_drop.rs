@@ -0,0 +1,154 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Vm};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Countdown {
+    remaining: i64,
+    drops: Arc<AtomicUsize>,
+}
+
+runestick::impl_external!(Countdown);
+
+impl Countdown {
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn next(&mut self) -> Option<i64> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        Some(self.remaining)
+    }
+
+    fn drop(&self) {
+        self.drops.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn run_countdown(source: &str, remaining: i64) -> Result<(i64, usize)> {
+    let mut module = Module::default();
+    module.ty(&["Countdown"]).build::<Countdown>()?;
+    module.inst_fn(runestick::INTO_ITER, Countdown::into_iter)?;
+    module.inst_fn(runestick::NEXT, Countdown::next)?;
+    module.inst_fn(runestick::DROP, Countdown::drop)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(&context, source)?;
+
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let countdown = Countdown {
+        remaining,
+        drops: drops.clone(),
+    };
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let output = i64::from_value(vm.call(&["main"], (countdown,))?.complete()?)?;
+    Ok((output, drops.load(Ordering::SeqCst)))
+}
+
+#[test]
+fn test_drop_for_iterator() -> Result<()> {
+    let source = r#"
+    fn main(countdown) {
+        let sum = 0;
+
+        for n in countdown {
+            sum = sum + n;
+        }
+
+        sum
+    }
+    "#;
+
+    assert_eq!(run_countdown(source, 4)?, (6, 1));
+    Ok(())
+}
+
+#[test]
+fn test_drop_for_iterator_on_break() -> Result<()> {
+    let source = r#"
+    fn main(countdown) {
+        let sum = 0;
+
+        for n in countdown {
+            if n == 1 {
+                break;
+            }
+
+            sum = sum + n;
+        }
+
+        sum
+    }
+    "#;
+
+    assert_eq!(run_countdown(source, 4)?, (5, 1));
+    Ok(())
+}
+
+#[test]
+fn test_drop_for_iterator_on_return() -> Result<()> {
+    let source = r#"
+    fn main(countdown) {
+        for n in countdown {
+            return n;
+        }
+
+        0
+    }
+    "#;
+
+    assert_eq!(run_countdown(source, 4)?, (3, 1));
+    Ok(())
+}
+
+#[test]
+fn test_drop_for_iterator_held_by_locals() -> Result<()> {
+    // NB: the protocol is called once for the value, even though several
+    // slots referenced it.
+    let source = r#"
+    fn main(countdown) {
+        let a = countdown;
+        let b = a;
+        let sum = 0;
+
+        for n in b {
+            sum = sum + n;
+        }
+
+        sum
+    }
+    "#;
+
+    assert_eq!(run_countdown(source, 4)?, (6, 1));
+    Ok(())
+}
+
+#[test]
+fn test_drop_not_called_for_locals() -> Result<()> {
+    // NB: only the iterator of a `for` loop is finalized, locals going out of
+    // scope are not.
+    let source = r#"
+    fn main(countdown) {
+        let a = countdown;
+
+        {
+            let b = a;
+        }
+
+        0
+    }
+    "#;
+
+    assert_eq!(run_countdown(source, 4)?, (0, 0));
+    Ok(())
+}
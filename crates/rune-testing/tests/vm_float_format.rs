@@ -0,0 +1,53 @@
+use rune_testing::*;
+use runestick::{Context, FloatFormat, FromValue, Output, Vm};
+use std::sync::Arc;
+
+fn render(float_format: FloatFormat) -> Result<String> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn third() { `{1.0 / 3.0}` }
+
+        async fn main() {
+            `{1.0} {third().await}`
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_float_format(float_format);
+    let output = block_on(vm.call(&["main"], ())?.async_complete())?;
+    Ok(String::from_value(output)?)
+}
+
+fn render_dbg(float_format: FloatFormat) -> Result<String> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, r#"fn main() { dbg([1.0, 1.0 / 3.0]) }"#)?;
+
+    let output = Output::new();
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_output(output.clone());
+    vm.set_float_format(float_format);
+    vm.call(&["main"], ())?.complete()?;
+    Ok(String::from_utf8(output.take()).expect("utf-8 output"))
+}
+
+#[test]
+fn test_float_format() -> Result<()> {
+    assert_eq!(render(FloatFormat::Shortest)?, "1.0 0.3333333333333333");
+    assert_eq!(render(FloatFormat::Fixed(2))?, "1.00 0.33");
+    assert_eq!(render(FloatFormat::Fixed(0))?, "1 0");
+    Ok(())
+}
+
+#[test]
+fn test_float_format_dbg() -> Result<()> {
+    assert_eq!(
+        render_dbg(FloatFormat::Shortest)?,
+        "[1.0, 0.3333333333333333]\n"
+    );
+    assert_eq!(render_dbg(FloatFormat::Fixed(2))?, "[1.00, 0.33]\n");
+    assert_eq!(render_dbg(FloatFormat::Fixed(0))?, "[1, 0]\n");
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use rune_testing::*;
+use runestick::{Context, FloatMode, FromValue, Inst, Module, Vm, VmError, VmErrorKind};
+use std::sync::Arc;
+
+fn sin(value: f64) -> Result<f64, VmError> {
+    FloatMode::check_reproducible("sin")?;
+    Ok(value.sin())
+}
+
+fn context() -> Result<Arc<Context>> {
+    let mut module = Module::default();
+    module.inst_fn("sin", sin)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    Ok(Arc::new(context))
+}
+
+#[test]
+fn test_deterministic_float_mode() -> Result<()> {
+    let context = context()?;
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(zero, x) {
+            let a = x + 0.2;
+            let b = a * 3.0 / 7.0;
+            let c = b - x / 3.0;
+            let d = c % 0.03;
+            let undefined = zero / zero;
+
+            let acc = x;
+
+            for i in 0..10 {
+                acc = acc * 1.1 + b;
+            }
+
+            [a, b, c, d, acc, undefined, -undefined, undefined * -1.0, zero * (1.0 / zero)]
+        }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_float_mode(FloatMode::Deterministic);
+    let output = vm.call(&["main"], (0.0f64, 0.1f64))?.complete()?;
+    let output = Vec::<f64>::from_value(output)?;
+
+    let x = 0.1f64;
+    let a = x + 0.2;
+    let b = a * 3.0 / 7.0;
+    let c = b - x / 3.0;
+    let d = c % 0.03;
+
+    let mut acc = x;
+
+    for _ in 0..10 {
+        acc = acc * 1.1 + b;
+    }
+
+    // NB: every NaN is the same canonical NaN, regardless of how it was
+    // produced or what its sign would otherwise be.
+    let nan = f64::NAN;
+    let expected = vec![a, b, c, d, acc, nan, nan, nan, nan];
+
+    assert_eq!(
+        output.iter().map(|f| f.to_bits()).collect::<Vec<_>>(),
+        expected.iter().map(|f| f.to_bits()).collect::<Vec<_>>(),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_non_deterministic_operation() -> Result<()> {
+    let context = context()?;
+    let (unit, _) = compile_source(&context, r#"fn main(x) { x.sin() }"#)?;
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let output = vm.call(&["main"], (0.0f64,))?.complete()?;
+    assert_eq!(f64::from_value(output)?, 0.0);
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_float_mode(FloatMode::Deterministic);
+    let error = vm.call(&["main"], (0.0f64,))?.complete().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::BadReturn { error, .. } => match error.kind() {
+            VmErrorKind::NonDeterministicFloatOperation { op } => assert_eq!(*op, "sin"),
+            kind => panic!("expected non-deterministic operation but got: {:?}", kind),
+        },
+        kind => panic!("expected bad return but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_deterministic_folded_nan() -> Result<()> {
+    let context = context()?;
+
+    let (mut unit, _) = compile_source(
+        &context,
+        r#"fn main() { [0.0 / 0.0, -(0.0 / 0.0), -nan, 0.0 * inf] }"#,
+    )?;
+
+    unit.fold_constants();
+
+    // NB: every NaN is folded into a constant by the host.
+    assert!(unit
+        .iter_instructions()
+        .all(|inst| !matches!(inst, Inst::Div | Inst::Neg | Inst::Mul)));
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_float_mode(FloatMode::Deterministic);
+    let output = vm.call(&["main"], ())?.complete()?;
+    let output = Vec::<f64>::from_value(output)?;
+
+    assert_eq!(
+        output.iter().map(|f| f.to_bits()).collect::<Vec<_>>(),
+        vec![f64::NAN.to_bits(); 4],
+    );
+
+    Ok(())
+}
@@ -231,6 +231,71 @@ fn test_loop() {
         },
         10,
     };
+
+    // NB: the stack is the same height at the end of the loop no matter how
+    // many locals are in scope when breaking, or if the loop is continued.
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let n = 0;
+
+                let total = 1 + loop {
+                    n += 1;
+                    let a = n * 2;
+                    let b = a + 1;
+
+                    if n % 2 == 0 {
+                        continue;
+                    }
+
+                    if b > 10 {
+                        let c = b * 10;
+                        break c;
+                    }
+                };
+
+                total + n
+            }
+            "#
+        },
+        1 + 110 + 5,
+    };
+}
+
+#[test]
+fn test_for_vec() {
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let sum = 0;
+
+                for n in [1, 2, 3, 4] {
+                    sum += n;
+                }
+
+                sum
+            }
+            "#
+        },
+        10,
+    };
+
+    let output: i64 = run(
+        &["main"],
+        (vec![10i64, 20, 30],),
+        r#"
+        fn main(values) {
+            let sum = 0;
+            for n in values { sum += n; }
+            sum
+        }
+        "#,
+    )
+    .expect("program to run");
+
+    assert_eq!(output, 60);
 }
 
 #[test]
@@ -492,6 +557,59 @@ fn test_vec_match() {
     };
 }
 
+#[test]
+fn test_rest_patterns() {
+    assert_eq! {
+        rune!((i64, Vec<i64>) => r#"fn main() { match [1, 2, 3] { [first, ..rest] => (first, rest) } }"#),
+        (1, vec![2, 3]),
+    };
+
+    assert_eq! {
+        rune!((Vec<i64>, i64) => r#"fn main() { match [1, 2, 3] { [..init, last] => (init, last) } }"#),
+        (vec![1, 2], 3),
+    };
+
+    assert_eq! {
+        rune!((i64, i64) => r#"fn main() { match [1, 2, 3, 4] { [a, .., b] => (a, b) } }"#),
+        (1, 4),
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { match [1, 2, 3, 4] { [1, ..middle, 4] => middle, _ => [] } }"#),
+        vec![2, 3],
+    };
+
+    // NB: the rest pattern can match nothing, but the patterns around it
+    // still need their elements.
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { match [1, 2] { [a, ..rest, b] => rest, _ => [0] } }"#),
+        Vec::<i64>::new(),
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { match [1] { [a, .., b] => true, _ => false } }"#),
+        false,
+    };
+
+    assert_eq! {
+        rune!((i64, (i64, i64)) => r#"fn main() { match (1, 2, 3) { (a, ..rest) => (a, rest) } }"#),
+        (1, (2, 3)),
+    };
+
+    assert_eq! {
+        rune!((i64, (i64,), i64) => r#"
+        struct Foo(a, b, c);
+
+        fn main() {
+            match Foo(1, 2, 3) {
+                Foo(a, ..rest, c) => (a, rest, c),
+            }
+        }
+        "#),
+        (1, (2,), 3),
+    };
+}
+
 #[test]
 fn test_object_match() {
     assert_eq! {
@@ -616,6 +734,69 @@ fn test_if_pattern() {
     };
 }
 
+#[test]
+fn test_if_let_option_and_result() {
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let a = if let Some(x) = Some(20) { x + 1 } else { 0 };
+                let b = if let Some(x) = None { x } else { 2 };
+                let c = if let Ok(v) = Ok(10) { v * 2 } else { 0 };
+                let d = if let Ok(v) = Err(10) { v } else { 3 };
+                a + b + c + d
+            }
+            "#
+        },
+        46,
+    };
+
+    // Bindings from the pattern don't leak into the else branch.
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let x = 1;
+
+                if let Some(x) = None {
+                    x
+                } else if let Err(x) = Ok(2) {
+                    x
+                } else {
+                    x
+                }
+            }
+            "#
+        },
+        1,
+    };
+}
+
+#[test]
+fn test_while_let_scope() {
+    // Bindings from the pattern only live inside of the loop body.
+    assert_eq! {
+        rune! {
+            (i64, i64) => r#"
+            fn numbers() { yield 1; yield 2; yield 3; }
+
+            fn main() {
+                let gen = numbers();
+                let x = 10;
+                let sum = 0;
+
+                while let Some(x) = gen.next() {
+                    sum += x;
+                }
+
+                (x, sum)
+            }
+            "#
+        },
+        (10, 6),
+    };
+}
+
 #[test]
 fn test_break_label() {
     assert_eq! {
@@ -651,6 +832,79 @@ fn test_break_label() {
     };
 }
 
+#[test]
+fn test_continue_label() {
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let out = 0;
+
+                'outer:
+                for a in [1, 2, 3, 4] {
+                    let skip = a * 10;
+
+                    for b in [1, 2, 3] {
+                        if a == 2 {
+                            continue 'outer;
+                        }
+
+                        if b == 2 {
+                            continue;
+                        }
+
+                        out += a * b;
+                    }
+
+                    out += skip;
+                }
+
+                out
+            }
+            "#
+        },
+        // NB: a = 2 is skipped entirely, and b = 2 is skipped for the rest.
+        (1 + 3) + 10 + (3 + 9) + 30 + (4 + 12) + 40,
+    };
+
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let n = 0;
+                let out = 0;
+
+                'outer:
+                while n < 5 {
+                    n += 1;
+                    let m = 0;
+
+                    loop {
+                        m += 1;
+
+                        if m > n {
+                            continue 'outer;
+                        }
+
+                        if let Some(v) = Some(m) {
+                            if v % 2 == 0 {
+                                continue;
+                            }
+
+                            out += v;
+                        }
+                    }
+                }
+
+                out
+            }
+            "#
+        },
+        // NB: the sum of all odd numbers up to n, for n in 1..=5.
+        1 + 1 + 4 + 4 + 9,
+    };
+}
+
 #[test]
 fn test_literal() {
     assert_eq! {
@@ -710,6 +964,31 @@ fn test_template_string() {
     };
 }
 
+#[test]
+fn test_template_string_escapes_and_nesting() {
+    assert_eq! {
+        rune!(String => r#"fn main() { `\{literal\} and \`backticks\`` }"#),
+        "{literal} and `backticks`",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let o = #{a: 1}; `{#{b: o.a + 1}.b} {"}"} {`{"{"}`}` }"#),
+        "2 } {",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { `{1 + 2} {1.5} {-7}` }"#),
+        "3 1.5 -7",
+    };
+
+    // NB: quotes and braces in character and raw string literals don't
+    // affect where an expression ends.
+    assert_eq! {
+        rune!(String => r##"fn main() { `{if '"' == 'a' { 1 } else { 0 }} {if '\'' == '}' { 1 } else { 2 }} {r#"}"#}` }"##),
+        "0 2 }",
+    };
+}
+
 #[test]
 fn test_variants_as_functions() {
     assert_eq! {
@@ -880,3 +1159,44 @@ fn test_index_get() {
         32,
     };
 }
+
+#[test]
+fn test_index_set_cycle() {
+    assert_vm_error!(
+        r#"
+        fn main() {
+            let object = #{};
+            object["self"] = object;
+        }
+        "#,
+        IndexSetCycle { target } => {
+            assert_eq!(target.to_string(), "Object");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        fn main() {
+            let vec = [1, 2, 3];
+            vec[0] = vec;
+        }
+        "#,
+        IndexSetCycle { target } => {
+            assert_eq!(target.to_string(), "Vec");
+        }
+    );
+
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            fn main() {
+                let a = #{};
+                let b = #{value: 42};
+                a["b"] = b;
+                a["b"]["value"]
+            }
+            "#
+        },
+        42,
+    };
+}
@@ -1,4 +1,6 @@
 use rune_testing::*;
+use runestick::{Context, FromValue, Generator, Output, Vm};
+use std::sync::Arc;
 
 #[test]
 fn test_simple_generator() {
@@ -61,3 +63,95 @@ fn test_resume() {
         6,
     };
 }
+
+#[test]
+fn test_yield_then_complete_with_unit() {
+    assert_eq! {
+        rune! {
+            (Vec<i64>, bool) => r#"
+            use std::generator::GeneratorState;
+
+            fn foo() { yield 1; yield 2; yield 3; }
+
+            fn main() {
+                let gen = foo();
+                let out = [];
+
+                loop {
+                    match gen.resume(()) {
+                        GeneratorState::Yielded(value) => out.push(value),
+                        GeneratorState::Complete(value) => return (out, value == ()),
+                    }
+                }
+            }
+            "#
+        },
+        (vec![1, 2, 3], true),
+    };
+}
+
+#[test]
+fn test_generator_from_rust() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn numbers(n) {
+            while n > 0 {
+                yield n;
+                n = n - 1;
+            }
+        }
+
+        fn main(n) {
+            numbers(n)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], (3i64,))?.complete()?;
+    let mut generator = Generator::from_value(output)?;
+
+    let mut values = Vec::new();
+
+    while let Some(value) = generator.next()? {
+        values.push(i64::from_value(value)?);
+    }
+
+    assert_eq!(values, vec![3, 2, 1]);
+    Ok(())
+}
+
+#[test]
+fn test_generator_is_lazy() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn foo() {
+            println("started");
+            yield 1;
+            println("resumed");
+        }
+
+        fn main() {
+            foo()
+        }
+        "#,
+    )?;
+
+    let output = Output::new();
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_output(output.clone());
+
+    let mut generator = Generator::from_value(vm.call(&["main"], ())?.complete()?)?;
+    assert!(output.take().is_empty());
+
+    assert_eq!(generator.next()?.map(i64::from_value).transpose()?, Some(1));
+    assert_eq!(String::from_utf8(output.take())?, "started\n");
+
+    assert!(generator.next()?.is_none());
+    assert_eq!(String::from_utf8(output.take())?, "resumed\n");
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use rune_testing::*;
+use runestick::{
+    BudgetOutcome, Context, FromValue, Hash, Item, LoopDetector, Unit, Vm, VmErrorKind,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[test]
+fn test_error_hook() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn divide(a, b) { a / b }
+        async fn main() { divide(1, 0).await }
+        "#,
+    )?;
+
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    let mut vm = Vm::new(context, Arc::new(unit));
+
+    vm.set_error_hook(Box::new({
+        let observed = observed.clone();
+
+        move |error| {
+            // NB: the hook is called as soon as the error is raised, before
+            // it's been unwound.
+            assert!(!error.is_unwound());
+            observed.borrow_mut().push(error.to_string());
+        }
+    }));
+
+    let error = block_on(vm.call(&["main"], ())?.async_complete()).unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::DivideByZero => (),
+        kind => panic!("expected divide by zero but got: {:?}", kind),
+    }
+
+    // NB: the error is only observed once, even though it propagated out of
+    // the async function.
+    assert_eq!(&*observed.borrow(), &[String::from("division by zero")]);
+    Ok(())
+}
+
+#[test]
+fn test_trace_hook() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn add(a, b) { a + b }
+        fn main() { add(1, 2) }
+        "#,
+    )?;
+
+    let trace = Rc::new(RefCell::new(Vec::new()));
+
+    let mut vm = Vm::new(context, Arc::new(unit));
+
+    vm.set_trace_hook(Box::new({
+        let trace = trace.clone();
+
+        move |ip, inst| {
+            trace.borrow_mut().push((ip, inst.to_string()));
+        }
+    }));
+
+    let output = vm.call(&["main"], ())?.complete()?;
+    assert_eq!(i64::from_value(output)?, 3);
+
+    let add = Hash::type_hash(Item::of(&["add"]));
+
+    assert_eq!(
+        &*trace.borrow(),
+        &[
+            (5, String::from("integer 1")),
+            (6, String::from("integer 2")),
+            (7, format!("call {}, 2", add)),
+            (0, String::from("copy 0")),
+            (1, String::from("copy 1")),
+            (2, String::from("add")),
+            (3, String::from("clean 2")),
+            (4, String::from("return")),
+            (8, String::from("return")),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_loop_detector() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn count(n) { while n > 0 { n = n - 1; } }
+
+        fn finite() { let n = 0; while n < 5 { count(n); n = n + 1; } n }
+        fn infinite() { loop { count(3); } }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+    let observed = Rc::new(RefCell::new(Vec::new()));
+
+    let vm = |unit: &Arc<Unit>| {
+        let mut vm = Vm::new(context.clone(), unit.clone());
+        vm.set_loop_detector(Some(LoopDetector::new(32, 10)));
+
+        vm.set_error_hook(Box::new({
+            let observed = observed.clone();
+
+            move |error| match error.kind() {
+                VmErrorKind::LikelyInfiniteLoop { repeats, .. } => {
+                    observed.borrow_mut().push(*repeats)
+                }
+                kind => panic!("expected likely infinite loop but got: {:?}", kind),
+            }
+        }));
+
+        vm
+    };
+
+    let output = vm(&unit).call(&["finite"], ())?.complete()?;
+    assert_eq!(i64::from_value(output)?, 5);
+    assert!(observed.borrow().is_empty());
+
+    // NB: the loop in `count` doesn't hide the loop in `infinite`, which is
+    // reported exactly once.
+    let mut execution = vm(&unit).call(&["infinite"], ())?;
+
+    for _ in 0..10 {
+        match execution.run_with_budget(1000)? {
+            BudgetOutcome::Suspended => (),
+            BudgetOutcome::Completed(..) => panic!("expected loop to never complete"),
+        }
+    }
+
+    assert_eq!(&*observed.borrow(), &[10]);
+    Ok(())
+}
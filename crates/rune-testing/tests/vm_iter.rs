@@ -0,0 +1,260 @@
+use rune_testing::*;
+use runestick::Iter;
+
+#[test]
+fn test_host_iter() -> Result<()> {
+    let output: i64 = run(
+        &["main"],
+        (Iter::new(0..10i64),),
+        r#"
+        fn main(it) {
+            let sum = 0;
+
+            for n in it {
+                sum += n;
+            }
+
+            sum
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, 45);
+
+    let output: (Option<String>, Option<String>, Option<String>) = run(
+        &["main"],
+        (Iter::new(vec!["a", "b"].into_iter().map(String::from)),),
+        r#"
+        fn main(it) {
+            (it.next(), it.next(), it.next())
+        }
+        "#,
+    )?;
+
+    assert_eq!(
+        output,
+        (Some(String::from("a")), Some(String::from("b")), None)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_host_iter_is_lazy() -> Result<()> {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let produced = Rc::new(Cell::new(0));
+
+    let iter = Iter::new({
+        let produced = produced.clone();
+
+        (0..).map(move |n: i64| {
+            produced.set(produced.get() + 1);
+            n
+        })
+    });
+
+    let output: i64 = run(
+        &["main"],
+        (iter,),
+        r#"
+        fn main(it) {
+            for n in it {
+                if n == 3 {
+                    return n;
+                }
+            }
+
+            0
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, 3);
+    assert_eq!(produced.get(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_fused_adapters() -> Result<()> {
+    let (output, log): (Vec<i64>, Vec<(String, i64)>) = run(
+        &["main"],
+        (),
+        r#"
+        fn main() {
+            let log = [];
+
+            let output = [1, 2, 3, 4]
+                .iter()
+                .map(|n| { log.push(("map", n)); n * 10 })
+                .filter(|n| { log.push(("filter", n)); n != 20 })
+                .collect();
+
+            (output, log)
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, vec![10, 30, 40]);
+
+    // NB: the adapters are fused, so every value passes through the whole
+    // chain before the next one is produced.
+    let log = log
+        .iter()
+        .map(|(op, n)| (op.as_str(), *n))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        log,
+        vec![
+            ("map", 1),
+            ("filter", 10),
+            ("map", 2),
+            ("filter", 20),
+            ("map", 3),
+            ("filter", 30),
+            ("map", 4),
+            ("filter", 40),
+        ]
+    );
+
+    let output: Vec<i64> = run(
+        &["main"],
+        (Iter::new(0..10i64),),
+        r#"
+        fn main(it) {
+            it.filter(|n| n % 3 == 0).map(|n| n + 1).collect()
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, vec![1, 4, 7, 10]);
+    Ok(())
+}
+
+#[test]
+fn test_vec_map_filter() {
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3].iter().map(|n| n * 2).filter(|n| n % 2 == 0).collect() }"#),
+        vec![2, 4, 6],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3, 4].iter().filter(|n| n % 2 == 0).map(|n| n * 2).collect() }"#),
+        vec![4, 8],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [].iter().map(|n| n * 2).filter(|n| n % 2 == 0).collect() }"#),
+        Vec::<i64>::new(),
+    };
+}
+
+#[test]
+fn test_fold() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { [1, 2, 3, 4].iter().fold(0, |acc, n| acc + n) }"#),
+        10,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { [].iter().fold(42, |acc, n| acc + n) }"#),
+        42,
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { ["b", "c"].iter().fold("a", |acc, s| acc + s) }"#),
+        "abc",
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { [1, 2, 3].iter().map(|n| n * 2).fold(0, |acc, n| acc + n) }"#),
+        12,
+    };
+
+    // NB: errors raised by the function are propagated.
+    assert_vm_error!(
+        r#"fn main() { [1].iter().fold("a", |acc, n| acc - n) }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(*op, "-");
+        }
+    );
+}
+
+#[test]
+fn test_zip() {
+    assert_eq! {
+        rune!(Vec<(i64, String)> => r#"fn main() { [1, 2].iter().zip(["a", "b"]).collect() }"#),
+        vec![(1, String::from("a")), (2, String::from("b"))],
+    };
+
+    // NB: zipping stops at the shorter of the two.
+    assert_eq! {
+        rune!(Vec<(i64, String)> => r#"fn main() { [1, 2, 3].iter().zip(["a", "b"].iter()).collect() }"#),
+        vec![(1, String::from("a")), (2, String::from("b"))],
+    };
+
+    assert_eq! {
+        rune!(Vec<(i64, i64)> => r#"fn main() { [1].iter().zip([1, 2].iter().map(|n| n * 10)).collect() }"#),
+        vec![(1, 10)],
+    };
+
+    assert_eq! {
+        rune!(Vec<(i64, i64)> => r#"fn main() { [].iter().zip([1, 2]).collect() }"#),
+        vec![],
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let out = 0;
+
+            for pair in [1, 2, 3].iter().map(|n| n * 10).zip([4, 5, 6]) {
+                out += pair.0 * pair.1;
+            }
+
+            out
+        }
+        "#),
+        10 * 4 + 20 * 5 + 30 * 6,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { [1].iter().zip(2) }"#,
+        BadReturn { error, .. } => {
+            assert!(matches!(error.kind(), BadArgumentType { arg: 1, .. }));
+        }
+    );
+}
+
+#[test]
+fn test_enumerate() {
+    assert_eq! {
+        rune!(Vec<(i64, String)> => r#"fn main() { ["a", "b", "c"].iter().enumerate().collect() }"#),
+        vec![
+            (0, String::from("a")),
+            (1, String::from("b")),
+            (2, String::from("c")),
+        ],
+    };
+
+    assert_eq! {
+        rune!(Vec<(i64, i64)> => r#"fn main() { [].iter().enumerate().collect() }"#),
+        vec![],
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let out = 0;
+
+            for entry in [1, 2, 3].iter().filter(|n| n != 2).enumerate() {
+                out += entry.0 * entry.1;
+            }
+
+            out
+        }
+        "#),
+        0 * 1 + 1 * 3,
+    };
+}
@@ -0,0 +1,83 @@
+use rune_testing::*;
+
+#[test]
+fn test_map_index() {
+    assert_eq! {
+        rune!((String, String, String, i64) => r#"
+        use std::map::Map;
+
+        fn main() {
+            let map = Map::new();
+            map[1] = "one";
+            map[(1, 2)] = "pair";
+            map["key"] = "string";
+            map[[1, 2]] = "vec";
+            map[1] = "uno";
+
+            (map[1], map[(1, 2)], map["key"], map.len())
+        }
+        "#),
+        (
+            String::from("uno"),
+            String::from("pair"),
+            String::from("string"),
+            4,
+        ),
+    };
+}
+
+#[test]
+fn test_map_functions() {
+    assert_eq! {
+        rune!((Option<i64>, Option<i64>, bool, Option<i64>, bool, i64) => r#"
+        use std::map::Map;
+
+        fn main() {
+            let map = Map::new();
+            let previous = map.insert(Some(1), 10);
+            map.insert(2, 20);
+
+            let sum = 0;
+
+            for entry in map {
+                sum += entry.1;
+            }
+
+            let removed = map.remove(2);
+            (previous, map.get(Some(1)), map.contains_key(2), removed, map.is_empty(), sum)
+        }
+        "#),
+        (None, Some(10), false, Some(20), false, 30),
+    };
+}
+
+#[test]
+fn test_map_errors() {
+    assert_vm_error!(
+        r#"
+        use std::map::Map;
+
+        fn main() {
+            let map = Map::new();
+            map[1.5] = 42;
+        }
+        "#,
+        UnsupportedHashKey { actual } => {
+            assert_eq!(actual.to_string(), "float");
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        use std::map::Map;
+
+        fn main() {
+            let map = Map::new();
+            map[(1, "a")]
+        }
+        "#,
+        MissingKey { key } => {
+            assert_eq!(key.to_string(), "(1, \"a\")");
+        }
+    );
+}
@@ -64,3 +64,118 @@ fn test_path_type_match() {
         true,
     };
 }
+
+#[test]
+fn test_float_match() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match 1.5 { 1.0 => 1, 1.5 => 2, _ => 3 } }"#),
+        2,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match 2.5 { 1.0 => 1, 1.5 => 2, _ => 3 } }"#),
+        3,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"fn main() { match 1 { 1.0 => 1, _ => 2 } }"#),
+        2,
+    };
+}
+
+#[test]
+fn test_match_guard() {
+    assert_eq! {
+        rune!(i64 => r#"
+        fn classify(n) {
+            match n {
+                Some(x) if x > 0 => 1,
+                Some(x) if x < 0 => 2,
+                Some(x) => 3,
+                None => 4,
+            }
+        }
+
+        fn main() {
+            classify(Some(5)) * 1000 + classify(Some(-5)) * 100 + classify(Some(0)) * 10 + classify(None)
+        }
+        "#),
+        1234,
+    };
+
+    // NB: variables bound by the pattern are visible to the guard, but don't
+    // leak into later arms when the guard fails.
+    assert_eq! {
+        rune!(i64 => r#"
+        fn main() {
+            let x = 10;
+
+            match (1, 2) {
+                (x, y) if x > y => x,
+                (a, b) => x + a + b,
+            }
+        }
+        "#),
+        13,
+    };
+}
+
+#[test]
+fn test_or_pattern() {
+    assert_eq! {
+        rune!(i64 => r#"
+        fn classify(n) {
+            match n {
+                1 | 2 | 3 => 1,
+                4 | 5 => 2,
+                _ => 3,
+            }
+        }
+
+        fn main() {
+            classify(1) * 100000 + classify(3) * 10000 + classify(4) * 1000 + classify(5) * 100 + classify(6) * 10 + classify(0)
+        }
+        "#),
+        112_233,
+    };
+
+    assert_eq! {
+        rune!(i64 => r#"
+        fn value(v) {
+            match v {
+                Some(a) | Ok(a) => a,
+                _ => 0,
+            }
+        }
+
+        fn main() {
+            value(Some(1)) * 100 + value(Ok(2)) * 10 + value(Err(3))
+        }
+        "#),
+        120,
+    };
+
+    // NB: alternatives can bind their variables in a different order and
+    // through different temporaries, and combine with guards.
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn value(v) {
+            match v {
+                (a, 0, b) | (0, [b, a], _) if a != b => a * 10 + b,
+                #{x: a, y: b} | [[b], a] => a * 100 + b,
+                _ => 0,
+            }
+        }
+
+        fn main() {
+            let a = value((1, 0, 2));
+            let b = value((0, [4, 3], ()));
+            let c = value((0, [3, 3], ()));
+            let d = value(#{x: 5, y: 6});
+            let e = value([[8], 7]);
+            [a, b, c, d, e]
+        }
+        "#),
+        vec![12, 34, 0, 506, 708],
+    };
+}
@@ -0,0 +1,62 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, IterationOrder, Output, Vm};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn keys(object) {
+    let out = [];
+
+    for entry in object {
+        let (key, value) = entry;
+        out.push(key);
+    }
+
+    out
+}
+
+fn first() {
+    let object = #{};
+    object.insert("c", 3);
+    object.insert("a", 1);
+    object.insert("b", 2);
+    object
+}
+
+fn second() {
+    let object = #{};
+    object.insert("b", 2);
+    object.insert("c", 3);
+    object.insert("a", 1);
+    object
+}
+
+fn main() {
+    dbg(first());
+    dbg(second());
+    (keys(first()), keys(second()))
+}
+"#;
+
+#[test]
+fn test_sorted_object_iteration() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+
+    let output = Output::new();
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_output(output.clone());
+    vm.set_object_iteration_order(IterationOrder::Sorted);
+
+    let (first, second) =
+        <(Vec<String>, Vec<String>)>::from_value(vm.call(&["main"], ())?.complete()?)?;
+
+    assert_eq!(first, vec!["a", "b", "c"]);
+    assert_eq!(first, second);
+
+    assert_eq!(
+        String::from_utf8(output.take())?,
+        "{\"a\": 1, \"b\": 2, \"c\": 3}\n{\"a\": 1, \"b\": 2, \"c\": 3}\n"
+    );
+
+    Ok(())
+}
@@ -29,3 +29,47 @@ fn test_option() {
         1,
     };
 }
+
+#[test]
+fn test_option_expect() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { Some(42).expect("needed") }"#),
+        42,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { None.expect("needed"); }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "needed");
+        }
+    );
+}
+
+#[test]
+fn test_option_flatten() {
+    assert_eq! {
+        rune!(Option<i64> => r#"fn main() { Some(Some(42)).flatten() }"#),
+        Some(42),
+    };
+
+    assert_eq! {
+        rune!(Option<i64> => r#"fn main() { Some(None).flatten() }"#),
+        None,
+    };
+
+    assert_eq! {
+        rune!(Option<i64> => r#"fn main() { None.flatten() }"#),
+        None,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { Some(42).flatten(); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            Expected { expected, actual } => {
+                assert_eq!(expected.to_string(), "Option");
+                assert_eq!(actual.to_string(), "integer");
+            }
+            kind => panic!("expected type error but got: {:?}", kind),
+        }
+    );
+}
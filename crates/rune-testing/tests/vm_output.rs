@@ -0,0 +1,37 @@
+use rune_testing::*;
+use runestick::{Context, Output, Vm};
+use std::sync::Arc;
+
+#[test]
+fn test_capture_output() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn greet(name) {
+            print("hello ");
+            println(name);
+        }
+
+        async fn main() {
+            println("first line");
+            greet("world").await;
+        }
+        "#,
+    )?;
+
+    let output = Output::new();
+
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_output(output.clone());
+    assert_eq!(vm.take_output(), Some(Vec::new()));
+
+    block_on(vm.call(&["main"], ())?.async_complete())?;
+
+    assert_eq!(
+        String::from_utf8(output.take())?,
+        "first line\nhello world\n"
+    );
+    assert!(output.take().is_empty());
+    Ok(())
+}
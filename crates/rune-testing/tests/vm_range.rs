@@ -0,0 +1,203 @@
+use rune_testing::*;
+
+#[test]
+fn test_range_iter() {
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+
+            for i in 0..5 {
+                out.push(i);
+            }
+
+            out
+        }
+        "#),
+        vec![0, 1, 2, 3, 4],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+
+            for i in 0..=5 {
+                out.push(i);
+            }
+
+            out
+        }
+        "#),
+        vec![0, 1, 2, 3, 4, 5],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+            let n = 2;
+
+            for i in n - 1..n * 2 {
+                out.push(i);
+            }
+
+            out
+        }
+        "#),
+        vec![1, 2, 3],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+
+            for i in 10.. {
+                if i == 13 {
+                    break;
+                }
+
+                out.push(i);
+            }
+
+            out
+        }
+        "#),
+        vec![10, 11, 12],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+
+            for i in 9223372036854775806.. {
+                out.push(i);
+            }
+
+            out
+        }
+        "#),
+        vec![i64::MAX - 1, i64::MAX],
+    };
+}
+
+#[test]
+fn test_empty_ranges() {
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+            for i in 5..5 { out.push(i); }
+            for i in 5..0 { out.push(i); }
+            for i in 5..=4 { out.push(i); }
+            out
+        }
+        "#),
+        Vec::<i64>::new(),
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let out = [];
+            for i in 5..=5 { out.push(i); }
+            out
+        }
+        "#),
+        vec![5],
+    };
+}
+
+#[test]
+fn test_range_values() {
+    assert_eq! {
+        rune!((bool, bool, bool, bool) => r#"
+        fn main() {
+            let r = 0..10;
+            (r.contains(0), r.contains(10), (0..=10).contains(10), (..0).contains(-1))
+        }
+        "#),
+        (true, false, true, true),
+    };
+
+    assert_eq! {
+        rune!((bool, bool) => r#"
+        fn main() {
+            ((0..10) == (0..10), (0..10) == (0..=10))
+        }
+        "#),
+        (true, false),
+    };
+
+    assert_eq! {
+        rune!(bool => r#"
+        fn main() {
+            let r = ();
+            r = 1..3;
+            r.contains(2)
+        }
+        "#),
+        true,
+    };
+}
+
+#[test]
+fn test_range_slicing() {
+    assert_eq! {
+        rune!((Vec<i64>, Vec<i64>, Vec<i64>, Vec<i64>, Vec<i64>, Vec<i64>) => r#"
+        fn main() {
+            let v = [1, 2, 3, 4];
+            (v[1..3], v[1..=3], v[..2], v[2..], v[..], v[2..2])
+        }
+        "#),
+        (
+            vec![2, 3],
+            vec![2, 3, 4],
+            vec![1, 2],
+            vec![3, 4],
+            vec![1, 2, 3, 4],
+            vec![],
+        ),
+    };
+}
+
+#[test]
+fn test_range_errors() {
+    assert_vm_error!(
+        r#"
+        fn main() {
+            let v = [1, 2, 3];
+            v[1..10]
+        }
+        "#,
+        IndexRangeOutOfBounds { len, .. } => {
+            assert_eq!(*len, 3);
+        }
+    );
+
+    assert_vm_error!(
+        r#"
+        fn main() {
+            let v = [1, 2, 3];
+            v[2..1]
+        }
+        "#,
+        IndexRangeOutOfBounds { .. } => {}
+    );
+
+    assert_vm_error!(
+        r#"
+        fn main() {
+            for i in ..3 {}
+        }
+        "#,
+        BadReturn { error, .. } => match error.kind() {
+            UnsupportedRangeIter { range } => {
+                assert_eq!(range.to_string(), "..3");
+            }
+            kind => panic!("expected unsupported range iter but got: {:?}", kind),
+        }
+    );
+}
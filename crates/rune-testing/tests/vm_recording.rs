@@ -0,0 +1,84 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Recording, Vm, VmErrorKind};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+fn add(a, b) { a + b }
+
+fn main(n) {
+    let out = 0;
+
+    while n > 0 {
+        out = add(out, n);
+        n = n - 1;
+    }
+
+    out
+}
+"#;
+
+#[test]
+fn test_record_and_replay() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+    execution.record();
+    let output = execution.complete()?;
+    let recording = execution.take_recording().expect("a recording");
+
+    assert_eq!(i64::from_value(output)?, 10);
+    assert!(!recording.is_empty());
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+    execution.replay(recording.clone());
+    let output = execution.complete()?;
+
+    assert_eq!(i64::from_value(output)?, 10);
+    assert_eq!(execution.take_recording(), Some(recording));
+    Ok(())
+}
+
+#[test]
+fn test_replay_divergence() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, SOURCE)?;
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+    execution.record();
+    execution.complete()?;
+    let recording = execution.take_recording().expect("a recording");
+
+    // Fewer iterations of the loop takes a shorter path.
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (2i64,))?;
+    execution.replay(recording.clone());
+    let error = execution.complete().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::ReplayDivergence { .. } => (),
+        kind => panic!("expected divergence but got: {:?}", kind),
+    }
+
+    // A truncated recording is shorter than the execution.
+    let truncated = Recording::from_ips(recording.ips()[..recording.len() - 1].to_vec());
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (4i64,))?;
+    execution.replay(truncated);
+    let error = execution.complete().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::ReplayLengthMismatch { expected, actual } => {
+            assert_eq!(*expected, recording.len() - 1);
+            assert_eq!(*actual, recording.len());
+        }
+        kind => panic!("expected length mismatch but got: {:?}", kind),
+    }
+
+    Ok(())
+}
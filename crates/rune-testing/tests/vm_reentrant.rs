@@ -0,0 +1,99 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Vm, VmError};
+use std::sync::Arc;
+
+/// A native function which calls back into the script for each value.
+fn map(values: Vec<Value>, f: Function) -> Result<Vec<Value>, VmError> {
+    values.into_iter().map(|value| f.call((value,))).collect()
+}
+
+fn run_with_map<T>(source: &str) -> Result<T>
+where
+    T: FromValue,
+{
+    let mut module = Module::default();
+    module.function(&["map"], map)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(&context, source)?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+    Ok(T::from_value(output)?)
+}
+
+#[test]
+fn test_native_calls_closure() -> Result<()> {
+    let output: (i64, Vec<i64>, i64) = run_with_map(
+        r#"
+        fn main() {
+            let before = 1;
+            let offset = 10;
+            let out = map([1, 2, 3], |v| v * 2 + offset);
+            let after = 2;
+            (before, out, after)
+        }
+        "#,
+    )?;
+
+    // NB: the locals around the call are intact.
+    assert_eq!(output, (1, vec![12, 14, 16], 2));
+    Ok(())
+}
+
+#[test]
+fn test_native_calls_function() -> Result<()> {
+    let output: Vec<String> = run_with_map(
+        r#"
+        fn greet(name) {
+            `hello {name}`
+        }
+
+        fn main() {
+            map(["john", "jane"], greet)
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, vec!["hello john", "hello jane"]);
+    Ok(())
+}
+
+#[test]
+fn test_nested_native_calls() -> Result<()> {
+    let output: Vec<Vec<i64>> = run_with_map(
+        r#"
+        fn main() {
+            map([1, 2], |a| map([10, 20], |b| a + b))
+        }
+        "#,
+    )?;
+
+    assert_eq!(output, vec![vec![11, 21], vec![12, 22]]);
+    Ok(())
+}
+
+#[test]
+fn test_native_call_error() -> Result<()> {
+    let error = run_with_map::<Value>(
+        r#"
+        fn main() {
+            map([1, 2], |v| v + "nope")
+        }
+        "#,
+    )
+    .unwrap_err();
+
+    let error = error.downcast_ref::<VmError>().expect("a vm error");
+    let (kind, _) = error.kind().into_unwound_ref();
+
+    match kind {
+        UnsupportedBinaryOperation { op, .. } => assert_eq!(*op, "+"),
+        kind => panic!("expected unsupported binary operation but got: {:?}", kind),
+    }
+
+    Ok(())
+}
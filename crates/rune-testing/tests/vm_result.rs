@@ -1,4 +1,26 @@
 use rune_testing::*;
+use runestick::{Context, FromValue, Module, Vm};
+use std::convert::TryFrom as _;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Port(u16);
+
+runestick::impl_external!(Port);
+
+impl Port {
+    /// A native constructor which fails for bad input.
+    fn new(port: i64) -> Result<Self, String> {
+        match u16::try_from(port) {
+            Ok(port) if port != 0 => Ok(Self(port)),
+            _ => Err(format!("bad port `{}`", port)),
+        }
+    }
+
+    fn number(&self) -> i64 {
+        self.0 as i64
+    }
+}
 
 #[test]
 fn test_result() {
@@ -29,3 +51,100 @@ fn test_result() {
         1,
     };
 }
+
+#[test]
+fn test_result_expect() {
+    assert_eq! {
+        rune!(i64 => r#"fn main() { Ok(42).expect("needed") }"#),
+        42,
+    };
+
+    assert_vm_error!(
+        r#"fn main() { Err("bad").expect("needed"); }"#,
+        Panic { reason } => {
+            assert_eq!(reason.to_string(), "needed");
+        }
+    );
+}
+
+#[test]
+fn test_result_flatten() {
+    assert_eq! {
+        rune!(Result<i64, String> => r#"fn main() { Ok(Ok(42)).flatten() }"#),
+        Ok(42),
+    };
+
+    assert_eq! {
+        rune!(Result<i64, String> => r#"fn main() { Ok(Err("inner")).flatten() }"#),
+        Err(String::from("inner")),
+    };
+
+    assert_eq! {
+        rune!(Result<i64, String> => r#"fn main() { Err("outer").flatten() }"#),
+        Err(String::from("outer")),
+    };
+
+    assert_vm_error!(
+        r#"fn main() { Ok(42).flatten(); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            Expected { expected, actual } => {
+                assert_eq!(expected.to_string(), "Result");
+                assert_eq!(actual.to_string(), "integer");
+            }
+            kind => panic!("expected type error but got: {:?}", kind),
+        }
+    );
+}
+
+#[test]
+fn test_fallible_native_constructor() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Port"]).build::<Port>()?;
+    module.function(&["Port", "new"], Port::new)?;
+    module.inst_fn("number", Port::number)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn open(n) {
+            match Port::new(n) {
+                Ok(port) => Ok(port.number()),
+                Err(error) => Err(`caught: {error}`),
+            }
+        }
+
+        fn try_open(n) {
+            let port = Port::new(n)?;
+            Ok(port.number())
+        }
+
+        fn main() {
+            (open(8080), open(-1), try_open(0))
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    type Output = (
+        Result<i64, String>,
+        Result<i64, String>,
+        Result<i64, String>,
+    );
+
+    assert_eq!(
+        Output::from_value(output)?,
+        (
+            Ok(8080),
+            Err(String::from("caught: bad port `-1`")),
+            Err(String::from("bad port `0`")),
+        )
+    );
+
+    Ok(())
+}
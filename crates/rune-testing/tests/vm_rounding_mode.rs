@@ -0,0 +1,35 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, RoundingMode, Vm};
+use std::sync::Arc;
+
+fn convert(rounding_mode: RoundingMode) -> Result<Vec<i64>> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let out = [];
+
+            for n in [2.7, -2.7, 2.5, 3.5, -2.5] {
+                out.push(n.to_integer());
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let mut vm = Vm::new(context, Arc::new(unit));
+    vm.set_rounding_mode(rounding_mode);
+    let output = vm.call(&["main"], ())?.complete()?;
+    Ok(Vec::<i64>::from_value(output)?)
+}
+
+#[test]
+fn test_rounding_mode() -> Result<()> {
+    assert_eq!(convert(RoundingMode::Truncate)?, vec![2, -2, 2, 3, -2]);
+    assert_eq!(convert(RoundingMode::Floor)?, vec![2, -3, 2, 3, -3]);
+    assert_eq!(convert(RoundingMode::Ceil)?, vec![3, -2, 3, 4, -2]);
+    assert_eq!(convert(RoundingMode::RoundHalfEven)?, vec![3, -3, 2, 4, -2]);
+    Ok(())
+}
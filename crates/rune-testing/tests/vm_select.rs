@@ -0,0 +1,140 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Module, Vm};
+use std::sync::{Arc, Weak};
+
+/// Keeps a reference count alive for as long as it's held.
+#[derive(Debug)]
+struct Tracker(Arc<()>);
+
+runestick::impl_external!(Tracker);
+
+/// Observes whether the tracker has been released.
+#[derive(Debug)]
+struct Probe(Weak<()>);
+
+runestick::impl_external!(Probe);
+
+impl Probe {
+    fn released(&self) -> bool {
+        self.0.upgrade().is_none()
+    }
+}
+
+/// A future holding on to the tracker, which never completes.
+async fn pending(tracker: Tracker) -> i64 {
+    let _tracker = tracker;
+    std::future::pending::<()>().await;
+    0
+}
+
+#[test]
+fn test_select_default() {
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            async fn main() {
+                select {
+                    value = async { 42 } => value,
+                    default => 0,
+                }
+            }
+            "#
+        },
+        42,
+    };
+
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            async fn main() {
+                let a = async { 1 };
+                let b = async { 2 };
+                a.await;
+                b.await;
+
+                select {
+                    value = a => value,
+                    value = b => value,
+                    default => 3,
+                }
+            }
+            "#
+        },
+        3,
+    };
+}
+
+#[test]
+fn test_race() {
+    assert_eq! {
+        rune! {
+            (i64, i64) => r#"
+            async fn main() {
+                let a = async { 1 };
+                let b = async { 2 };
+                let c = async { 3 };
+                a.await;
+                c.await;
+                std::future::race([a, b, c]).await
+            }
+            "#
+        },
+        (1, 2),
+    };
+
+    assert_vm_error!(
+        r#"
+        async fn main() {
+            let a = async { 1 };
+            a.await;
+            std::future::race((a,)).await
+        }
+        "#,
+        NoPendingFutures => ()
+    );
+
+    assert_vm_error!(
+        r#"async fn main() { std::future::race([]).await }"#,
+        NoPendingFutures => ()
+    );
+}
+
+#[test]
+fn test_select_releases_abandoned_futures() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Tracker"]).build::<Tracker>()?;
+    module.ty(&["Probe"]).build::<Probe>()?;
+    module.async_function(&["pending"], pending)?;
+    module.inst_fn("released", Probe::released)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn main(probe, tracker) {
+            let before = probe.released();
+
+            let value = select {
+                value = pending(tracker) => value,
+                value = async { 1 } => value,
+            };
+
+            (before, value, probe.released())
+        }
+        "#,
+    )?;
+
+    let tracker = Arc::new(());
+    let probe = Arc::downgrade(&tracker);
+    let vm = Vm::new(context, Arc::new(unit));
+    let mut execution = vm.call(&["main"], (Probe(probe), Tracker(tracker)))?;
+    let output = block_on(execution.async_complete())?;
+
+    // NB: the losing future is released as soon as the select completes,
+    // not when the task does.
+    assert_eq!(<(bool, i64, bool)>::from_value(output)?, (false, 1, true));
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Hash, Shared, Value, Vm};
+use serde_json::json;
+use std::sync::Arc;
+
+#[test]
+fn test_serialize_json() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        struct Point { x, y }
+
+        fn main() {
+            #{
+                "integer": 1,
+                "float": 2.5,
+                "bool": true,
+                "string": `hello {1}`,
+                "vec": [1, [2, 3], ()],
+                "none": None,
+                "some": Some(4),
+                "point": Point { x: 5, y: #{"z": 6} },
+            }
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    let expected = json!({
+        "integer": 1,
+        "float": 2.5,
+        "bool": true,
+        "string": "hello 1",
+        "vec": [1, [2, 3], null],
+        "none": null,
+        "some": 4,
+        "point": {"x": 5, "y": {"z": 6}},
+    });
+
+    assert_eq!(serde_json::to_value(&output)?, expected);
+
+    // NB: typed objects come back as plain objects.
+    let value = serde_json::from_value::<Value>(expected.clone())?;
+    assert_eq!(serde_json::to_value(&value)?, expected);
+    Ok(())
+}
+
+#[test]
+fn test_serialize_json_errors() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn work() {}
+        fn future() { [work()] }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let output = vm.call(&["future"], ())?.complete()?;
+    let error = serde_json::to_string(&output).unwrap_err();
+    assert_eq!(error.to_string(), "cannot serialize futures");
+
+    let output = Value::vec(vec![Value::Type(Hash::type_hash(&["Foo"]))]);
+    let error = serde_json::to_string(&output).unwrap_err();
+    assert_eq!(error.to_string(), "cannot serialize types");
+
+    // Values which are exclusively borrowed can't be serialized.
+    let vec = Shared::new(vec![Value::Integer(1)]);
+    let value = Value::Vec(vec.clone());
+    let guard = vec.borrow_mut()?;
+    assert!(serde_json::to_string(&value).is_err());
+    drop(guard);
+    assert_eq!(serde_json::to_string(&value)?, "[1]");
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_json() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(data) {
+            (data.name, data.count + 1, data.ratio * 2.0, data.items[1], data.nested.flag)
+        }
+        "#,
+    )?;
+
+    let data = serde_json::from_str::<Value>(
+        r#"{
+            "name": "rune",
+            "count": 41,
+            "ratio": 0.25,
+            "items": [1, 2.0, null],
+            "nested": {"flag": true}
+        }"#,
+    )?;
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let output = vm.call(&["main"], (data,))?.complete()?;
+
+    assert_eq!(
+        <(String, i64, f64, f64, bool)>::from_value(output)?,
+        (String::from("rune"), 42, 0.5, 2.0, true)
+    );
+
+    // Integers which don't fit in an `i64` become big integers.
+    match serde_json::from_str::<Value>("18446744073709551615")? {
+        Value::BigInt(big_int) => assert_eq!(big_int.to_string(), "18446744073709551615"),
+        value => panic!("expected big integer but got: {:?}", value),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use rune_testing::*;
+use runestick::{
+    Context, FromValue, GeneratorState, Inst, Value, Vm, VmErrorKind, VmExecutionState,
+};
+use std::sync::Arc;
+
+#[test]
+fn test_snapshot_and_restore() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(n) {
+            let values = [1, 2];
+            let alias = values;
+            values.push(3);
+            values.push(12 / n);
+            alias
+        }
+        "#,
+    )?;
+
+    let ip = unit
+        .iter_instructions()
+        .position(|inst| matches!(inst, Inst::Integer { number: 3 }))
+        .expect("unit to push the first value");
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_breakpoint(ip);
+
+    let mut execution = vm.call(&["main"], (0i64,))?;
+
+    match execution.run_to_breakpoint()? {
+        VmExecutionState::Breakpoint { ip: at } => assert_eq!(at, ip),
+        VmExecutionState::Complete(..) => panic!("expected breakpoint"),
+    }
+
+    let snapshot = execution.vm()?.snapshot()?;
+    assert_eq!(snapshot.ip(), ip);
+
+    // NB: the first push modifies the vector before the second one errors.
+    let error = execution.run_to_breakpoint().unwrap_err();
+
+    match error.into_unwound().0.kind() {
+        VmErrorKind::DivideByZero => (),
+        kind => panic!("expected divide by zero but got: {:?}", kind),
+    }
+
+    let vm = execution.vm_mut()?;
+    vm.restore(snapshot);
+    assert_eq!(vm.ip(), ip);
+
+    let (values, alias) = match (vm.stack().at_offset(1)?, vm.stack().at_offset(2)?) {
+        (Value::Vec(values), Value::Vec(alias)) => (values.clone(), alias.clone()),
+        _ => panic!("expected vectors on the stack"),
+    };
+
+    // The vector is restored as it was, and is still shared by both variables.
+    assert_eq!(values.borrow_ref()?.len(), 2);
+    values.borrow_mut()?.push(Value::from(0i64));
+    assert_eq!(alias.borrow_ref()?.len(), 3);
+    values.borrow_mut()?.pop();
+
+    *vm.stack_mut().at_offset_mut(0)? = Value::from(4i64);
+    vm.clear_breakpoint(ip);
+
+    match execution.run_to_breakpoint()? {
+        VmExecutionState::Complete(output) => {
+            assert_eq!(Vec::<i64>::from_value(output)?, vec![1, 2, 3, 3]);
+        }
+        VmExecutionState::Breakpoint { .. } => panic!("expected completion"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_snapshot_deeply_nested() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(depth) {
+            let v = [];
+            let i = 1;
+
+            while i < depth {
+                v = [v];
+                i += 1;
+            }
+
+            yield 1;
+            v.len()
+        }
+        "#,
+    )?;
+
+    let unit = Arc::new(unit);
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (256i64,))?;
+    assert!(matches!(execution.resume()?, GeneratorState::Yielded(..)));
+    execution.vm()?.snapshot()?;
+
+    let vm = Vm::new(context, unit);
+    let mut execution = vm.call(&["main"], (257i64,))?;
+    assert!(matches!(execution.resume()?, GeneratorState::Yielded(..)));
+
+    match execution.vm()?.snapshot().unwrap_err().kind() {
+        VmErrorKind::ValueTooDeep { max } => assert_eq!(*max, 256),
+        kind => panic!("expected value too deep but got: {:?}", kind),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, GeneratorState, Hash, Unit, UnitFn, Vm, VmExecution};
+use std::sync::Arc;
+
+/// Resume the execution until it completes, resuming every yield with a unit.
+fn resume_to_end(execution: &mut VmExecution) -> Result<Value> {
+    loop {
+        match execution.resume()? {
+            GeneratorState::Yielded(..) => {
+                execution.vm_mut()?.stack_mut().push(());
+            }
+            GeneratorState::Complete(value) => return Ok(value),
+        }
+    }
+}
+
+fn setup(source: &str) -> Result<(Arc<Context>, Arc<Unit>)> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, source)?;
+    Ok((context, Arc::new(unit)))
+}
+
+#[test]
+fn test_save_and_restore_at_yield() -> Result<()> {
+    let (context, unit) = setup(
+        r#"
+        fn main() {
+            let values = [1, 2, 3];
+            let alias = values;
+            let object = #{name: "rune", score: Some(2.5)};
+            let n = 0;
+            let i = 0;
+
+            while i < values.len() {
+                yield values[i];
+                n += values[i];
+                i += 1;
+            }
+
+            alias.push(n);
+            (values, object.name, object.score, 'x', b'a')
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], ())?;
+
+    match execution.resume()? {
+        GeneratorState::Yielded(value) => assert_eq!(i64::from_value(value)?, 1),
+        GeneratorState::Complete(..) => panic!("expected yield"),
+    }
+
+    let state = execution.vm()?.save_state()?;
+
+    let mut restored = Vm::new(context, unit);
+    restored.restore_state(&state)?;
+    restored.stack_mut().push(());
+    let mut restored = restored.into_execution();
+
+    execution.vm_mut()?.stack_mut().push(());
+
+    type Output = (Vec<i64>, String, Option<f64>, char, u8);
+
+    let expected = Output::from_value(resume_to_end(&mut execution)?)?;
+    let actual = Output::from_value(resume_to_end(&mut restored)?)?;
+
+    // NB: the pushed value is visible through `values` since it is shared with
+    // `alias`.
+    assert_eq!(expected.0, vec![1, 2, 3, 6]);
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
+#[test]
+fn test_save_and_restore_call_frames() -> Result<()> {
+    let (context, unit) = setup(
+        r#"
+        fn helper(a, b) {
+            a * b
+        }
+
+        fn main() {
+            let a = 10;
+            let b = helper(a, 2);
+            a + b
+        }
+        "#,
+    )?;
+
+    let offset = match unit.lookup(Hash::type_hash(&["helper"])) {
+        Some(UnitFn::Offset { offset, .. }) => offset,
+        _ => panic!("missing helper function"),
+    };
+
+    let mut vm = Vm::new(context.clone(), unit.clone());
+    vm.set_breakpoint(offset);
+
+    let mut execution = vm.call(&["main"], ())?;
+    execution.run_to_breakpoint()?;
+    assert_eq!(execution.vm()?.call_frames().len(), 1);
+
+    let state = execution.vm()?.save_state()?;
+
+    let mut restored = Vm::new(context, unit);
+    restored.restore_state(&state)?;
+    assert_eq!(restored.call_frames().len(), 1);
+    assert_eq!(restored.ip(), offset);
+
+    let mut restored = restored.into_execution();
+    assert_eq!(i64::from_value(restored.complete()?)?, 30);
+    assert_eq!(i64::from_value(execution.complete()?)?, 30);
+    Ok(())
+}
+
+#[test]
+fn test_save_unsupported_value() -> Result<()> {
+    let (context, unit) = setup(
+        r#"
+        fn main() {
+            let f = |a| a + 1;
+            yield 1;
+            f(1)
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, unit);
+    let mut execution = vm.call(&["main"], ())?;
+    execution.resume()?;
+
+    let error = execution.vm()?.save_state().unwrap_err();
+
+    match error.kind() {
+        UnsupportedVmStateValue { .. } => (),
+        kind => panic!("expected unsupported value but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_restore_bad_state() -> Result<()> {
+    let (context, unit) = setup(r#"fn main() { yield 1; 2 }"#)?;
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], ())?;
+    execution.resume()?;
+    let state = execution.vm()?.save_state()?;
+
+    let mut vm = Vm::new(context, unit);
+
+    for bad in &[&state[..state.len() - 1], &b"not a state"[..]] {
+        match vm.restore_state(bad).unwrap_err().kind() {
+            BadVmState => (),
+            kind => panic!("expected bad state but got: {:?}", kind),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_restore_deeply_nested_state() -> Result<()> {
+    const OPTION: u8 = 14;
+    const UNIT: u8 = 0;
+
+    /// Encode a state with a single value on the stack, which is a unit
+    /// wrapped in the given number of options.
+    fn nested(depth: usize) -> Vec<u8> {
+        let mut state = b"RNVM\x01".to_vec();
+
+        // NB: the instruction pointer, the stack bottom, no call frames, and
+        // a single value on the stack.
+        for n in &[0u64, 0, 0, 1] {
+            state.extend_from_slice(&n.to_le_bytes());
+        }
+
+        for _ in 0..depth {
+            state.extend_from_slice(&[OPTION, 1]);
+        }
+
+        state.push(UNIT);
+        state
+    }
+
+    let (context, unit) = setup(r#"fn main() { 1 }"#)?;
+    let mut vm = Vm::new(context, unit);
+
+    vm.restore_state(&nested(255))?;
+    assert_eq!(vm.stack().len(), 1);
+
+    match vm.restore_state(&nested(100_000)).unwrap_err().kind() {
+        BadVmState => (),
+        kind => panic!("expected bad state but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_save_deeply_nested_state() -> Result<()> {
+    let (context, unit) = setup(
+        r#"
+        fn main(depth) {
+            let v = [];
+            let i = 1;
+
+            while i < depth {
+                v = [v];
+                i += 1;
+            }
+
+            yield 1;
+            v.len()
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context.clone(), unit.clone());
+    let mut execution = vm.call(&["main"], (256i64,))?;
+    execution.resume()?;
+    let state = execution.vm()?.save_state()?;
+
+    // NB: anything which can be saved can also be restored.
+    let mut restored = Vm::new(context.clone(), unit.clone());
+    restored.restore_state(&state)?;
+    restored.stack_mut().push(());
+    assert_eq!(i64::from_value(restored.into_execution().complete()?)?, 1);
+
+    let vm = Vm::new(context, unit);
+    let mut execution = vm.call(&["main"], (257i64,))?;
+    execution.resume()?;
+
+    match execution.vm()?.save_state().unwrap_err().kind() {
+        ValueTooDeep { max } => assert_eq!(*max, 256),
+        kind => panic!("expected value too deep but got: {:?}", kind),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use rune_testing::*;
+use runestick::{Context, Inst};
+use std::sync::Arc;
+
+#[test]
+fn test_string_lines() {
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { "foo\nbar\r\nbaz".lines() }"#),
+        vec![String::from("foo"), String::from("bar"), String::from("baz")],
+    };
+
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { "foo\nbar\n".lines() }"#),
+        vec![String::from("foo"), String::from("bar")],
+    };
+
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { "foo\n\nbar".lines() }"#),
+        vec![String::from("foo"), String::new(), String::from("bar")],
+    };
+
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { "".lines() }"#),
+        Vec::<String>::new(),
+    };
+}
+
+#[test]
+fn test_string_index() {
+    assert_eq! {
+        rune!((char, char, char) => r#"
+        fn main() {
+            let s = `hello`;
+            ("hello"[0], s[4], "åäö"[1])
+        }
+        "#),
+        ('h', 'o', 'ä'),
+    };
+
+    assert_vm_error!(
+        r#"fn main() { "åäö"[3] }"#,
+        StringIndexOutOfBounds { index, len } => {
+            assert_eq!(*index, 3);
+            assert_eq!(*len, 3);
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { "abc"[-1] }"#,
+        StringIndexOutOfBounds { index, .. } => {
+            assert_eq!(*index, -1);
+        }
+    );
+}
+
+#[test]
+fn test_string_char_at() {
+    assert_eq! {
+        rune!((Option<char>, Option<char>, Option<char>) => r#"
+        fn main() {
+            let s = `hello`;
+            (s.char_at(0), s.char_at(4), s.char_at(5))
+        }
+        "#),
+        (Some('h'), Some('o'), None),
+    };
+
+    // NB: indexes are in characters, not bytes.
+    assert_eq! {
+        rune!((Option<char>, Option<char>, Option<char>) => r#"
+        fn main() {
+            let s = "héllo 😀!";
+            (s.char_at(1), s.char_at(6), s.char_at(8))
+        }
+        "#),
+        (Some('é'), Some('😀'), None),
+    };
+
+    assert_eq! {
+        rune!(Option<char> => r#"fn main() { "abc".char_at(-1) }"#),
+        None,
+    };
+}
+
+#[test]
+fn test_string_pad() {
+    assert_eq! {
+        rune!((String, String, String) => r#"
+        fn main() {
+            let s = `42`;
+            (s.pad_left(5, ' '), s.pad_right(5, '.'), "åä".pad_left(3, 'ö'))
+        }
+        "#),
+        (
+            String::from("   42"),
+            String::from("42..."),
+            String::from("öåä"),
+        ),
+    };
+
+    // NB: strings which are already wide enough are never truncated.
+    assert_eq! {
+        rune!((String, String, String) => r#"
+        fn main() {
+            let s = "hello";
+            (s.pad_left(3, ' '), s.pad_right(5, ' '), s.pad_right(-1, ' '))
+        }
+        "#),
+        (
+            String::from("hello"),
+            String::from("hello"),
+            String::from("hello"),
+        ),
+    };
+}
+
+#[test]
+fn test_string_slice() {
+    assert_eq! {
+        rune!((String, String, String, String) => r#"
+        fn main() {
+            let s = `hello world`;
+            (s[0..5], "hello world"[6..], "åäö"[2..=3], "åäö"[..0])
+        }
+        "#),
+        (
+            String::from("hello"),
+            String::from("world"),
+            String::from("ä"),
+            String::new(),
+        ),
+    };
+
+    assert_vm_error!(
+        r#"fn main() { "åäö"[1..2] }"#,
+        StringSliceNotCharBoundary { range } => {
+            assert_eq!(range.to_string(), "1..2");
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { "abc"[1..4] }"#,
+        IndexRangeOutOfBounds { len, .. } => {
+            assert_eq!(*len, 3);
+        }
+    );
+}
+
+#[test]
+fn test_string_template_add_assign() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+    let (unit, _) = compile_source(&context, r#"fn main() { let s = ""; s += `{1}`; }"#)?;
+
+    // NB: the template isn't built into a temporary string first.
+    assert!(unit
+        .iter_instructions()
+        .any(|inst| matches!(inst, Inst::StringConcatAssign { .. })));
+    assert!(!unit
+        .iter_instructions()
+        .any(|inst| matches!(inst, Inst::StringConcat { .. })));
+
+    // NB: the template is appended in place, so everything fits into the
+    // initial allocation.
+    assert_eq! {
+        rune!((String, usize) => r#"
+        fn main() {
+            let s = String::with_capacity(64);
+
+            for n in 0..32 {
+                s += `{n}`;
+            }
+
+            s += `{0.5}`;
+            (s, s.capacity())
+        }
+        "#),
+        ((0..32).map(|n| n.to_string()).collect::<String>() + "0.5", 64),
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        fn main() {
+            let s = String::from_str("a");
+            s += `{s}-{s}`;
+            s
+        }
+        "#),
+        "aa-a",
+    };
+
+    assert_eq! {
+        rune!(String => r#"
+        fn main() {
+            let s = String::new();
+            let t = s;
+            s += `{1}, {"b"}`;
+            t
+        }
+        "#),
+        "1, b",
+    };
+
+    assert_vm_error!(
+        r#"fn main() { let n = 1; n += `{2}`; }"#,
+        UnsupportedBinaryOperation { op, .. } => {
+            assert_eq!(*op, "+=");
+        }
+    );
+
+    Ok(())
+}
@@ -1,4 +1,75 @@
 use rune_testing::*;
+use runestick::{Context, FromValue, Module, Range, StaticString, Value, Vm, VmError};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Squares;
+
+runestick::impl_external!(Squares);
+
+impl Squares {
+    fn index_get(&self, index: i64) -> i64 {
+        index * index
+    }
+}
+
+#[derive(Debug)]
+struct Buffer {
+    values: Vec<i64>,
+}
+
+runestick::impl_external!(Buffer);
+
+impl Buffer {
+    fn new() -> Self {
+        Self {
+            values: (0..10).collect(),
+        }
+    }
+
+    /// Resolve a range into the indexes it covers in the buffer.
+    fn indexes(&self, range: Range) -> impl Iterator<Item = usize> {
+        let start = range.start.unwrap_or(0) as usize;
+        let end = match range.end {
+            Some(end) if range.inclusive => end as usize + 1,
+            Some(end) => end as usize,
+            None => self.values.len(),
+        };
+
+        start..end
+    }
+
+    fn index_get(&self, index: Value) -> Result<Value, VmError> {
+        Ok(match index {
+            Value::Integer(index) => Value::Integer(self.values[index as usize]),
+            Value::Range(range) => {
+                let range = *range.borrow_ref()?;
+                let values = self.indexes(range).map(|i| Value::Integer(self.values[i]));
+                Value::vec(values.collect())
+            }
+            actual => return Err(VmError::expected::<i64>(actual.type_info()?)),
+        })
+    }
+
+    fn index_set(&mut self, index: Value, value: i64) -> Result<(), VmError> {
+        match index {
+            Value::Integer(index) => {
+                self.values[index as usize] = value;
+            }
+            Value::Range(range) => {
+                let range = *range.borrow_ref()?;
+
+                for i in self.indexes(range).collect::<Vec<_>>() {
+                    self.values[i] = value;
+                }
+            }
+            actual => return Err(VmError::expected::<i64>(actual.type_info()?)),
+        }
+
+        Ok(())
+    }
+}
 
 #[test]
 fn test_instance_kinds() {
@@ -41,3 +112,175 @@ fn test_instance_kinds() {
         (4, 5, 6, 7),
     };
 }
+
+#[test]
+fn test_index_get_protocol() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Squares"]).build::<Squares>()?;
+    module.function(&["Squares", "new"], || Squares)?;
+    module.inst_fn(runestick::INDEX_GET, Squares::index_get)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let squares = Squares::new();
+            squares[3] + squares[4]
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+    assert_eq!(i64::from_value(output)?, 25);
+    Ok(())
+}
+
+#[test]
+fn test_index_protocol_with_ranges() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Buffer"]).build::<Buffer>()?;
+    module.function(&["Buffer", "new"], Buffer::new)?;
+    module.inst_fn(runestick::INDEX_GET, Buffer::index_get)?;
+    module.inst_fn(runestick::INDEX_SET, Buffer::index_set)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let buffer = Buffer::new();
+            buffer[1] = 100;
+            buffer[4..=6] = 0;
+            (buffer[1], buffer[2..5], buffer[..3], buffer[8..])
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    assert_eq!(
+        <(i64, Vec<i64>, Vec<i64>, Vec<i64>)>::from_value(output)?,
+        (100, vec![2, 3, 0], vec![0, 100, 2], vec![8, 9]),
+    );
+
+    Ok(())
+}
+
+thread_local! {
+    static RECEIVER: RefCell<Option<Arc<StaticString>>> = RefCell::new(None);
+}
+
+/// Count the references to the receiver tracked in `RECEIVER`.
+fn receiver_strong_count(_: &str) -> i64 {
+    RECEIVER.with(|receiver| {
+        let receiver = receiver.borrow();
+        Arc::strong_count(receiver.as_ref().expect("tracked receiver")) as i64
+    })
+}
+
+#[test]
+fn test_instance_call_does_not_clone_receiver() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let counts = [];
+
+            for n in 0..100 {
+                counts.push("receiver".strong_count());
+            }
+
+            counts
+        }
+        "#,
+    )?;
+
+    // Track the static string used as a receiver, so that the native
+    // function can observe how many references to it are alive while the
+    // call is in progress.
+    let receiver = unit
+        .iter_static_strings()
+        .find(|s| s.as_str() == "receiver")
+        .cloned()
+        .expect("receiver string");
+
+    RECEIVER.with(|tracked| *tracked.borrow_mut() = Some(receiver));
+
+    let mut module = Module::default();
+    module.inst_fn("strong_count", receiver_strong_count)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+
+    let vm = Vm::new(Arc::new(context), Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    // One reference is held by the unit and one by `RECEIVER`. The receiver
+    // on the stack is consumed when it's converted into a `&str`, so any
+    // additional reference means that it was cloned to resolve its type.
+    assert_eq!(Vec::<i64>::from_value(output)?, vec![2; 100]);
+    Ok(())
+}
+
+#[test]
+fn test_polymorphic_call_site() {
+    // NB: the same call site is resolved again whenever the type of the
+    // instance changes.
+    assert_eq! {
+        rune! {
+            i64 => r#"
+            struct Foo;
+            struct Bar;
+
+            impl Foo {
+                fn value(self) { 1 }
+            }
+
+            impl Bar {
+                fn value(self) { 10 }
+            }
+
+            fn main() {
+                let out = 0;
+
+                for v in [Foo, Foo, Bar, Foo, Bar, Bar, [1, 2, 3]] {
+                    out += if v is Vec { v.len() } else { v.value() };
+                }
+
+                out
+            }
+            "#
+        },
+        1 + 1 + 10 + 1 + 10 + 10 + 3,
+    };
+
+    assert_vm_error!(
+        r#"
+        struct Foo;
+
+        impl Foo {
+            fn value(self) { 1 }
+        }
+
+        fn main() {
+            for v in [Foo, Foo, 42] {
+                v.value();
+            }
+        }
+        "#,
+        MissingInstanceFunction { instance, .. } => {
+            assert_eq!(instance.to_string(), "integer");
+        }
+    );
+}
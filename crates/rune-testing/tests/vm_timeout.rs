@@ -0,0 +1,136 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Future, Module, Shared, Vm, VmError, VmErrorKind};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Resource {
+    _counter: Arc<()>,
+}
+
+runestick::impl_external!(Resource);
+
+#[test]
+fn test_timeout_infinite_loop() -> Result<()> {
+    let mut module = Module::default();
+    module.ty(&["Resource"]).build::<Resource>()?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main(resource) {
+            let values = [resource];
+            loop {}
+        }
+        "#,
+    )?;
+
+    let counter = Arc::new(());
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let mut execution = vm.call(
+        &["main"],
+        (Resource {
+            _counter: counter.clone(),
+        },),
+    )?;
+    let error = execution
+        .complete_with_timeout(Duration::from_millis(50))
+        .unwrap_err();
+
+    match error.kind() {
+        VmErrorKind::Timeout { timeout } => {
+            assert_eq!(*timeout, Duration::from_millis(50));
+        }
+        kind => panic!("expected timeout but got: {:?}", kind),
+    }
+
+    // NB: the values held by the execution are released on timeout.
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    match execution.complete().unwrap_err().kind() {
+        VmErrorKind::NoRunningVm => (),
+        kind => panic!("expected no running vm but got: {:?}", kind),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_timeout_completes() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn add(a, b) { a + b }
+
+        fn main(n) {
+            let out = 0;
+
+            while n > 0 {
+                out = add(out, n);
+                n = n - 1;
+            }
+
+            out
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context.clone(), Arc::new(unit));
+    let mut execution = vm.call(&["main"], (1000i64,))?;
+    let output = execution.complete_with_timeout(Duration::from_secs(10))?;
+    assert_eq!(i64::from_value(output)?, 500500);
+    Ok(())
+}
+
+#[test]
+fn test_await_timeout() -> Result<()> {
+    let context = Arc::new(Context::with_default_modules()?);
+
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        async fn main(future) {
+            future.await
+        }
+        "#,
+    )?;
+
+    let guard = Arc::new(());
+    let inner = guard.clone();
+
+    let future = Shared::new(Future::new(async move {
+        let _inner = inner;
+        std::future::pending::<()>().await;
+        Ok::<_, VmError>(())
+    }));
+
+    let mut vm = Vm::new(context.clone(), Arc::new(unit));
+    vm.set_await_timeout(Some(Duration::from_millis(50)));
+
+    let mut execution = vm.call(&["main"], (future,))?;
+    let error = futures_executor::block_on(execution.async_complete()).unwrap_err();
+
+    match error.kind() {
+        VmErrorKind::AwaitTimeout { timeout } => {
+            assert_eq!(*timeout, Duration::from_millis(50));
+        }
+        kind => panic!("expected await timeout but got: {:?}", kind),
+    }
+
+    // NB: the timed out future is released and can't be polled again.
+    assert_eq!(Arc::strong_count(&guard), 1);
+
+    match execution.complete().unwrap_err().kind() {
+        VmErrorKind::NoRunningVm => (),
+        kind => panic!("expected no running vm but got: {:?}", kind),
+    }
+
+    Ok(())
+}
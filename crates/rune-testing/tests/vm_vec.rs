@@ -0,0 +1,260 @@
+use rune_testing::*;
+use runestick::{Context, FromValue, Integer, Module, Vm};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[test]
+fn test_vec_slice() {
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3, 4].slice(1, 3) }"#),
+        vec![2, 3],
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3, 4].slice(2, 2) }"#),
+        Vec::<i64>::new(),
+    };
+
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3, 4].slice(0, 4) }"#),
+        vec![1, 2, 3, 4],
+    };
+
+    // NB: heap values are shared with the original vector.
+    assert_eq! {
+        rune!(Vec<i64> => r#"
+        fn main() {
+            let a = [[1], [2]];
+            let b = a.slice(0, 1);
+            b[0].push(3);
+            a[0]
+        }
+        "#),
+        vec![1, 3],
+    };
+
+    assert_vm_error!(
+        r#"fn main() { [1, 2, 3, 4].slice(2, 5); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            OutOfRange { start, end, len } => {
+                assert_eq!(*start, 2);
+                assert_eq!(*end, 5);
+                assert_eq!(*len, 4);
+            }
+            kind => panic!("expected out of range error but got: {:?}", kind),
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { [1, 2, 3, 4].slice(3, 1); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            OutOfRange { start, end, len } => {
+                assert_eq!(*start, 3);
+                assert_eq!(*end, 1);
+                assert_eq!(*len, 4);
+            }
+            kind => panic!("expected out of range error but got: {:?}", kind),
+        }
+    );
+}
+
+#[test]
+fn test_vec_contains_all() {
+    assert_eq! {
+        rune!(bool => r#"fn main() { [1, 2, 3, 4].contains_all([4, 2]) }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { [1, 2, 3, 4].contains_all([4, 5]) }"#),
+        false,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { [1, 2].contains_all([]) }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { [[1, 2], #{a: "b"}].contains_all([#{a: "b"}, [1, 2]]) }"#),
+        true,
+    };
+
+    assert_eq! {
+        rune!(bool => r#"fn main() { [[1, 2], #{a: "b"}].contains_all([[1, 3]]) }"#),
+        false,
+    };
+}
+
+#[test]
+fn test_vec_dedup() {
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 1, 3, 2, 4].dedup() }"#),
+        vec![1, 2, 3, 4],
+    };
+
+    assert_eq! {
+        rune!(Vec<Vec<i64>> => r#"fn main() { [[1, 2], [3], [1, 2], []].dedup() }"#),
+        vec![vec![1, 2], vec![3], vec![]],
+    };
+
+    assert_eq! {
+        rune!(Vec<String> => r#"fn main() { ["a", "b", "a"].dedup() }"#),
+        vec![String::from("a"), String::from("b")],
+    };
+}
+
+#[test]
+fn test_vec_join() {
+    assert_eq! {
+        rune!(String => r#"fn main() { ["a", "b", "c"].join(", ") }"#),
+        "a, b, c",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { let b = `b`; ["a", b].join("") }"#),
+        "ab",
+    };
+
+    assert_eq! {
+        rune!(String => r#"fn main() { [].join(", ") }"#),
+        "",
+    };
+
+    assert_vm_error!(
+        r#"fn main() { ["a", 1, "c"].join(", "); }"#,
+        BadReturn { error, .. } => match error.kind() {
+            BadElementType { index, expected, actual } => {
+                assert_eq!(*index, 1);
+                assert_eq!(expected.to_string(), "String");
+                assert_eq!(actual.to_string(), "integer");
+            }
+            kind => panic!("expected bad element type but got: {:?}", kind),
+        }
+    );
+}
+
+#[test]
+fn test_vec_clear() {
+    // NB: clearing keeps the capacity, so refilling never reallocates.
+    assert_eq! {
+        rune!((bool, Vec<i64>) => r#"
+        fn main() {
+            let v = [];
+
+            for n in 0..16 {
+                v.push(n);
+            }
+
+            let capacity = v.capacity();
+            let reused = true;
+
+            for round in 0..4 {
+                v.clear();
+
+                if v.len() != 0 {
+                    reused = false;
+                }
+
+                for n in 0..16 {
+                    v.push(n * round);
+                }
+
+                if v.capacity() != capacity {
+                    reused = false;
+                }
+            }
+
+            v.clear();
+            (reused, v)
+        }
+        "#),
+        (true, vec![]),
+    };
+}
+
+fn sum(values: Vec<i64>) -> i64 {
+    values.iter().sum()
+}
+
+fn keys(object: HashMap<String, i64>) -> Vec<String> {
+    let mut keys = object.into_iter().map(|(key, _)| key).collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_vec_from_value() -> Result<()> {
+    assert_eq! {
+        rune!(Vec<i64> => r#"fn main() { [1, 2, 3] }"#),
+        vec![1, 2, 3],
+    };
+
+    assert_eq! {
+        rune!(HashMap<String, Vec<i64>> => r#"fn main() { #{"a": [1], "b": []} }"#),
+        vec![(String::from("a"), vec![1]), (String::from("b"), vec![])]
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+    };
+
+    let mut module = Module::default();
+    module.function(&["sum"], sum)?;
+    module.function(&["keys"], keys)?;
+
+    let mut context = Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    // NB: converting arguments leaves the collections usable by the script.
+    let (unit, _) = compile_source(
+        &context,
+        r#"
+        fn main() {
+            let v = [1, 2, 3];
+            let o = #{"b": 2, "a": 1};
+            (sum(v), v.len(), keys(o), o.len())
+        }
+        "#,
+    )?;
+
+    let vm = Vm::new(context, Arc::new(unit));
+    let output = vm.call(&["main"], ())?.complete()?;
+
+    assert_eq!(
+        <(i64, usize, Vec<String>, usize)>::from_value(output)?,
+        (6, 3, vec![String::from("a"), String::from("b")], 2)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_index_out_of_bounds() {
+    assert_vm_error!(
+        r#"fn main() { let v = [1, 2, 3]; v[3] }"#,
+        IndexOutOfBounds { index: Integer::Usize(3), len } => {
+            assert_eq!(*len, 3);
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { let v = [1, 2, 3]; v[-1] }"#,
+        IndexOutOfBounds { index: Integer::I64(-1), len } => {
+            assert_eq!(*len, 3);
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { let t = (1, 2); t.2 }"#,
+        IndexOutOfBounds { index: Integer::Usize(2), len } => {
+            assert_eq!(*len, 2);
+        }
+    );
+
+    assert_vm_error!(
+        r#"fn main() { let t = (1, 2); t[5] }"#,
+        IndexOutOfBounds { index: Integer::Usize(5), len } => {
+            assert_eq!(*len, 2);
+        }
+    );
+}
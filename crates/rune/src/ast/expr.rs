@@ -63,12 +63,16 @@ pub enum Expr {
     ExprGroup(ast::ExprGroup),
     /// A binary expression.
     ExprBinary(ast::ExprBinary),
+    /// A range expression.
+    ExprRange(ast::ExprRange),
     /// A unary expression.
     ExprUnary(ast::ExprUnary),
     /// An index set operation.
     ExprIndexGet(ast::ExprIndexGet),
     /// A break expression.
     ExprBreak(ast::ExprBreak),
+    /// A continue expression.
+    ExprContinue(ast::ExprContinue),
     /// A yield expression.
     ExprYield(ast::ExprYield),
     /// A block as an expression.
@@ -119,6 +123,7 @@ impl Expr {
             Self::ExprIf(expr_if) => expr_if.produces_nothing(),
             Self::ExprGroup(expr_group) => expr_group.produces_nothing(),
             Self::ExprBreak(..) => true,
+            Self::ExprContinue(..) => true,
             Self::ExprBinary(expr_binary) => expr_binary.produces_nothing(),
             Self::ExprBlock(expr_block) => expr_block.produces_nothing(),
             Self::ExprReturn(..) => true,
@@ -155,8 +160,10 @@ impl Expr {
             Self::ExprGroup(expr) => expr.span(),
             Self::ExprUnary(expr) => expr.span(),
             Self::ExprBinary(expr) => expr.span(),
+            Self::ExprRange(expr) => expr.span(),
             Self::ExprIndexGet(expr) => expr.span(),
             Self::ExprBreak(b) => b.span(),
+            Self::ExprContinue(c) => c.span(),
             Self::ExprYield(b) => b.span(),
             Self::ExprBlock(b) => b.span(),
             Self::ExprReturn(ret) => ret.span(),
@@ -212,8 +219,54 @@ impl Expr {
         eager_brace: EagerBrace,
         expr_chain: ExprChain,
     ) -> Result<Self, ParseError> {
+        if parser.peek::<ast::ExprRangeLimits>()? {
+            return Self::parse_expr_range(parser, None, eager_brace);
+        }
+
         let lhs = Self::parse_primary(parser, eager_brace, expr_chain)?;
-        Ok(Self::parse_expr_binary(parser, lhs, 0, eager_brace)?)
+        let lhs = Self::parse_expr_binary(parser, lhs, 0, eager_brace)?;
+
+        if parser.peek::<ast::ExprRangeLimits>()? {
+            return Self::parse_expr_range(parser, Some(lhs), eager_brace);
+        }
+
+        Ok(lhs)
+    }
+
+    /// Parse a range expression, where the optional start of the range has
+    /// already been parsed.
+    fn parse_expr_range(
+        parser: &mut Parser<'_>,
+        from: Option<Self>,
+        eager_brace: EagerBrace,
+    ) -> Result<Self, ParseError> {
+        let limits = parser.parse::<ast::ExprRangeLimits>()?;
+
+        // NB: a closed range must have an end, for the others we need to check
+        // if there's something that looks like an expression following the
+        // limits. Note that `for i in 0.. {` is an open range followed by a
+        // block.
+        let has_end = match limits {
+            ast::ExprRangeLimits::Closed(..) => true,
+            ast::ExprRangeLimits::HalfOpen(..) => {
+                parser.peek::<Self>()? && (*eager_brace || !parser.peek::<ast::OpenBrace>()?)
+            }
+        };
+
+        let to = if has_end {
+            let rhs = Self::parse_primary(parser, eager_brace, ExprChain(true))?;
+            let rhs =
+                Self::parse_expr_binary(parser, rhs, ast::BinOp::Or.precedence(), eager_brace)?;
+            Some(Box::new(rhs))
+        } else {
+            None
+        };
+
+        Ok(Self::ExprRange(ast::ExprRange {
+            from: from.map(Box::new),
+            limits,
+            to,
+        }))
     }
 
     /// Parse expressions that start with an identifier.
@@ -314,7 +367,9 @@ impl Expr {
                 });
             }
             ast::Kind::Hash => Self::LitObject(parser.parse()?),
-            ast::Kind::Bang | Kind::Amp | Kind::Star => Self::ExprUnary(parser.parse()?),
+            ast::Kind::Bang | Kind::Dash | Kind::Amp | Kind::Star => {
+                Self::ExprUnary(parser.parse()?)
+            }
             ast::Kind::While => Self::ExprWhile(parser.parse()?),
             ast::Kind::Loop => Self::ExprLoop(parser.parse()?),
             ast::Kind::For => Self::ExprFor(parser.parse()?),
@@ -333,6 +388,7 @@ impl Expr {
             ast::Kind::True | Kind::False => Self::LitBool(parser.parse()?),
             ast::Kind::Ident => Self::parse_ident_start(parser, eager_brace)?,
             ast::Kind::Break => Self::ExprBreak(parser.parse()?),
+            ast::Kind::Continue => Self::ExprContinue(parser.parse()?),
             ast::Kind::Yield => Self::ExprYield(parser.parse()?),
             ast::Kind::Return => Self::ExprReturn(parser.parse()?),
             _ => {
@@ -468,7 +524,13 @@ impl Expr {
                 parser.token_next()?;
             }
 
-            let mut rhs = Self::parse_primary(parser, eager_brace, ExprChain(true))?;
+            let is_range = op.is_assign() && parser.peek::<ast::ExprRangeLimits>()?;
+
+            let mut rhs = if is_range {
+                Self::parse_expr_range(parser, None, eager_brace)?
+            } else {
+                Self::parse_primary(parser, eager_brace, ExprChain(true))?
+            };
 
             lookahead_tok = parser.token_peek_pair()?;
 
@@ -487,6 +549,13 @@ impl Expr {
                 lookahead_tok = parser.token_peek_pair()?;
             }
 
+            // NB: ranges bind tighter than assignments, so `a = 0..10` assigns
+            // the range to `a`.
+            if !is_range && op.is_assign() && parser.peek::<ast::ExprRangeLimits>()? {
+                rhs = Self::parse_expr_range(parser, Some(rhs), eager_brace)?;
+                lookahead_tok = parser.token_peek_pair()?;
+            }
+
             lhs = Expr::ExprBinary(ast::ExprBinary {
                 lhs: Box::new(lhs),
                 op,
@@ -533,6 +602,10 @@ impl Expr {
 /// parse_all::<ast::Expr>("foo.bar()[0].baz()[1]").unwrap();
 ///
 /// parse_all::<ast::Expr>("42 is int::int").unwrap();
+/// parse_all::<ast::Expr>("0..10").unwrap();
+/// parse_all::<ast::Expr>("0..=n + 1").unwrap();
+/// parse_all::<ast::Expr>("..").unwrap();
+/// parse_all::<ast::Expr>("a = ..10").unwrap();
 /// ```
 impl Parse for Expr {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -553,7 +626,7 @@ impl Peek for Expr {
             ast::Kind::Select => true,
             ast::Kind::Label => matches!(t2.map(|t| t.kind), Some(Kind::Colon)),
             ast::Kind::Hash => true,
-            ast::Kind::Bang | ast::Kind::Amp | ast::Kind::Star => true,
+            ast::Kind::Bang | ast::Kind::Dash | ast::Kind::Amp | ast::Kind::Star => true,
             ast::Kind::While => true,
             ast::Kind::Loop => true,
             ast::Kind::For => true,
@@ -571,7 +644,9 @@ impl Peek for Expr {
             ast::Kind::True | Kind::False => true,
             ast::Kind::Ident => true,
             ast::Kind::Break => true,
+            ast::Kind::Continue => true,
             ast::Kind::Return => true,
+            ast::Kind::DotDot | ast::Kind::DotDotEq => true,
             _ => false,
         }
     }
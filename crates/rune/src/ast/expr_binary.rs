@@ -119,6 +119,24 @@ impl BinOp {
         }
     }
 
+    /// Test if the operator is an assignment operator.
+    pub(super) fn is_assign(self) -> bool {
+        matches!(
+            self,
+            Self::Assign
+                | Self::AddAssign
+                | Self::SubAssign
+                | Self::MulAssign
+                | Self::DivAssign
+                | Self::RemAssign
+                | Self::BitAndAssign
+                | Self::BitXorAssign
+                | Self::BitOrAssign
+                | Self::ShlAssign
+                | Self::ShrAssign
+        )
+    }
+
     /// Test if operator is left associative.
     pub(super) fn is_assoc(self) -> bool {
         match self {
@@ -1,5 +1,5 @@
 use crate::ast::utils;
-use crate::ast::{CloseBrace, Comma, Expr, If, Match, OpenBrace, Pat, Rocket};
+use crate::ast::{CloseBrace, Comma, Expr, If, Match, OpenBrace, Pat, PatOr, Pipe, Rocket};
 use crate::error::ParseError;
 use crate::parser::Parser;
 use crate::traits::Parse;
@@ -38,11 +38,19 @@ impl ExprMatchBranch {
 /// use rune::{parse_all, ast};
 ///
 /// parse_all::<ast::ExprMatchBranch>("1 => { foo }").unwrap();
+/// parse_all::<ast::ExprMatchBranch>("1 | 2 => { foo }").unwrap();
+/// parse_all::<ast::ExprMatchBranch>("Some(a) | Ok(a) if a > 0 => { foo }").unwrap();
 /// ```
 impl Parse for ExprMatchBranch {
     fn parse(parser: &mut Parser) -> Result<Self, ParseError> {
         let pat = parser.parse()?;
 
+        let pat = if parser.peek::<Pipe>()? {
+            Pat::PatOr(PatOr::parse_with_first(parser, pat)?)
+        } else {
+            pat
+        };
+
         let condition = if parser.peek::<If>()? {
             Some((parser.parse()?, Box::new(parser.parse()?)))
         } else {
@@ -0,0 +1,73 @@
+use crate::ast;
+use crate::ast::{Kind, Token};
+use crate::error::ParseError;
+use crate::parser::Parser;
+use crate::traits::{Parse, Peek};
+use runestick::Span;
+
+/// The limits of a range expression.
+#[derive(Debug, Clone, Copy)]
+pub enum ExprRangeLimits {
+    /// A half-open range `..`.
+    HalfOpen(ast::DotDot),
+    /// A closed range `..=`.
+    Closed(ast::DotDotEq),
+}
+
+impl ExprRangeLimits {
+    /// Access the span of the limits.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::HalfOpen(dot_dot) => dot_dot.span(),
+            Self::Closed(dot_dot_eq) => dot_dot_eq.span(),
+        }
+    }
+}
+
+impl Parse for ExprRangeLimits {
+    fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let token = parser.token_peek_eof()?;
+
+        Ok(match token.kind {
+            Kind::DotDotEq => Self::Closed(parser.parse()?),
+            _ => Self::HalfOpen(parser.parse()?),
+        })
+    }
+}
+
+impl Peek for ExprRangeLimits {
+    fn peek(t1: Option<Token>, _: Option<Token>) -> bool {
+        matches!(
+            t1.map(|t| t.kind),
+            Some(Kind::DotDot) | Some(Kind::DotDotEq)
+        )
+    }
+}
+
+/// A range expression `a..b`, `a..=b`, `a..`, `..b`, or `..`.
+#[derive(Debug, Clone)]
+pub struct ExprRange {
+    /// The optional start of the range.
+    pub from: Option<Box<ast::Expr>>,
+    /// The limits of the range.
+    pub limits: ExprRangeLimits,
+    /// The optional end of the range.
+    pub to: Option<Box<ast::Expr>>,
+}
+
+impl ExprRange {
+    /// Access the span of the expression.
+    pub fn span(&self) -> Span {
+        let span = self.limits.span();
+
+        let span = match &self.from {
+            Some(from) => from.span().join(span),
+            None => span,
+        };
+
+        match &self.to {
+            Some(to) => span.join(to.span()),
+            None => span,
+        }
+    }
+}
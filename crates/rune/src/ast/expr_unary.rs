@@ -32,6 +32,7 @@ impl ExprUnary {
 /// use rune::{parse_all, ast};
 ///
 /// parse_all::<ast::ExprUnary>("!0").unwrap();
+/// parse_all::<ast::ExprUnary>("-foo").unwrap();
 /// parse_all::<ast::ExprUnary>("*foo").unwrap();
 /// parse_all::<ast::ExprUnary>("&foo").unwrap();
 /// ```
@@ -57,6 +58,8 @@ impl Parse for ExprUnary {
 pub enum UnaryOp {
     /// Not `!<thing>`.
     Not,
+    /// Negation `-<thing>`.
+    Neg,
     /// Reference `&<thing>`.
     BorrowRef,
     /// Dereference `*<thing>`.
@@ -68,6 +71,7 @@ impl UnaryOp {
     pub fn from_token(token: ast::Token) -> Result<Self, ParseError> {
         Ok(match token.kind {
             ast::Kind::Bang => Self::Not,
+            ast::Kind::Dash => Self::Neg,
             ast::Kind::Amp => Self::BorrowRef,
             ast::Kind::Star => Self::Deref,
             actual => {
@@ -84,6 +88,7 @@ impl fmt::Display for UnaryOp {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Not => write!(fmt, "!")?,
+            Self::Neg => write!(fmt, "-")?,
             Self::BorrowRef => write!(fmt, "&")?,
             Self::Deref => write!(fmt, "*")?,
         }
@@ -33,7 +33,8 @@ impl LitByteStr {
         while let Some((n, c)) = it.next() {
             buffer.push(match c {
                 '\\' => ast::utils::parse_byte_escape(span.with_start(n), &mut it)?,
-                c => c as u8,
+                c if c.is_ascii() => c as u8,
+                c => return Err(non_ascii(n, c)),
             });
         }
 
@@ -50,11 +51,22 @@ impl<'a> Resolve<'a> for LitByteStr {
             .source(span)
             .ok_or_else(|| ParseError::BadSlice { span })?;
 
-        Ok(if self.escaped {
-            Cow::Owned(self.parse_escaped(span, string)?)
-        } else {
-            Cow::Borrowed(string.as_bytes())
-        })
+        if self.escaped {
+            return Ok(Cow::Owned(self.parse_escaped(span, string)?));
+        }
+
+        if let Some((n, c)) = string.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(non_ascii(span.start + n, c));
+        }
+
+        Ok(Cow::Borrowed(string.as_bytes()))
+    }
+}
+
+/// Construct an error for a non-ASCII character at the given offset.
+fn non_ascii(start: usize, c: char) -> ParseError {
+    ParseError::NonAsciiByteStr {
+        span: Span::new(start, start + c.len_utf8()),
     }
 }
 
@@ -81,3 +93,37 @@ impl Parse for LitByteStr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseError;
+    use crate::traits::Resolve as _;
+    use crate::{ast, parse_all};
+    use runestick::{Source, Span};
+
+    fn resolve(input: &str) -> Result<Vec<u8>, ParseError> {
+        let source = Source::new("test", input.to_owned());
+        let lit_byte_str = parse_all::<ast::LitByteStr>(input)?;
+        Ok(lit_byte_str.resolve(&source)?.into_owned())
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(resolve(r#"b"\x00\xFF""#).unwrap(), vec![0x00, 0xff]);
+        assert_eq!(resolve(r#"b"a\tb""#).unwrap(), b"a\tb".to_vec());
+        assert_eq!(resolve(r#"b"hello""#).unwrap(), b"hello".to_vec());
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        assert!(matches!(
+            resolve(r#"b"a\xffé""#),
+            Err(ParseError::NonAsciiByteStr { span }) if span == Span::new(7, 9)
+        ));
+
+        assert!(matches!(
+            resolve(r#"b"aé""#),
+            Err(ParseError::NonAsciiByteStr { span }) if span == Span::new(3, 5)
+        ));
+    }
+}
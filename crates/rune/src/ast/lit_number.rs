@@ -3,6 +3,7 @@ use crate::error::ParseError;
 use crate::parser::Parser;
 use crate::traits::{Parse, Resolve};
 use runestick::{Source, Span};
+use std::borrow::Cow;
 
 /// A resolved number literal.
 pub enum Number {
@@ -10,6 +11,10 @@ pub enum Number {
     Float(f64),
     /// An integer literal number.
     Integer(i64),
+    /// An integer literal number which is out of bounds for a 64-bit integer.
+    BigInt(num::BigInt),
+    /// A byte literal number, like `255u8`.
+    Byte(u8),
 }
 
 /// A number literal.
@@ -21,6 +26,8 @@ pub struct LitNumber {
     is_fractional: bool,
     /// The kind of the number literal.
     number: ast::NumberKind,
+    /// The type suffix of the number literal.
+    suffix: Option<ast::NumberSuffix>,
     /// The token corresponding to the literal.
     token: ast::Token,
 }
@@ -43,6 +50,9 @@ impl LitNumber {
 /// parse_all::<ast::LitNumber>("42.42").unwrap();
 /// parse_all::<ast::LitNumber>("0.42").unwrap();
 /// parse_all::<ast::LitNumber>("0.42e10").unwrap();
+/// parse_all::<ast::LitNumber>("inf").unwrap();
+/// parse_all::<ast::LitNumber>("-inf").unwrap();
+/// parse_all::<ast::LitNumber>("nan").unwrap();
 /// ```
 impl Parse for LitNumber {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -53,11 +63,12 @@ impl Parse for LitNumber {
                 is_negative,
                 is_fractional,
                 number,
-                ..
+                suffix,
             } => LitNumber {
                 is_negative,
                 is_fractional,
                 number,
+                suffix,
                 token,
             },
             _ => {
@@ -84,14 +95,42 @@ impl<'a> Resolve<'a> for LitNumber {
             .source(span)
             .ok_or_else(|| ParseError::BadSlice { span })?;
 
+        let string = match self.suffix {
+            Some(suffix) => &string[..string.len() - suffix.as_str().len()],
+            None => string,
+        };
+
         let string = if self.is_negative {
             &string[1..]
         } else {
             string
         };
 
-        if self.is_fractional {
-            let number = f64::from_str(string).map_err(err_span(span))?;
+        let is_float = self.suffix.map(|s| s.is_float()).unwrap_or_default();
+
+        if self.is_fractional && self.suffix.is_some() && !is_float {
+            return Err(ParseError::BadNumberLiteral { span });
+        }
+
+        if self.is_fractional || is_float {
+            if self.number != ast::NumberKind::Decimal {
+                return Err(ParseError::BadNumberLiteral { span });
+            }
+
+            let string =
+                strip_separators(string).ok_or_else(|| ParseError::BadNumberLiteral { span })?;
+            let number = f64::from_str(&string).map_err(err_span(span))?;
+            let number = if self.is_negative { -number } else { number };
+
+            if let Some(ast::NumberSuffix::F32) = self.suffix {
+                if number.is_finite() && (number as f32).is_infinite() {
+                    return Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                        span,
+                        suffix: ast::NumberSuffix::F32,
+                    });
+                }
+            }
+
             return Ok(Number::Float(number));
         }
 
@@ -102,23 +141,195 @@ impl<'a> Resolve<'a> for LitNumber {
             ast::NumberKind::Decimal => (0, 10),
         };
 
-        let number = num::BigUint::from_str_radix(&string[s..], radix).map_err(err_span(span))?;
+        let string =
+            strip_separators(&string[s..]).ok_or_else(|| ParseError::BadNumberLiteral { span })?;
+        let number = num::BigUint::from_str_radix(&string, radix).map_err(err_span(span))?;
 
+        let number = num::BigInt::from(number);
         let number = if self.is_negative {
-            num::BigInt::from(number).neg().to_i64()
+            number.neg()
         } else {
-            number.to_i64()
+            number
         };
 
-        let number = match number {
-            Some(n) => n,
-            None => return Err(ParseError::BadNumberOutOfBounds { span }),
+        let suffix = match self.suffix {
+            Some(suffix) => suffix,
+            None => {
+                return Ok(match number.to_i64() {
+                    Some(n) => Number::Integer(n),
+                    None => Number::BigInt(number),
+                });
+            }
         };
 
-        return Ok(Number::Integer(number));
+        // NB: only `u8` has a native representation which is distinct from a
+        // 64-bit integer, other suffixes are only checked for bounds.
+        let number = match suffix {
+            ast::NumberSuffix::U8 => number.to_u8().map(Number::Byte),
+            ast::NumberSuffix::I8 => number.to_i8().map(|n| Number::Integer(n.into())),
+            ast::NumberSuffix::I16 => number.to_i16().map(|n| Number::Integer(n.into())),
+            ast::NumberSuffix::I32 => number.to_i32().map(|n| Number::Integer(n.into())),
+            ast::NumberSuffix::U16 => number.to_u16().map(|n| Number::Integer(n.into())),
+            ast::NumberSuffix::U32 => number.to_u32().map(|n| Number::Integer(n.into())),
+            // NB: values which don't fit in an `i64` can't be represented.
+            ast::NumberSuffix::I64 | ast::NumberSuffix::U64 => match number.to_i64() {
+                Some(n) if suffix == ast::NumberSuffix::I64 || n >= 0 => Some(Number::Integer(n)),
+                _ => None,
+            },
+            // NB: float suffixes are handled above.
+            ast::NumberSuffix::F32 | ast::NumberSuffix::F64 => unreachable!(),
+        };
+
+        return number.ok_or_else(|| ParseError::BadNumberOutOfBoundsForSuffix { span, suffix });
 
         fn err_span<E>(span: Span) -> impl Fn(E) -> ParseError {
             move |_| ParseError::BadNumberLiteral { span }
         }
     }
 }
+
+/// Strip digit separators (`_`) from the given number.
+///
+/// Separators may only appear between digits, so this returns `None` if a
+/// separator is leading or trailing.
+fn strip_separators(string: &str) -> Option<Cow<'_, str>> {
+    if !string.contains('_') {
+        return Some(Cow::Borrowed(string));
+    }
+
+    let mut out = String::with_capacity(string.len());
+    let mut prev = None;
+
+    for c in string.chars() {
+        match (prev, c) {
+            (None, '_') => return None,
+            (Some(p), '_') if !(char::is_alphanumeric(p) || p == '_') => return None,
+            (Some('_'), c) if !char::is_alphanumeric(c) => return None,
+            (_, '_') => (),
+            (_, c) => out.push(c),
+        }
+
+        prev = Some(c);
+    }
+
+    if prev == Some('_') {
+        return None;
+    }
+
+    Some(Cow::Owned(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Number;
+    use crate::error::ParseError;
+    use crate::traits::Resolve as _;
+    use crate::{ast, parse_all};
+    use runestick::Source;
+
+    fn resolve(input: &str) -> Result<Number, ParseError> {
+        let source = Source::new("test", input.to_owned());
+        parse_all::<ast::LitNumber>(input)?.resolve(&source)
+    }
+
+    #[test]
+    fn test_digit_separators() {
+        assert!(matches!(
+            resolve("1_000_000"),
+            Ok(Number::Integer(1_000_000))
+        ));
+        assert!(matches!(resolve("-1_000"), Ok(Number::Integer(-1_000))));
+        assert!(matches!(resolve("0x1_00"), Ok(Number::Integer(0x1_00))));
+        assert!(matches!(resolve("0b1_0"), Ok(Number::Integer(0b1_0))));
+
+        match resolve("1_000.5") {
+            Ok(Number::Float(n)) => assert_eq!(n, 1_000.5),
+            _ => panic!("expected a float"),
+        }
+
+        assert!(matches!(
+            resolve("1_"),
+            Err(ParseError::BadNumberLiteral { .. })
+        ));
+        assert!(matches!(
+            resolve("0x_1"),
+            Err(ParseError::BadNumberLiteral { .. })
+        ));
+        assert!(matches!(
+            resolve("1_.5"),
+            Err(ParseError::BadNumberLiteral { .. })
+        ));
+    }
+
+    #[test]
+    fn test_suffixes() {
+        assert!(matches!(resolve("42i64"), Ok(Number::Integer(42))));
+        assert!(matches!(resolve("-128i8"), Ok(Number::Integer(-128))));
+        assert!(matches!(
+            resolve("4_294_967_295u32"),
+            Ok(Number::Integer(4_294_967_295))
+        ));
+        assert!(matches!(resolve("255u8"), Ok(Number::Byte(255))));
+        assert!(matches!(resolve("0xffu8"), Ok(Number::Byte(255))));
+
+        match resolve("3.0f64") {
+            Ok(Number::Float(n)) => assert_eq!(n, 3.0),
+            _ => panic!("expected a float"),
+        }
+
+        match resolve("3f32") {
+            Ok(Number::Float(n)) => assert_eq!(n, 3.0),
+            _ => panic!("expected a float"),
+        }
+
+        assert!(matches!(
+            resolve("256u8"),
+            Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                suffix: ast::NumberSuffix::U8,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            resolve("-1u8"),
+            Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                suffix: ast::NumberSuffix::U8,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            resolve("128i8"),
+            Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                suffix: ast::NumberSuffix::I8,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            resolve("9223372036854775808u64"),
+            Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                suffix: ast::NumberSuffix::U64,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            resolve("1.0e39f32"),
+            Err(ParseError::BadNumberOutOfBoundsForSuffix {
+                suffix: ast::NumberSuffix::F32,
+                ..
+            })
+        ));
+
+        assert!(matches!(
+            resolve("3.5i64"),
+            Err(ParseError::BadNumberLiteral { .. })
+        ));
+
+        assert!(matches!(
+            resolve("42u7"),
+            Err(ParseError::UnsupportedNumberSuffix { .. })
+        ));
+    }
+}
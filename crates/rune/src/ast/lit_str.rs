@@ -12,6 +12,8 @@ pub struct LitStr {
     token: ast::Token,
     /// If the string literal is escaped.
     escaped: bool,
+    /// If the string literal is raw.
+    raw: bool,
 }
 
 impl LitStr {
@@ -44,10 +46,34 @@ impl LitStr {
     }
 }
 
+impl LitStr {
+    /// Resolve a raw string literal, which is never escaped.
+    fn resolve_raw<'a>(&self, source: &'a Source) -> Result<Cow<'a, str>, ParseError> {
+        let span = self.token.span;
+
+        let string = source
+            .source(span)
+            .ok_or_else(|| ParseError::BadSlice { span })?;
+
+        // NB: skip the `r` prefix, and count the hashes that delimit the
+        // string.
+        let hashes = string[1..].chars().take_while(|c| *c == '#').count();
+        let string = string
+            .get(hashes + 2..string.len() - hashes - 1)
+            .ok_or_else(|| ParseError::BadSlice { span })?;
+
+        Ok(Cow::Borrowed(string))
+    }
+}
+
 impl<'a> Resolve<'a> for LitStr {
     type Output = Cow<'a, str>;
 
     fn resolve(&self, source: &'a Source) -> Result<Cow<'a, str>, ParseError> {
+        if self.raw {
+            return self.resolve_raw(source);
+        }
+
         let span = self.token.span.narrow(1);
         let string = source
             .source(span)
@@ -70,13 +96,18 @@ impl<'a> Resolve<'a> for LitStr {
 ///
 /// let item = parse_all::<ast::LitStr>("\"hello world\"").unwrap();
 /// let item = parse_all::<ast::LitStr>("\"hello\\nworld\"").unwrap();
+/// let item = parse_all::<ast::LitStr>("r#\"hello \"world\"\"#").unwrap();
 /// ```
 impl Parse for LitStr {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
         let token = parser.token_next()?;
 
         match token.kind {
-            ast::Kind::LitStr { escaped } => Ok(LitStr { token, escaped }),
+            ast::Kind::LitStr { escaped, raw } => Ok(LitStr {
+                token,
+                escaped,
+                raw,
+            }),
             _ => Err(ParseError::ExpectedString {
                 actual: token.kind,
                 span: token.span,
@@ -84,3 +115,47 @@ impl Parse for LitStr {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ParseError;
+    use crate::traits::Resolve as _;
+    use crate::{ast, parse_all};
+    use runestick::{Source, Span};
+
+    fn resolve(input: &str) -> Result<String, ParseError> {
+        let source = Source::new("test", input.to_owned());
+        let lit_str = parse_all::<ast::LitStr>(input)?;
+        Ok(lit_str.resolve(&source)?.into_owned())
+    }
+
+    #[test]
+    fn test_escapes() {
+        assert_eq!(resolve(r#""\u{1F600}""#).unwrap(), "😀");
+        assert_eq!(resolve(r#""a\u{e9}b""#).unwrap(), "aéb");
+        assert_eq!(resolve(r#""\x41\x7f""#).unwrap(), "A\x7f");
+
+        assert!(matches!(
+            resolve(r#""\u{D800}""#),
+            Err(ParseError::BadUnicodeEscape { .. })
+        ));
+
+        assert!(matches!(
+            resolve(r#""\x80""#),
+            Err(ParseError::UnsupportedUnicodeByteEscape { .. })
+        ));
+    }
+
+    #[test]
+    fn test_raw() {
+        assert_eq!(resolve(r#"r"a\nb""#).unwrap(), "a\\nb");
+        assert_eq!(resolve(r##"r#"a "quote" b"#"##).unwrap(), "a \"quote\" b");
+        assert_eq!(resolve(r###"r##"a "# b"##"###).unwrap(), "a \"# b");
+        assert_eq!(resolve(r#"r"""#).unwrap(), "");
+
+        assert!(matches!(
+            resolve(r##"r#"a "quote" b""##),
+            Err(ParseError::UnterminatedStrLit { span }) if span == Span::new(0, 15)
+        ));
+    }
+}
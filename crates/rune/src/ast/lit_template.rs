@@ -74,7 +74,7 @@ impl<'a> Resolve<'a> for LitTemplate {
                         buf.clear();
                     }
 
-                    let span = ast::utils::template_expr(span, &mut it)?;
+                    let span = ast::utils::template_expr(source.as_str(), 0, span, &mut it)?;
                     let source = &source.as_str()[..span.end];
 
                     let mut parser = Parser::new_with_start(source, span.start);
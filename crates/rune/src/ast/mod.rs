@@ -22,6 +22,7 @@ mod expr_break;
 mod expr_call;
 mod expr_call_macro;
 mod expr_closure;
+mod expr_continue;
 mod expr_else;
 mod expr_else_if;
 mod expr_field_access;
@@ -35,6 +36,7 @@ mod expr_is_not;
 mod expr_let;
 mod expr_loop;
 mod expr_match;
+mod expr_range;
 mod expr_return;
 mod expr_select;
 mod expr_try;
@@ -56,7 +58,9 @@ mod lit_vec;
 mod parenthesized;
 mod pat;
 mod pat_object;
+mod pat_or;
 mod pat_path;
+mod pat_rest;
 mod pat_tuple;
 mod pat_vec;
 mod path;
@@ -80,6 +84,7 @@ pub use self::expr_break::{ExprBreak, ExprBreakValue};
 pub use self::expr_call::ExprCall;
 pub use self::expr_call_macro::ExprCallMacro;
 pub use self::expr_closure::ExprClosure;
+pub use self::expr_continue::ExprContinue;
 pub use self::expr_else::ExprElse;
 pub use self::expr_else_if::ExprElseIf;
 pub use self::expr_field_access::{ExprField, ExprFieldAccess};
@@ -93,6 +98,7 @@ pub use self::expr_is_not::ExprIsNot;
 pub use self::expr_let::ExprLet;
 pub use self::expr_loop::ExprLoop;
 pub use self::expr_match::{ExprMatch, ExprMatchBranch};
+pub use self::expr_range::{ExprRange, ExprRangeLimits};
 pub use self::expr_return::ExprReturn;
 pub use self::expr_select::ExprSelect;
 pub use self::expr_try::ExprTry;
@@ -114,11 +120,13 @@ pub use self::lit_vec::LitVec;
 pub use self::parenthesized::Parenthesized;
 pub use self::pat::Pat;
 pub use self::pat_object::{PatObject, PatObjectItem};
+pub use self::pat_or::PatOr;
 pub use self::pat_path::PatPath;
+pub use self::pat_rest::PatRest;
 pub use self::pat_tuple::PatTuple;
 pub use self::pat_vec::PatVec;
 pub use self::path::Path;
-pub use self::token::{Delimiter, Kind, NumberKind, Token};
+pub use self::token::{Delimiter, Kind, NumberKind, NumberSuffix, Token};
 
 macro_rules! decl_tokens {
     ($(($parser:ident, $doc:expr, $($kind:tt)*),)*) => {
@@ -204,11 +212,13 @@ decl_tokens! {
     (For, "The `for` keyword.", Kind::For),
     (In, "The `in` keyword.", Kind::In),
     (Break, "The `break` keyword.", Kind::Break),
+    (Continue, "The `continue` keyword.", Kind::Continue),
     (Yield, "The `yield` keyword.", Kind::Yield),
     (Return, "The `return` keyword.", Kind::Return),
     (Rocket, "The rocket `=>`.", Kind::Rocket),
     (Hash, "The hash `#`.", Kind::Hash),
     (DotDot, "Two dots `..`.", Kind::DotDot),
+    (DotDotEq, "Two dots and an equals `..=`.", Kind::DotDotEq),
     (Await, "The `await` keyword.", Kind::Await),
     (Async, "The `async` keyword.", Kind::Async),
     (Select, "The `select` keyword.", Kind::Select),
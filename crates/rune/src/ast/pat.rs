@@ -28,6 +28,8 @@ pub enum Pat {
     PatTuple(ast::PatTuple),
     /// An object pattern.
     PatObject(ast::PatObject),
+    /// An or-pattern, like `1 | 2`.
+    PatOr(ast::PatOr),
 }
 
 impl Pat {
@@ -44,6 +46,7 @@ impl Pat {
             Self::PatVec(pat) => pat.span(),
             Self::PatTuple(pat) => pat.span(),
             Self::PatObject(pat) => pat.span(),
+            Self::PatOr(pat) => pat.span(),
         }
     }
 
@@ -106,6 +109,8 @@ impl Pat {
 /// parse_all::<ast::Pat>("var").unwrap();
 /// parse_all::<ast::Pat>("_").unwrap();
 /// parse_all::<ast::Pat>("Foo(n)").unwrap();
+/// parse_all::<ast::Pat>("[a, ..rest]").unwrap();
+/// parse_all::<ast::Pat>("(a, .., b)").unwrap();
 /// ```
 impl Parse for Pat {
     fn parse(parser: &mut Parser<'_>) -> Result<Self, ParseError> {
@@ -0,0 +1,44 @@
+use crate::ast::{Pat, Pipe};
+use crate::error::ParseError;
+use crate::parser::Parser;
+use runestick::Span;
+
+/// An or-pattern, like `1 | 2 | 3`, which matches if any of its alternatives
+/// match.
+#[derive(Debug, Clone)]
+pub struct PatOr {
+    /// The first alternative.
+    pub first: Box<Pat>,
+    /// The remaining alternatives, each preceded by a pipe.
+    pub rest: Vec<(Pipe, Pat)>,
+}
+
+impl PatOr {
+    /// Get the span of the pattern.
+    pub fn span(&self) -> Span {
+        match self.rest.last() {
+            Some((_, last)) => self.first.span().join(last.span()),
+            None => self.first.span(),
+        }
+    }
+
+    /// Iterate over all alternatives of the pattern.
+    pub fn alternatives(&self) -> impl Iterator<Item = &Pat> {
+        std::iter::once(&*self.first).chain(self.rest.iter().map(|(_, pat)| pat))
+    }
+
+    /// Parse the remaining alternatives of an or-pattern, given its first
+    /// alternative.
+    pub fn parse_with_first(parser: &mut Parser, first: Pat) -> Result<Self, ParseError> {
+        let mut rest = Vec::new();
+
+        while parser.peek::<Pipe>()? {
+            rest.push((parser.parse()?, parser.parse()?));
+        }
+
+        Ok(Self {
+            first: Box::new(first),
+            rest,
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use crate::ast::{Comma, DotDot, Ident, Pat};
+use crate::error::ParseError;
+use crate::parser::Parser;
+use crate::traits::Peek;
+use runestick::Span;
+
+/// A rest pattern in a vector or tuple pattern, like `..` or `..rest`, which
+/// matches any number of elements.
+///
+/// It can be followed by patterns matching the last elements, like `b` in
+/// `[a, .., b]`.
+#[derive(Debug, Clone)]
+pub struct PatRest {
+    /// The `..` token.
+    pub dot_dot: DotDot,
+    /// The variable the matched elements are bound to, if any.
+    pub binding: Option<Ident>,
+    /// The comma separating the rest pattern from the patterns following it.
+    pub comma: Option<Comma>,
+    /// The patterns following the rest pattern.
+    pub tail: Vec<(Box<Pat>, Option<Comma>)>,
+}
+
+impl PatRest {
+    /// Get the span of the pattern.
+    pub fn span(&self) -> Span {
+        let span = match &self.binding {
+            Some(binding) => self.dot_dot.span().join(binding.span()),
+            None => self.dot_dot.span(),
+        };
+
+        match self.tail.last() {
+            Some((pat, _)) => span.join(pat.span()),
+            None => span,
+        }
+    }
+
+    /// Parse a rest pattern and the patterns following it, up until the
+    /// closing delimiter `T`.
+    pub fn parse_until<T>(parser: &mut Parser) -> Result<Self, ParseError>
+    where
+        T: Peek,
+    {
+        let dot_dot = parser.parse()?;
+
+        let binding = if parser.peek::<Ident>()? {
+            Some(parser.parse()?)
+        } else {
+            None
+        };
+
+        let comma = if parser.peek::<Comma>()? {
+            Some(parser.parse()?)
+        } else {
+            None
+        };
+
+        let mut tail = Vec::new();
+
+        if comma.is_some() {
+            while !parser.peek::<T>()? {
+                let pat = parser.parse()?;
+
+                if !parser.peek::<Comma>()? {
+                    tail.push((Box::new(pat), None));
+                    break;
+                }
+
+                tail.push((Box::new(pat), Some(parser.parse()?)));
+            }
+        }
+
+        Ok(Self {
+            dot_dot,
+            binding,
+            comma,
+            tail,
+        })
+    }
+}
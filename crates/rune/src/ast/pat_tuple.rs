@@ -13,8 +13,8 @@ pub struct PatTuple {
     pub open: ast::OpenParen,
     /// The numbers matched against.
     pub items: Vec<(Box<ast::Pat>, Option<ast::Comma>)>,
-    /// Indicates if the pattern is open or not.
-    pub open_pattern: Option<ast::DotDot>,
+    /// The rest pattern, if the pattern is open.
+    pub open_pattern: Option<ast::PatRest>,
     /// The close bracket.
     pub close: ast::CloseParen,
 }
@@ -53,7 +53,7 @@ impl PatTuple {
         }
 
         let open_pattern = if is_open && parser.peek::<ast::DotDot>()? {
-            Some(parser.parse()?)
+            Some(ast::PatRest::parse_until::<ast::CloseParen>(parser)?)
         } else {
             None
         };
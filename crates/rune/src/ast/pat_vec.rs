@@ -1,4 +1,4 @@
-use crate::ast::{CloseBracket, Comma, DotDot, OpenBracket, Pat};
+use crate::ast::{CloseBracket, Comma, DotDot, OpenBracket, Pat, PatRest};
 use crate::error::ParseError;
 use crate::parser::Parser;
 use crate::traits::Parse;
@@ -11,8 +11,8 @@ pub struct PatVec {
     pub open: OpenBracket,
     /// The numbers matched against.
     pub items: Vec<(Box<Pat>, Option<Comma>)>,
-    /// Indicates if the pattern is open or not.
-    pub open_pattern: Option<DotDot>,
+    /// The rest pattern, if the pattern is open.
+    pub open_pattern: Option<PatRest>,
     /// The close bracket.
     pub close: CloseBracket,
 }
@@ -45,7 +45,7 @@ impl Parse for PatVec {
         }
 
         let open_pattern = if is_open && parser.peek::<DotDot>()? {
-            Some(parser.parse()?)
+            Some(PatRest::parse_until::<CloseBracket>(parser)?)
         } else {
             None
         };
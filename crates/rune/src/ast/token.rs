@@ -40,6 +40,77 @@ impl fmt::Display for NumberKind {
     }
 }
 
+/// The type suffix of a number literal, like `u8` in `255u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NumberSuffix {
+    /// The `i8` suffix.
+    I8,
+    /// The `i16` suffix.
+    I16,
+    /// The `i32` suffix.
+    I32,
+    /// The `i64` suffix.
+    I64,
+    /// The `u8` suffix.
+    U8,
+    /// The `u16` suffix.
+    U16,
+    /// The `u32` suffix.
+    U32,
+    /// The `u64` suffix.
+    U64,
+    /// The `f32` suffix.
+    F32,
+    /// The `f64` suffix.
+    F64,
+}
+
+impl NumberSuffix {
+    /// Parse the given suffix.
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    /// Get the suffix as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+        }
+    }
+
+    /// Test if the suffix is for a floating point number.
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+}
+
+impl fmt::Display for NumberSuffix {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
 /// A delimiter, `{`, `{`, or `[`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Delimiter {
@@ -112,6 +183,8 @@ pub enum Kind {
     False,
     /// A `break` token.
     Break,
+    /// A `continue` token.
+    Continue,
     /// A `yield` token.
     Yield,
     /// A `return` token.
@@ -140,6 +213,8 @@ pub enum Kind {
         is_negative: bool,
         /// The number literal kind.
         number: NumberKind,
+        /// The type suffix of the number literal, if any.
+        suffix: Option<NumberSuffix>,
     },
     /// A characer literal.
     LitChar,
@@ -149,6 +224,8 @@ pub enum Kind {
     LitStr {
         /// If the string literal contains escapes.
         escaped: bool,
+        /// If the string literal is raw, like `r#"hello"#`.
+        raw: bool,
     },
     /// A byte string literal, including escape sequences. Like `b"hello\nworld"`.
     LitByteStr {
@@ -210,6 +287,8 @@ pub enum Kind {
     QuestionMark,
     /// `..`.
     DotDot,
+    /// `..=`.
+    DotDotEq,
     /// `&&`.
     AmpAmp,
     /// `||`.
@@ -268,6 +347,7 @@ impl fmt::Display for Kind {
             Self::True => write!(f, "true")?,
             Self::False => write!(f, "false")?,
             Self::Break => write!(f, "break")?,
+            Self::Continue => write!(f, "continue")?,
             Self::Yield => write!(f, "yield")?,
             Self::Return => write!(f, "return")?,
             Self::Await => write!(f, "await")?,
@@ -322,6 +402,7 @@ impl fmt::Display for Kind {
             Self::Bang => write!(f, "!")?,
             Self::QuestionMark => write!(f, "?")?,
             Self::DotDot => write!(f, "..")?,
+            Self::DotDotEq => write!(f, "..=")?,
             Self::AmpAmp => write!(f, "&&")?,
             Self::PipePipe => write!(f, "||")?,
             Self::Pipe => write!(f, "|")?,
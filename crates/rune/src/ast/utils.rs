@@ -1,10 +1,12 @@
 use crate::ast;
 use crate::error::ParseError;
+use crate::lexer::Lexer;
 use runestick::Span;
 use std::iter::Peekable;
 use std::ops;
 
-/// Indicates if we are parsing braces.
+/// Indicates if we are parsing template escapes, which permits escaping
+/// braces and backticks.
 #[derive(Debug, Clone, Copy)]
 pub(super) struct WithBrace(pub(super) bool);
 
@@ -74,6 +76,7 @@ where
     Ok(match c {
         '{' if *with_brace => '{',
         '}' if *with_brace => '}',
+        '`' if *with_brace => '`',
         '\'' => '\'',
         '\"' => '\"',
         'n' => '\n',
@@ -142,7 +145,7 @@ where
         _ => return Err(ParseError::BadUnicodeEscape { span }),
     };
 
-    let mut first = true;
+    let mut digits = 0;
     let mut result = 0u32;
 
     loop {
@@ -154,10 +157,11 @@ where
 
         match c {
             '}' => {
-                if first {
+                if digits == 0 {
                     return Err(ParseError::BadUnicodeEscape { span });
                 }
 
+                // NB: rejects invalid scalar values, like surrogate halves.
                 if let Some(c) = std::char::from_u32(result) {
                     return Ok(c);
                 }
@@ -165,11 +169,15 @@ where
                 return Err(ParseError::BadUnicodeEscape { span });
             }
             c => {
-                first = false;
+                digits += 1;
 
-                result = result
-                    .checked_shl(4)
-                    .ok_or_else(|| ParseError::BadUnicodeEscape { span })?;
+                // NB: a unicode escape has at most 6 digits, which also
+                // guarantees that the result doesn't overflow.
+                if digits > 6 {
+                    return Err(ParseError::BadUnicodeEscape { span });
+                }
+
+                result <<= 4;
 
                 result += match c {
                     '0'..='9' => c as u32 - '0' as u32,
@@ -187,33 +195,52 @@ where
 /// Find the span of an expression inside of a balanced collection of braces.
 ///
 /// This is expected to start parsing immediately after an opening brace `{`.
-pub(crate) fn template_expr<I>(span: Span, it: &mut I) -> Result<Span, ParseError>
+/// The expression is lexed from `source`, where the positions produced by the
+/// iterator are relative to `offset`, so that braces inside of literals like
+/// strings and characters don't affect the nesting.
+pub(crate) fn template_expr<I>(
+    source: &str,
+    offset: usize,
+    span: Span,
+    it: &mut I,
+) -> Result<Span, ParseError>
 where
-    I: Iterator<Item = (usize, char)>,
+    I: Clone + Iterator<Item = (usize, char)>,
 {
-    let mut start = None;
-    let mut level = 1;
+    let start = match it.clone().next() {
+        Some((start, _)) => start,
+        None => return Err(ParseError::InvalidTemplateLiteral { span }),
+    };
 
-    loop {
-        let (n, c) = it
-            .next()
-            .ok_or_else(|| ParseError::InvalidTemplateLiteral { span })?;
+    let mut lexer = Lexer::new_with_start(source, offset + start);
+    let mut level = 1;
 
-        if start.is_none() {
-            start = Some(n);
-        }
+    let end = loop {
+        let token = match lexer.next()? {
+            Some(token) => token,
+            None => return Err(ParseError::InvalidTemplateLiteral { span }),
+        };
 
-        match c {
-            '{' => level += 1,
-            '}' => level -= 1,
+        match token.kind {
+            ast::Kind::Open(ast::Delimiter::Brace) => level += 1,
+            ast::Kind::Close(ast::Delimiter::Brace) => level -= 1,
             _ => (),
         }
 
         if level == 0 {
-            let start = start.ok_or_else(|| ParseError::InvalidTemplateLiteral { span })?;
-            return Ok(Span::new(start, n));
+            break token.span.start - offset;
+        }
+    };
+
+    // NB: advance the iterator past the closing brace, which must be part of
+    // the input it covers.
+    for (n, _) in it {
+        if n == end {
+            return Ok(Span::new(start, end));
         }
     }
+
+    Err(ParseError::InvalidTemplateLiteral { span })
 }
 
 /// Test if the given expression qualifieis as a block end or not, as with a
@@ -234,7 +261,7 @@ pub(crate) fn is_block_end(expr: &ast::Expr, comma: Option<&ast::Comma>) -> bool
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_hex_escape, parse_unicode_escape};
+    use super::{parse_hex_escape, parse_unicode_escape, template_expr};
     use runestick::Span;
 
     macro_rules! input {
@@ -260,5 +287,30 @@ mod tests {
 
         let c = parse_unicode_escape(Span::empty(), input!("{1f4af}")).unwrap();
         assert_eq!(c, '💯');
+
+        let c = parse_unicode_escape(Span::empty(), input!("{10FFFF}")).unwrap();
+        assert_eq!(c, '\u{10FFFF}');
+
+        assert!(parse_unicode_escape(Span::empty(), input!("{}")).is_err());
+        assert!(parse_unicode_escape(Span::empty(), input!("{D800}")).is_err());
+        assert!(parse_unicode_escape(Span::empty(), input!("{110000}")).is_err());
+        assert!(parse_unicode_escape(Span::empty(), input!("{0000001}")).is_err());
+        assert!(parse_unicode_escape(Span::empty(), input!("{FFFFFFFFF}")).is_err());
+    }
+
+    #[test]
+    fn test_template_expr() {
+        fn expr(source: &str) -> Result<Span, crate::ParseError> {
+            template_expr(source, 0, Span::empty(), &mut source.char_indices())
+        }
+
+        assert_eq!(expr("a}").unwrap(), Span::new(0, 1));
+        assert_eq!(expr("{ #{a: 1}.a }} rest").unwrap(), Span::new(0, 13));
+        assert_eq!(expr(r#""}" + "\"{"}"#).unwrap(), Span::new(0, 11));
+        assert_eq!(expr(r#"'"' + '}' + '\''}"#).unwrap(), Span::new(0, 16));
+        assert_eq!(expr(r##"r#"}"# + r"{"}"##).unwrap(), Span::new(0, 13));
+
+        assert!(expr("{a}").is_err());
+        assert!(expr(r#""}"#).is_err());
     }
 }
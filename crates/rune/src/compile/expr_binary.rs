@@ -1,4 +1,5 @@
 use crate::ast;
+use crate::compile::lit_template::compile_template;
 use crate::compiler::{Compiler, Needs};
 use crate::error::CompileResult;
 use crate::traits::{Compile, Resolve as _};
@@ -208,6 +209,24 @@ fn compile_assign_binop(
         return Err(CompileError::UnsupportedAssignExpr { span });
     };
 
+    // NB: a template string added to a string is appended to it in place,
+    // instead of first being built into a temporary string.
+    if let (ast::BinOp::AddAssign, ast::Expr::LitTemplate(lit_template)) = (bin_op, rhs) {
+        compile_template(compiler, lit_template, |len, size_hint| {
+            Inst::StringConcatAssign {
+                offset,
+                len,
+                size_hint,
+            }
+        })?;
+
+        if needs.value() {
+            compiler.asm.push(Inst::Unit, span);
+        }
+
+        return Ok(());
+    }
+
     compiler.compile((rhs, Needs::Value))?;
 
     match bin_op {
@@ -44,7 +44,8 @@ impl Compile<(&ast::ExprCall, Needs)> for Compiler<'_> {
 
                     let ident = ident.resolve(&*self.source)?;
                     let hash = Hash::of(ident);
-                    self.asm.push(Inst::CallInstance { hash, args }, span);
+                    let slot = self.unit.borrow_mut().new_instance_call_slot();
+                    self.asm.push(Inst::CallInstance { hash, args, slot }, span);
                 }
                 expr => {
                     log::trace!("ExprCall(Other) => {:?}", self.source.source(span));
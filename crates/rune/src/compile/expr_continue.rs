@@ -0,0 +1,51 @@
+use crate::ast;
+use crate::compiler::Compiler;
+use crate::error::CompileResult;
+use crate::{traits::Compile, CompileError};
+use runestick::Inst;
+
+/// Compile a continue expression.
+impl Compile<&ast::ExprContinue> for Compiler<'_> {
+    fn compile(&mut self, expr_continue: &ast::ExprContinue) -> CompileResult<()> {
+        let span = expr_continue.span();
+        log::trace!("ExprContinue => {:?}", self.source.source(span));
+
+        let current_loop = match self.loops.last() {
+            Some(current_loop) => current_loop,
+            None => {
+                return Err(CompileError::ContinueOutsideOfLoop { span });
+            }
+        };
+
+        let (last_loop, to_drop) = match expr_continue.label {
+            Some(label) => {
+                let (last_loop, mut to_drop) = self.loops.walk_until_label(&*self.source, label)?;
+
+                // NB: the loop being continued keeps its temporary, like its
+                // iterator.
+                if last_loop.drop.is_some() {
+                    to_drop.pop();
+                }
+
+                (last_loop, to_drop)
+            }
+            None => (current_loop, Vec::new()),
+        };
+
+        // Drop temporaries of the loops being exited.
+        for offset in to_drop {
+            self.asm.push(Inst::Drop { offset }, span);
+        }
+
+        let vars = self
+            .scopes
+            .last(span)?
+            .total_var_count
+            .checked_sub(last_loop.continue_var_count)
+            .ok_or_else(|| CompileError::internal("var count should be larger", span))?;
+
+        self.locals_pop(vars, span);
+        self.asm.jump(last_loop.continue_label, span);
+        Ok(())
+    }
+}
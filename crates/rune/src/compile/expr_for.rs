@@ -22,10 +22,12 @@ impl Compile<(&ast::ExprFor, Needs)> for Compiler<'_> {
             self.compile((&*expr_for.iter, Needs::Value))?;
 
             let iter_offset = loop_scope.decl_anon(span);
+            let slot = self.unit.borrow_mut().new_instance_call_slot();
             self.asm.push_with_comment(
                 Inst::CallInstance {
                     hash: *runestick::INTO_ITER,
                     args: 0,
+                    slot,
                 },
                 span,
                 format!("into_iter (offset: {})", iter_offset),
@@ -35,14 +37,6 @@ impl Compile<(&ast::ExprFor, Needs)> for Compiler<'_> {
             (iter_offset, loop_scope_expected)
         };
 
-        let _guard = self.loops.push(Loop {
-            label: expr_for.label.map(|(label, _)| label),
-            break_label,
-            total_var_count,
-            needs,
-            drop: Some(iter_offset),
-        });
-
         // Declare named loop variable.
         let binding_offset = {
             self.asm.push(Inst::Unit, expr_for.iter.span());
@@ -80,6 +74,17 @@ impl Compile<(&ast::ExprFor, Needs)> for Compiler<'_> {
             None
         };
 
+        // NB: the loop is continued after the loop variables are declared.
+        let _guard = self.loops.push(Loop {
+            label: expr_for.label.map(|(label, _)| label),
+            break_label,
+            continue_label: start_label,
+            continue_var_count: self.scopes.last(span)?.total_var_count,
+            total_var_count,
+            needs,
+            drop: Some(iter_offset),
+        });
+
         self.asm.label(start_label)?;
 
         // Use the memoized loop variable.
@@ -118,10 +123,12 @@ impl Compile<(&ast::ExprFor, Needs)> for Compiler<'_> {
                 expr_for.iter.span(),
             );
 
+            let slot = self.unit.borrow_mut().new_instance_call_slot();
             self.asm.push_with_comment(
                 Inst::CallInstance {
                     hash: *runestick::NEXT,
                     args: 0,
+                    slot,
                 },
                 span,
                 "next",
@@ -15,10 +15,14 @@ impl Compile<(&ast::ExprLoop, Needs)> for Compiler<'_> {
         let end_label = self.asm.new_label("loop_end");
         let break_label = self.asm.new_label("loop_break");
 
+        let total_var_count = self.scopes.last(span)?.total_var_count;
+
         let _guard = self.loops.push(Loop {
             label: expr_loop.label.map(|(label, _)| label),
             break_label,
-            total_var_count: self.scopes.last(span)?.total_var_count,
+            continue_label: start_label,
+            continue_var_count: total_var_count,
+            total_var_count,
             needs,
             drop: None,
         });
@@ -0,0 +1,40 @@
+use crate::ast;
+use crate::compiler::{Compiler, Needs};
+use crate::error::CompileResult;
+use crate::traits::Compile;
+use runestick::Inst;
+
+/// Compile a range expression.
+impl Compile<(&ast::ExprRange, Needs)> for Compiler<'_> {
+    fn compile(&mut self, (expr_range, needs): (&ast::ExprRange, Needs)) -> CompileResult<()> {
+        let span = expr_range.span();
+        log::trace!("ExprRange => {:?}", self.source.source(span));
+
+        if let Some(from) = &expr_range.from {
+            self.compile((&**from, Needs::Value))?;
+        }
+
+        if let Some(to) = &expr_range.to {
+            self.compile((&**to, Needs::Value))?;
+        }
+
+        let inclusive = matches!(expr_range.limits, ast::ExprRangeLimits::Closed(..));
+
+        self.asm.push(
+            Inst::Range {
+                from: expr_range.from.is_some(),
+                to: expr_range.to.is_some(),
+                inclusive,
+            },
+            span,
+        );
+
+        // NB: the bounds are still evaluated for their side effects.
+        if !needs.value() {
+            self.warnings.not_used(self.source_id, span, self.context());
+            self.asm.push(Inst::Pop, span);
+        }
+
+        Ok(())
+    }
+}
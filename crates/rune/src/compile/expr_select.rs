@@ -27,7 +27,13 @@ impl Compile<(&ast::ExprSelect, Needs)> for Compiler<'_> {
             self.compile((&*branch.expr, Needs::Value))?;
         }
 
-        self.asm.push(Inst::Select { len }, span);
+        // NB: with a default branch we only poll the futures once, and take
+        // the default branch if none of them are ready.
+        if expr_select.default_branch.is_some() {
+            self.asm.push(Inst::SelectDefault { len }, span);
+        } else {
+            self.asm.push(Inst::Select { len }, span);
+        }
 
         for (branch, (label, _)) in branches.iter().enumerate() {
             self.asm.jump_if_branch(branch as i64, *label, span);
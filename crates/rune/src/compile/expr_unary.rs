@@ -24,6 +24,9 @@ impl Compile<(&ast::ExprUnary, Needs)> for Compiler<'_> {
             ast::UnaryOp::Not { .. } => {
                 self.asm.push(Inst::Not, span);
             }
+            ast::UnaryOp::Neg { .. } => {
+                self.asm.push(Inst::Neg, span);
+            }
             op => {
                 return Err(CompileError::UnsupportedUnaryOp { span, op });
             }
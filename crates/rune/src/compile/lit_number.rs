@@ -1,6 +1,6 @@
 use crate::ast;
 use crate::compiler::{Compiler, Needs};
-use crate::error::CompileResult;
+use crate::error::{CompileResult, ParseError};
 use crate::traits::{Compile, Resolve as _};
 use runestick::Inst;
 
@@ -25,6 +25,20 @@ impl Compile<(&ast::LitNumber, Needs)> for Compiler<'_> {
             ast::Number::Integer(number) => {
                 self.asm.push(Inst::Integer { number }, span);
             }
+            ast::Number::Byte(b) => {
+                self.asm.push(Inst::Byte { b }, span);
+            }
+            ast::Number::BigInt(number) => {
+                if !self.options.big_integers {
+                    return Err(ParseError::BadNumberOutOfBounds { span }.into());
+                }
+
+                let slot = self
+                    .unit
+                    .borrow_mut()
+                    .new_static_string(&number.to_string())?;
+                self.asm.push(Inst::BigInt { slot }, span);
+            }
         }
 
         Ok(())
@@ -16,39 +16,50 @@ impl Compile<(&ast::LitTemplate, Needs)> for Compiler<'_> {
             return Ok(());
         }
 
-        let template = lit_template.resolve(&*self.source)?;
+        compile_template(self, lit_template, |len, size_hint| Inst::StringConcat {
+            len,
+            size_hint,
+        })
+    }
+}
 
-        if !template.has_expansions {
-            self.warnings
-                .template_without_expansions(self.source_id, span, self.context());
-        }
+/// Compile the components of a template string onto the stack, and consume
+/// them with the instruction constructed out of their number and size hint.
+pub(super) fn compile_template(
+    compiler: &mut Compiler<'_>,
+    lit_template: &ast::LitTemplate,
+    inst: impl FnOnce(usize, usize) -> Inst,
+) -> CompileResult<()> {
+    let span = lit_template.span();
+    let template = lit_template.resolve(&*compiler.source)?;
+
+    if !template.has_expansions {
+        compiler
+            .warnings
+            .template_without_expansions(compiler.source_id, span, compiler.context());
+    }
 
-        let scope = self.scopes.child(span)?;
-        let expected = self.scopes.push(scope);
-
-        for c in template.components.iter() {
-            match c {
-                ast::TemplateComponent::String(string) => {
-                    let slot = self.unit.borrow_mut().new_static_string(&string)?;
-                    self.asm.push(Inst::String { slot }, span);
-                    self.scopes.decl_anon(span)?;
-                }
-                ast::TemplateComponent::Expr(expr) => {
-                    self.compile((&**expr, Needs::Value))?;
-                    self.scopes.decl_anon(span)?;
-                }
+    let scope = compiler.scopes.child(span)?;
+    let expected = compiler.scopes.push(scope);
+
+    for c in template.components.iter() {
+        match c {
+            ast::TemplateComponent::String(string) => {
+                let slot = compiler.unit.borrow_mut().new_static_string(&string)?;
+                compiler.asm.push(Inst::String { slot }, span);
+                compiler.scopes.decl_anon(span)?;
+            }
+            ast::TemplateComponent::Expr(expr) => {
+                compiler.compile((&**expr, Needs::Value))?;
+                compiler.scopes.decl_anon(span)?;
             }
         }
+    }
 
-        self.asm.push(
-            Inst::StringConcat {
-                len: template.components.len(),
-                size_hint: template.size_hint,
-            },
-            span,
-        );
+    compiler
+        .asm
+        .push(inst(template.components.len(), template.size_hint), span);
 
-        let _ = self.scopes.pop(expected, span)?;
-        Ok(())
-    }
+    let _ = compiler.scopes.pop(expected, span)?;
+    Ok(())
 }
@@ -6,6 +6,7 @@ mod expr_block;
 mod expr_break;
 mod expr_call;
 mod expr_closure;
+mod expr_continue;
 mod expr_field_access;
 mod expr_for;
 mod expr_if;
@@ -15,6 +16,7 @@ mod expr_let;
 mod expr_loop;
 mod expr_match;
 mod expr_path;
+mod expr_range;
 mod expr_return;
 mod expr_select;
 mod expr_self;
@@ -684,10 +684,15 @@ impl<'a> Compiler<'a> {
         // that it is indeed a vector.
         self.asm.push(Inst::Copy { offset }, span);
 
+        let tail = pat_vec
+            .open_pattern
+            .as_ref()
+            .map_or(0, |pat_rest| pat_rest.tail.len());
+
         self.asm.push(
             Inst::MatchSequence {
                 type_check: TypeCheck::Vec,
-                len: pat_vec.items.len(),
+                len: pat_vec.items.len() + tail,
                 exact: pat_vec.open_pattern.is_none(),
             },
             span,
@@ -706,6 +711,167 @@ impl<'a> Compiler<'a> {
             self.compile_pat(scope, &*pat, false_label, &load)?;
         }
 
+        if let Some(pat_rest) = &pat_vec.open_pattern {
+            let start = pat_vec.items.len();
+            self.compile_pat_rest(scope, pat_rest, offset, start, false_label)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encode the rest pattern of a vector or tuple pattern, which binds the
+    /// elements it covers and matches the patterns following it.
+    ///
+    /// The sequence being matched is stored at `offset`, and `start` is the
+    /// number of patterns preceding the rest pattern.
+    fn compile_pat_rest(
+        &mut self,
+        scope: &mut Scope,
+        pat_rest: &ast::PatRest,
+        offset: usize,
+        start: usize,
+        false_label: Label,
+    ) -> CompileResult<()> {
+        let span = pat_rest.span();
+        log::trace!("PatRest => {:?}", self.source.source(span));
+
+        let end = pat_rest.tail.len();
+
+        if let Some(binding) = &pat_rest.binding {
+            let span = binding.span();
+            let source = self.source.clone();
+            let name = binding.resolve(&*source)?;
+
+            self.asm
+                .push(Inst::TupleSliceAt { offset, start, end }, span);
+            scope.decl_var(name, span);
+        }
+
+        for (n, (pat, _)) in pat_rest.tail.iter().enumerate() {
+            let span = pat.span();
+            let index = end - n - 1;
+
+            let load = move |asm: &mut Assembly| {
+                asm.push(Inst::TupleIndexGetBackAt { offset, index }, span);
+            };
+
+            self.compile_pat(scope, &*pat, false_label, &load)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encode an or-pattern match.
+    ///
+    /// The alternatives are tested in order. The variables bound by the one
+    /// that matches are then moved into the same slots, regardless of which
+    /// alternative it was, so that the code following the pattern sees a
+    /// single stack layout.
+    pub(crate) fn compile_pat_or(
+        &mut self,
+        scope: &mut Scope,
+        pat_or: &ast::PatOr,
+        false_label: Label,
+        load: &dyn Fn(&mut Assembly),
+    ) -> CompileResult<()> {
+        let span = pat_or.span();
+        log::trace!("PatOr => {:?}", self.source.source(span));
+
+        // NB: a failed match pops every variable in the scope before jumping
+        // to the false label, so the next alternative can only be tested if
+        // the scope is empty.
+        if scope.local_var_count != 0 {
+            return Err(CompileError::UnsupportedPattern { span });
+        }
+
+        let base = scope.total_var_count;
+        let matched = self.asm.new_label("pat_or_matched");
+        let mut bindings = None::<Vec<(String, Span)>>;
+        let mut alternatives = pat_or.alternatives().peekable();
+
+        while let Some(pat) = alternatives.next() {
+            let span = pat.span();
+
+            let next_label = if alternatives.peek().is_some() {
+                Some(self.asm.new_label("pat_or_next"))
+            } else {
+                None
+            };
+
+            let mut alternative = scope.clone();
+            self.compile_pat(
+                &mut alternative,
+                pat,
+                next_label.unwrap_or(false_label),
+                load,
+            )?;
+
+            let vars = alternative.vars();
+
+            let bindings = bindings.get_or_insert_with(|| {
+                vars.iter()
+                    .map(|(name, var)| (name.to_string(), var.span()))
+                    .collect()
+            });
+
+            let missing = vars
+                .iter()
+                .map(|(name, _)| *name)
+                .find(|name| !bindings.iter().any(|(n, _)| n == name))
+                .or_else(|| {
+                    bindings
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .find(|name| alternative.get(name).is_none())
+                });
+
+            if let Some(name) = missing {
+                return Err(CompileError::InconsistentOrPatternBindings {
+                    span,
+                    name: name.to_owned(),
+                });
+            }
+
+            let offsets = bindings
+                .iter()
+                .map(|(name, _)| alternative.get(name).map(|var| var.offset))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| CompileError::internal("missing or-pattern binding", span))?;
+
+            let is_canonical = alternative.local_var_count == offsets.len()
+                && offsets.iter().enumerate().all(|(n, o)| *o == base + n);
+
+            // Move the bound variables into place, and pop any temporaries
+            // used while matching.
+            if !is_canonical {
+                for offset in &offsets {
+                    self.asm.push(Inst::Copy { offset: *offset }, span);
+                }
+
+                for n in (0..offsets.len()).rev() {
+                    self.asm.push(Inst::Replace { offset: base + n }, span);
+                }
+
+                let count = alternative.local_var_count - offsets.len();
+
+                if count > 0 {
+                    self.asm.push(Inst::PopN { count }, span);
+                }
+            }
+
+            self.asm.jump(matched, span);
+
+            if let Some(next_label) = next_label {
+                self.asm.label(next_label)?;
+            }
+        }
+
+        self.asm.label(matched)?;
+
+        for (name, span) in bindings.into_iter().flatten() {
+            scope.decl_var(&name, span);
+        }
+
         Ok(())
     }
 
@@ -725,6 +891,11 @@ impl<'a> Compiler<'a> {
         load(&mut self.asm);
         let offset = scope.decl_anon(span);
 
+        let tail = pat_tuple
+            .open_pattern
+            .as_ref()
+            .map_or(0, |pat_rest| pat_rest.tail.len());
+
         let type_check = if let Some(path) = &pat_tuple.path {
             let item = self.convert_path_to_item(path)?;
 
@@ -749,7 +920,7 @@ impl<'a> Compiler<'a> {
                     return Err(CompileError::UnsupportedPattern { span });
                 };
 
-            let count = pat_tuple.items.len();
+            let count = pat_tuple.items.len() + tail;
             let is_open = pat_tuple.open_pattern.is_some();
 
             if !(tuple.args == count || count < tuple.args && is_open) {
@@ -773,7 +944,7 @@ impl<'a> Compiler<'a> {
         self.asm.push(
             Inst::MatchSequence {
                 type_check,
-                len: pat_tuple.items.len(),
+                len: pat_tuple.items.len() + tail,
                 exact: pat_tuple.open_pattern.is_none(),
             },
             span,
@@ -791,6 +962,11 @@ impl<'a> Compiler<'a> {
             self.compile_pat(scope, &*pat, false_label, &load)?;
         }
 
+        if let Some(pat_rest) = &pat_tuple.open_pattern {
+            let start = pat_tuple.items.len();
+            self.compile_pat_rest(scope, pat_rest, offset, start, false_label)?;
+        }
+
         Ok(())
     }
 
@@ -1034,15 +1210,31 @@ impl<'a> Compiler<'a> {
                 let span = number_literal.span();
                 let number = number_literal.resolve(&*self.source)?;
 
-                let integer = match number {
-                    ast::Number::Integer(integer) => integer,
-                    ast::Number::Float(..) => {
-                        return Err(CompileError::MatchFloatInPattern { span });
+                load(&mut self.asm);
+
+                match number {
+                    ast::Number::Integer(integer) => {
+                        self.asm.push(Inst::EqInteger { integer }, span);
                     }
-                };
+                    ast::Number::Float(float) => {
+                        self.asm.push(Inst::EqFloat { float }, span);
+                    }
+                    ast::Number::Byte(byte) => {
+                        self.asm.push(Inst::EqByte { byte }, span);
+                    }
+                    ast::Number::BigInt(number) => {
+                        if !self.options.big_integers {
+                            return Err(ParseError::BadNumberOutOfBounds { span }.into());
+                        }
 
-                load(&mut self.asm);
-                self.asm.push(Inst::EqInteger { integer }, span);
+                        let slot = self
+                            .unit
+                            .borrow_mut()
+                            .new_static_string(&number.to_string())?;
+                        self.asm.push(Inst::BigInt { slot }, span);
+                        self.asm.push(Inst::Eq, span);
+                    }
+                }
             }
             ast::Pat::PatString(pat_string) => {
                 let span = pat_string.span();
@@ -1063,6 +1255,10 @@ impl<'a> Compiler<'a> {
                 self.compile_pat_object(scope, object, false_label, &load)?;
                 return Ok(true);
             }
+            ast::Pat::PatOr(pat_or) => {
+                self.compile_pat_or(scope, pat_or, false_label, &load)?;
+                return Ok(true);
+            }
         }
 
         self.asm
@@ -255,6 +255,20 @@ pub enum ParseError {
         /// Span of the illegal number literal.
         span: Span,
     },
+    /// Number out of bounds for its type suffix.
+    #[error("number literal out of bounds for `{suffix}`")]
+    BadNumberOutOfBoundsForSuffix {
+        /// Span of the illegal number literal.
+        span: Span,
+        /// The type suffix of the number literal.
+        suffix: ast::NumberSuffix,
+    },
+    /// Number literal with an unsupported type suffix.
+    #[error("unsupported number suffix")]
+    UnsupportedNumberSuffix {
+        /// Span of the unsupported suffix.
+        span: Span,
+    },
     /// A bad character literal.
     #[error("bad character literal")]
     BadCharLiteral {
@@ -267,6 +281,12 @@ pub enum ParseError {
         /// Span containing the bad byte literal.
         span: Span,
     },
+    /// A non-ASCII character was used in a byte string literal.
+    #[error("non-ASCII character in byte string literal")]
+    NonAsciiByteStr {
+        /// Span of the offending character.
+        span: Span,
+    },
     /// We tried to parse a unicode escape in a byte sequence.
     #[error("unicode escapes are not supported as a byte or byte string")]
     UnicodeEscapeNotSupported {
@@ -386,8 +406,11 @@ impl ParseError {
             Self::BadEscapeSequence { span, .. } => span,
             Self::BadNumberLiteral { span, .. } => span,
             Self::BadNumberOutOfBounds { span, .. } => span,
+            Self::BadNumberOutOfBoundsForSuffix { span, .. } => span,
+            Self::UnsupportedNumberSuffix { span, .. } => span,
             Self::BadCharLiteral { span, .. } => span,
             Self::BadByteLiteral { span, .. } => span,
+            Self::NonAsciiByteStr { span, .. } => span,
             Self::UnicodeEscapeNotSupported { span, .. } => span,
             Self::BadUnicodeEscape { span, .. } => span,
             Self::UnsupportedUnicodeByteEscape { span, .. } => span,
@@ -693,6 +716,14 @@ pub enum CompileError {
         /// Span where the error occured.
         span: Span,
     },
+    /// The alternatives of an or-pattern don't all bind the same variables.
+    #[error("variable `{name}` is not bound in all alternatives of the pattern")]
+    InconsistentOrPatternBindings {
+        /// Span of the alternative which is inconsistent with the first one.
+        span: Span,
+        /// The name of the variable which is inconsistently bound.
+        name: String,
+    },
     /// The pattern is not supported as a binding.
     #[error("not a valid binding")]
     UnsupportedBinding {
@@ -705,6 +736,12 @@ pub enum CompileError {
         /// The span of the illegal break.
         span: Span,
     },
+    /// Error raised when trying to use a continue outside of a loop.
+    #[error("continue outside of loop")]
+    ContinueOutsideOfLoop {
+        /// The span of the illegal continue.
+        span: Span,
+    },
     /// An error raised when attempting to return locally created references
     /// from a function.
     #[error("cannot return locally created references")]
@@ -716,12 +753,6 @@ pub enum CompileError {
         /// The references we tried to return.
         references_at: Vec<Span>,
     },
-    /// Attempting to use a float in a match pattern.
-    #[error("floating point numbers cannot be used in patterns")]
-    MatchFloatInPattern {
-        /// Where the float was used.
-        span: Span,
-    },
     /// Attempting to create an object with a duplicate object key.
     #[error("duplicate key in literal object")]
     DuplicateObjectKey {
@@ -835,10 +866,11 @@ impl CompileError {
             Self::UnsupportedMetaPattern { span, .. } => span,
             Self::UnsupportedMetaClosure { span, .. } => span,
             Self::UnsupportedPattern { span, .. } => span,
+            Self::InconsistentOrPatternBindings { span, .. } => span,
             Self::UnsupportedBinding { span, .. } => span,
             Self::BreakOutsideOfLoop { span, .. } => span,
+            Self::ContinueOutsideOfLoop { span, .. } => span,
             Self::ReturnLocalReferences { span, .. } => span,
-            Self::MatchFloatInPattern { span, .. } => span,
             Self::DuplicateObjectKey { span, .. } => span,
             Self::LitObjectMissingField { span, .. } => span,
             Self::LitObjectNotField { span, .. } => span,
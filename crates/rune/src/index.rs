@@ -344,6 +344,11 @@ impl Index<ast::Pat> for Indexer<'_> {
             ast::Pat::PatTuple(pat_tuple) => {
                 self.index(pat_tuple)?;
             }
+            ast::Pat::PatOr(pat_or) => {
+                for pat in pat_or.alternatives() {
+                    self.index(pat)?;
+                }
+            }
             ast::Pat::PatByte(..) => (),
             ast::Pat::PatIgnore(..) => (),
             ast::Pat::PatNumber(..) => (),
@@ -362,6 +367,24 @@ impl Index<ast::PatTuple> for Indexer<'_> {
             self.index(&**pat)?;
         }
 
+        if let Some(pat_rest) = &pat_tuple.open_pattern {
+            self.index(pat_rest)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Index<ast::PatRest> for Indexer<'_> {
+    fn index(&mut self, pat_rest: &ast::PatRest) -> Result<(), CompileError> {
+        if let Some(binding) = &pat_rest.binding {
+            self.index(binding)?;
+        }
+
+        for (pat, _) in &pat_rest.tail {
+            self.index(&**pat)?;
+        }
+
         Ok(())
     }
 }
@@ -391,6 +414,10 @@ impl Index<ast::PatVec> for Indexer<'_> {
             self.index(&**pat)?;
         }
 
+        if let Some(pat_rest) = &pat_vec.open_pattern {
+            self.index(pat_rest)?;
+        }
+
         Ok(())
     }
 }
@@ -419,6 +446,9 @@ impl Index<ast::Expr> for Indexer<'_> {
             ast::Expr::ExprBinary(expr_binary) => {
                 self.index(expr_binary)?;
             }
+            ast::Expr::ExprRange(expr_range) => {
+                self.index(expr_range)?;
+            }
             ast::Expr::ExprMatch(expr_if) => {
                 self.index(expr_if)?;
             }
@@ -452,6 +482,8 @@ impl Index<ast::Expr> for Indexer<'_> {
             ast::Expr::ExprBreak(expr_break) => {
                 self.index(expr_break)?;
             }
+            // NB: continue doesn't contain anything to index.
+            ast::Expr::ExprContinue(..) => (),
             ast::Expr::ExprYield(expr_yield) => {
                 self.index(expr_yield)?;
             }
@@ -521,6 +553,20 @@ impl Index<ast::ExprBinary> for Indexer<'_> {
     }
 }
 
+impl Index<ast::ExprRange> for Indexer<'_> {
+    fn index(&mut self, expr_range: &ast::ExprRange) -> Result<(), CompileError> {
+        if let Some(from) = &expr_range.from {
+            self.index(&**from)?;
+        }
+
+        if let Some(to) = &expr_range.to {
+            self.index(&**to)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Index<ast::ExprMatch> for Indexer<'_> {
     fn index(&mut self, expr_match: &ast::ExprMatch) -> Result<(), CompileError> {
         self.index(&*expr_match.expr)?;
@@ -826,6 +872,24 @@ impl Index<ast::ExprSelect> for Indexer<'_> {
 
 impl Index<ast::ExprCall> for Indexer<'_> {
     fn index(&mut self, expr_call: &ast::ExprCall) -> Result<(), CompileError> {
+        // NB: instance calls compile their receiver before their arguments,
+        // and they must be indexed in the same order so that closures and
+        // async blocks are assigned the same items as when they are compiled.
+        if let ast::Expr::ExprFieldAccess(ast::ExprFieldAccess {
+            expr,
+            expr_field: ast::ExprField::Ident(..),
+            ..
+        }) = &*expr_call.expr
+        {
+            self.index(&**expr)?;
+
+            for (expr, _) in expr_call.args.items.iter() {
+                self.index(expr)?;
+            }
+
+            return Ok(());
+        }
+
         for (expr, _) in expr_call.args.items.iter() {
             self.index(expr)?;
         }
@@ -64,6 +64,20 @@ impl<'a> Lexer<'a> {
             .unwrap_or_else(|| self.source.len())
     }
 
+    /// Test if the given keyword is at the given position in the source,
+    /// and isn't just the prefix of a longer identifier.
+    fn is_keyword_at(&self, start: usize, keyword: &str) -> bool {
+        let rest = match self.source.get(start..) {
+            Some(rest) if rest.starts_with(keyword) => &rest[keyword.len()..],
+            _ => return false,
+        };
+
+        match rest.chars().next() {
+            Some(c) => !(c.is_alphanumeric() || c == '_'),
+            None => true,
+        }
+    }
+
     fn next_ident<I>(&mut self, it: &mut I, start: usize) -> Result<Option<ast::Token>, ParseError>
     where
         I: Clone + Iterator<Item = (usize, char)>,
@@ -103,6 +117,7 @@ impl<'a> Lexer<'a> {
             "is" => ast::Kind::Is,
             "not" => ast::Kind::Not,
             "break" => ast::Kind::Break,
+            "continue" => ast::Kind::Continue,
             "yield" => ast::Kind::Yield,
             "return" => ast::Kind::Return,
             "await" => ast::Kind::Await,
@@ -111,6 +126,12 @@ impl<'a> Lexer<'a> {
             "default" => ast::Kind::Default,
             "impl" => ast::Kind::Impl,
             "mod" => ast::Kind::Mod,
+            "inf" | "nan" => ast::Kind::LitNumber {
+                is_fractional: true,
+                is_negative: false,
+                number: ast::NumberKind::Decimal,
+                suffix: None,
+            },
             _ => ast::Kind::Ident,
         };
 
@@ -155,6 +176,8 @@ impl<'a> Lexer<'a> {
             ast::NumberKind::Decimal
         };
 
+        let mut suffix_start = None;
+
         self.cursor = loop {
             let (n, c) = match it.next() {
                 Some((n, c)) => (n, c),
@@ -162,24 +185,50 @@ impl<'a> Lexer<'a> {
             };
 
             match c {
-                c if char::is_alphanumeric(c) => (),
-                '.' if !is_fractional => {
-                    is_fractional = true;
-
-                    // char immediately following a dot should be numerical.
+                // NB: `f` is a hex digit, so hex literals can't have a float
+                // suffix.
+                'i' | 'u' if suffix_start.is_none() => {
+                    suffix_start = Some(self.cursor + n);
+                }
+                'f' if suffix_start.is_none() && number != ast::NumberKind::Hex => {
+                    suffix_start = Some(self.cursor + n);
+                }
+                c if char::is_alphanumeric(c) || c == '_' => (),
+                '.' if !is_fractional && suffix_start.is_none() => {
+                    // char immediately following a dot should be numerical,
+                    // otherwise the dot belongs to something else like a
+                    // range `0..10` or a field access.
                     if !it.next().map(|(_, c)| c.is_numeric()).unwrap_or_default() {
                         break self.cursor + n;
                     }
+
+                    is_fractional = true;
                 }
                 _ => break self.cursor + n,
             }
         };
 
+        let suffix = match suffix_start {
+            Some(suffix_start) => {
+                let span = Span::new(suffix_start, self.cursor);
+
+                let suffix = self
+                    .source
+                    .get(suffix_start..self.cursor)
+                    .and_then(ast::NumberSuffix::parse)
+                    .ok_or_else(|| ParseError::UnsupportedNumberSuffix { span })?;
+
+                Some(suffix)
+            }
+            None => None,
+        };
+
         Ok(Some(ast::Token {
             kind: ast::Kind::LitNumber {
                 is_fractional,
                 is_negative,
                 number,
+                suffix,
             },
             span: Span {
                 start,
@@ -369,7 +418,78 @@ impl<'a> Lexer<'a> {
         };
 
         Ok(Some(ast::Token {
-            kind: ast::Kind::LitStr { escaped },
+            kind: ast::Kind::LitStr {
+                escaped,
+                raw: false,
+            },
+            span: Span {
+                start,
+                end: self.cursor,
+            },
+        }))
+    }
+
+    /// Test if the given iterator is at the start of a raw string literal,
+    /// after the `r` prefix.
+    fn is_raw_lit_str<I>(mut it: I) -> bool
+    where
+        I: Iterator<Item = (usize, char)>,
+    {
+        loop {
+            match it.next() {
+                Some((_, '#')) => (),
+                Some((_, '"')) => return true,
+                _ => return false,
+            }
+        }
+    }
+
+    /// Consume a raw string literal, like `r#"hello"#`.
+    ///
+    /// This expects the `r` prefix to have been consumed.
+    fn next_raw_lit_str<I>(
+        &mut self,
+        it: &mut I,
+        start: usize,
+    ) -> Result<Option<ast::Token>, ParseError>
+    where
+        I: Clone + Iterator<Item = (usize, char)>,
+    {
+        let mut hashes = 0;
+
+        while let Some((_, '#')) = it.next() {
+            hashes += 1;
+        }
+
+        self.cursor = loop {
+            match it.next() {
+                Some((_, '"')) => {
+                    let mut closing = it.clone();
+
+                    // NB: the string is only closed by a quote followed by
+                    // the same number of hashes as it was opened with.
+                    if (0..hashes).all(|_| matches!(closing.next(), Some((_, '#')))) {
+                        *it = closing;
+                        break self.end_span(it);
+                    }
+                }
+                Some(..) => (),
+                None => {
+                    return Err(ParseError::UnterminatedStrLit {
+                        span: Span {
+                            start,
+                            end: self.source.len(),
+                        },
+                    });
+                }
+            }
+        };
+
+        Ok(Some(ast::Token {
+            kind: ast::Kind::LitStr {
+                escaped: false,
+                raw: true,
+            },
             span: Span {
                 start,
                 end: self.cursor,
@@ -445,7 +565,7 @@ impl<'a> Lexer<'a> {
                     '`' => self.end_span(it),
                     '{' => {
                         let span = Span::new(start, n);
-                        utils::template_expr(span, it)?;
+                        utils::template_expr(self.source, self.cursor, span, it)?;
                         continue;
                     }
                     '\\' => match it.next() {
@@ -600,7 +720,13 @@ impl<'a> Lexer<'a> {
                         }
                         ('.', '.') => {
                             it.next();
-                            break ast::Kind::DotDot;
+
+                            break if matches!(it.clone().next(), Some((_, '='))) {
+                                it.next();
+                                ast::Kind::DotDotEq
+                            } else {
+                                ast::Kind::DotDot
+                            };
                         }
                         ('=', '>') => {
                             it.next();
@@ -610,11 +736,30 @@ impl<'a> Lexer<'a> {
                             it.next();
                             return self.next_number_literal(&mut it, c, start, true);
                         }
+                        ('-', 'i') if self.is_keyword_at(start + 1, "inf") => {
+                            self.cursor = start + 4;
+
+                            return Ok(Some(ast::Token {
+                                kind: ast::Kind::LitNumber {
+                                    is_fractional: true,
+                                    is_negative: true,
+                                    number: ast::NumberKind::Decimal,
+                                    suffix: None,
+                                },
+                                span: Span {
+                                    start,
+                                    end: self.cursor,
+                                },
+                            }));
+                        }
                         ('b', '\'') => {
                             it.next();
                             it.next();
                             return self.next_lit_byte(&mut it, start);
                         }
+                        ('r', '"') | ('r', '#') if Self::is_raw_lit_str(it.clone()) => {
+                            return self.next_raw_lit_str(&mut it, start);
+                        }
                         ('b', '"') => {
                             it.next();
                             it.next();
@@ -741,6 +886,7 @@ mod tests {
                 span: Span::new(10, 19),
                 kind: ast::Kind::LitStr {
                     escaped: false,
+                    raw: false,
                 },
             }
         };
@@ -811,6 +957,7 @@ mod tests {
                     is_fractional: false,
                     is_negative: false,
                     number: ast::NumberKind::Decimal,
+                    suffix: None,
                 },
             },
             ast::Token {
@@ -820,6 +967,116 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_number_suffixes() {
+        test_lexer! {
+            "42i64 0xffu8 3.0f64",
+            ast::Token {
+                span: Span::new(0, 5),
+                kind: ast::Kind::LitNumber {
+                    is_fractional: false,
+                    is_negative: false,
+                    number: ast::NumberKind::Decimal,
+                    suffix: Some(ast::NumberSuffix::I64),
+                },
+            },
+            ast::Token {
+                span: Span::new(6, 12),
+                kind: ast::Kind::LitNumber {
+                    is_fractional: false,
+                    is_negative: false,
+                    number: ast::NumberKind::Hex,
+                    suffix: Some(ast::NumberSuffix::U8),
+                },
+            },
+            ast::Token {
+                span: Span::new(13, 19),
+                kind: ast::Kind::LitNumber {
+                    is_fractional: true,
+                    is_negative: false,
+                    number: ast::NumberKind::Decimal,
+                    suffix: Some(ast::NumberSuffix::F64),
+                },
+            },
+        };
+    }
+
+    #[test]
+    fn test_ranges() {
+        let number = ast::Kind::LitNumber {
+            is_fractional: false,
+            is_negative: false,
+            number: ast::NumberKind::Decimal,
+            suffix: None,
+        };
+
+        test_lexer! {
+            "0..10 0..=10",
+            ast::Token {
+                span: Span::new(0, 1),
+                kind: number,
+            },
+            ast::Token {
+                span: Span::new(1, 3),
+                kind: ast::Kind::DotDot,
+            },
+            ast::Token {
+                span: Span::new(3, 5),
+                kind: number,
+            },
+            ast::Token {
+                span: Span::new(6, 7),
+                kind: number,
+            },
+            ast::Token {
+                span: Span::new(7, 10),
+                kind: ast::Kind::DotDotEq,
+            },
+            ast::Token {
+                span: Span::new(10, 12),
+                kind: number,
+            },
+        };
+    }
+
+    #[test]
+    fn test_float_keywords() {
+        let number = |is_negative| ast::Kind::LitNumber {
+            is_fractional: true,
+            is_negative,
+            number: ast::NumberKind::Decimal,
+            suffix: None,
+        };
+
+        test_lexer! {
+            "inf -inf nan infinity -info",
+            ast::Token {
+                span: Span::new(0, 3),
+                kind: number(false),
+            },
+            ast::Token {
+                span: Span::new(4, 8),
+                kind: number(true),
+            },
+            ast::Token {
+                span: Span::new(9, 12),
+                kind: number(false),
+            },
+            ast::Token {
+                span: Span::new(13, 21),
+                kind: ast::Kind::Ident,
+            },
+            ast::Token {
+                span: Span::new(22, 23),
+                kind: ast::Kind::Dash,
+            },
+            ast::Token {
+                span: Span::new(23, 27),
+                kind: ast::Kind::Ident,
+            },
+        };
+    }
+
     #[test]
     fn test_template_literals() {
         test_lexer! {
@@ -23,6 +23,10 @@ pub(crate) struct Loop {
     pub(crate) label: Option<ast::Label>,
     /// The end label of the loop.
     pub(crate) break_label: Label,
+    /// The label to jump to when continuing the loop.
+    pub(crate) continue_label: Label,
+    /// The number of variables observed where the loop is continued.
+    pub(crate) continue_var_count: usize,
     /// The number of variables observed at the start of the loop.
     pub(crate) total_var_count: usize,
     /// If the loop needs a value.
@@ -10,6 +10,9 @@ pub struct Options {
     pub(crate) debug_info: bool,
     /// Support (experimental) macros.
     pub(crate) macros: bool,
+    /// Compile integer literals which don't fit in a 64-bit integer as big
+    /// integers.
+    pub(crate) big_integers: bool,
 }
 
 impl Options {
@@ -30,6 +33,9 @@ impl Options {
             Some("macros") => {
                 self.macros = it.next() != Some("false");
             }
+            Some("big-integers") => {
+                self.big_integers = it.next() != Some("false");
+            }
             _ => {
                 return Err(ConfigurationError::UnsupportedOptimizationOption {
                     option: option.to_owned(),
@@ -48,6 +54,7 @@ impl Default for Options {
             memoize_instance_fn: true,
             debug_info: true,
             macros: false,
+            big_integers: false,
         }
     }
 }
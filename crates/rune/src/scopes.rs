@@ -140,6 +140,19 @@ impl Scope {
         Ok(())
     }
 
+    /// Get the named variables declared in this scope, ordered by their
+    /// offset.
+    pub(crate) fn vars(&self) -> Vec<(&str, &Var)> {
+        let mut vars = self
+            .locals
+            .iter()
+            .map(|(name, var)| (name.as_str(), var))
+            .collect::<Vec<_>>();
+
+        vars.sort_by_key(|(_, var)| var.offset);
+        vars
+    }
+
     /// Access the variable with the given name.
     pub(crate) fn get(&self, name: &str) -> Option<&Var> {
         if let Some(var) = self.locals.get(name) {
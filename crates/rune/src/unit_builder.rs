@@ -213,6 +213,8 @@ pub struct UnitBuilder {
     static_object_keys: Vec<Box<[String]>>,
     /// Used to detect duplicates in the collection of static object keys.
     static_object_keys_rev: HashMap<Hash, usize>,
+    /// The number of inline cache slots used by instance calls.
+    instance_call_slots: usize,
     /// The current label count.
     label_count: usize,
     /// A collection of required function hashes.
@@ -438,6 +440,13 @@ impl UnitBuilder {
         Ok(new_slot)
     }
 
+    /// Allocate a new inline cache slot for an instance call site.
+    pub(crate) fn new_instance_call_slot(&mut self) -> usize {
+        let slot = self.instance_call_slots;
+        self.instance_call_slots += 1;
+        slot
+    }
+
     /// Insert a new collection of static object keys, or return one already
     /// existing.
     pub(crate) fn new_static_object_keys(
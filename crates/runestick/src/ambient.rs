@@ -0,0 +1,65 @@
+use crate::{Context, FloatFormat, FloatMode, IterationOrder, Output, RoundingMode};
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    /// The ambient state of the virtual machine currently running on this
+    /// thread.
+    static CURRENT: RefCell<Ambient> = RefCell::new(Ambient::default());
+}
+
+/// The state of a virtual machine which is made available to native
+/// functions while it's running, like through [RoundingMode::current].
+///
+/// It's installed all at once so that running the virtual machine, which
+/// happens once per instruction when stepping through it, only has to touch
+/// the thread local once.
+#[derive(Default)]
+pub(crate) struct Ambient {
+    /// The output to write to, if it's captured.
+    pub(crate) output: Option<Output>,
+    /// The context of the virtual machine.
+    pub(crate) context: Option<Arc<Context>>,
+    /// The iteration order of objects.
+    pub(crate) iteration_order: IterationOrder,
+    /// The rounding mode used when converting floats to integers.
+    pub(crate) rounding_mode: RoundingMode,
+    /// The float mode used for arithmetic.
+    pub(crate) float_mode: FloatMode,
+    /// The format used when converting floats to strings.
+    pub(crate) float_format: FloatFormat,
+}
+
+impl Ambient {
+    /// Access the ambient state of the current thread.
+    pub(crate) fn with<F, O>(f: F) -> O
+    where
+        F: FnOnce(&Self) -> O,
+    {
+        CURRENT.with(|current| f(&current.borrow()))
+    }
+
+    /// Make this the ambient state of the current thread, until the returned
+    /// guard is dropped.
+    pub(crate) fn enter(self) -> AmbientGuard {
+        let previous = CURRENT.with(|current| current.replace(self));
+
+        AmbientGuard {
+            previous: Some(previous),
+        }
+    }
+}
+
+/// Guard restoring the previous ambient state of the current thread when
+/// dropped.
+pub(crate) struct AmbientGuard {
+    previous: Option<Ambient>,
+}
+
+impl Drop for AmbientGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            CURRENT.with(|current| current.replace(previous));
+        }
+    }
+}
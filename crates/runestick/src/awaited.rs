@@ -1,4 +1,7 @@
-use crate::{Future, Select, Shared, ToValue, Vm, VmError};
+use crate::{Future, Select, Shared, ToValue, Vm, VmError, VmErrorKind};
+use futures::future::Either;
+use std::future;
+use std::time::Duration;
 
 /// A stored await task.
 #[derive(Debug)]
@@ -12,14 +15,16 @@ pub enum Awaited {
 impl Awaited {
     /// Wait for the given awaited into the specified virtual machine.
     pub(crate) async fn into_vm(self, vm: &mut Vm) -> Result<(), VmError> {
+        let timeout = vm.await_timeout();
+
         match self {
             Self::Future(future) => {
-                let value = future.borrow_mut()?.await?;
+                let value = with_timeout(timeout, &mut *future.borrow_mut()?).await?;
                 vm.stack_mut().push(value);
                 vm.advance();
             }
             Self::Select(select) => {
-                let (branch, value) = select.await?;
+                let (branch, value) = with_timeout(timeout, select).await?;
                 vm.stack_mut().push(value);
                 vm.stack_mut().push(ToValue::to_value(branch)?);
                 vm.advance();
@@ -29,3 +34,24 @@ impl Awaited {
         Ok(())
     }
 }
+
+/// Await the given future, erroring if it doesn't complete within the given
+/// timeout.
+///
+/// NB: on timeout the future is dropped without being polled again.
+async fn with_timeout<F, T>(timeout: Option<Duration>, future: F) -> Result<T, VmError>
+where
+    F: future::Future<Output = Result<T, VmError>> + Unpin,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return future.await,
+    };
+
+    let delay = futures_timer::Delay::new(timeout);
+
+    match futures::future::select(future, delay).await {
+        Either::Left((result, _)) => result,
+        Either::Right(((), _)) => Err(VmError::from(VmErrorKind::AwaitTimeout { timeout })),
+    }
+}
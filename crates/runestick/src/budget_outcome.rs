@@ -0,0 +1,25 @@
+use crate::Value;
+
+/// The outcome of running an execution with an instruction budget.
+///
+/// See [VmExecution::run_with_budget][crate::VmExecution::run_with_budget].
+#[derive(Debug)]
+pub enum BudgetOutcome {
+    /// The execution completed within its budget.
+    Completed(Value),
+    /// The budget was exhausted before the execution completed. The execution
+    /// can be resumed by running it again.
+    Suspended,
+}
+
+impl BudgetOutcome {
+    /// Test if the execution completed.
+    pub fn is_completed(&self) -> bool {
+        matches!(self, Self::Completed(..))
+    }
+
+    /// Test if the execution was suspended.
+    pub fn is_suspended(&self) -> bool {
+        matches!(self, Self::Suspended)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ambient::Ambient;
 use crate::collections::{HashMap, HashSet};
 use crate::module::{
     ModuleAssociatedFn, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleType, ModuleUnitType,
@@ -236,6 +237,12 @@ impl Context {
         Context::default()
     }
 
+    /// Get the context of the virtual machine currently running on this
+    /// thread, if any.
+    pub(crate) fn current() -> Option<Arc<Context>> {
+        Ambient::with(|ambient| ambient.context.clone())
+    }
+
     /// Use the specified type check.
     pub fn type_check_for(&self, item: &Item) -> Option<TypeCheck> {
         let ty = self.types.get(&Hash::type_hash(item))?;
@@ -255,6 +262,8 @@ impl Context {
         this.install(&crate::modules::iter::module()?)?;
         this.install(&crate::modules::vec::module()?)?;
         this.install(&crate::modules::object::module()?)?;
+        this.install(&crate::modules::map::module()?)?;
+        this.install(&crate::modules::ops::module()?)?;
         this.install(&crate::modules::result::module()?)?;
         this.install(&crate::modules::option::module()?)?;
         this.install(&crate::modules::future::module()?)?;
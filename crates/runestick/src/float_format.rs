@@ -0,0 +1,50 @@
+use crate::ambient::Ambient;
+use std::fmt::Write as _;
+
+/// The policy used by the virtual machine when converting floats to strings.
+///
+/// This affects all conversions performed by the virtual machine itself, like
+/// when a float is used in a template string, and how floats are formatted by
+/// the `Debug` implementation of [Value][crate::Value] while the virtual
+/// machine is running, like when they're printed through `dbg`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Format floats using the shortest representation which round-trips
+    /// back to the same float. So `1.0` is formatted as `1.0`, and `1.0 / 3.0`
+    /// as `0.3333333333333333`.
+    ///
+    /// This is the default policy.
+    Shortest,
+    /// Format floats with a fixed number of decimal places. So with two
+    /// decimal places `1.0` is formatted as `1.00`, and `1.0 / 3.0` as `0.33`.
+    Fixed(usize),
+}
+
+impl FloatFormat {
+    /// Format the given float according to the policy, appending it to the
+    /// given buffer.
+    pub fn format_into(self, float: f64, buf: &mut String) {
+        match self {
+            Self::Shortest => {
+                let mut buffer = ryu::Buffer::new();
+                buf.push_str(buffer.format(float));
+            }
+            Self::Fixed(places) => {
+                // NB: writing to a string cannot fail.
+                let _ = write!(buf, "{:.*}", places, float);
+            }
+        }
+    }
+
+    /// Get the float format of the virtual machine currently running on this
+    /// thread.
+    pub(crate) fn current() -> Self {
+        Ambient::with(|ambient| ambient.float_format)
+    }
+}
+
+impl Default for FloatFormat {
+    fn default() -> Self {
+        Self::Shortest
+    }
+}
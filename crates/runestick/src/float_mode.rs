@@ -0,0 +1,64 @@
+use crate::ambient::Ambient;
+use crate::{VmError, VmErrorKind};
+
+/// The mode used by the virtual machine for float arithmetic.
+///
+/// Basic float arithmetic is specified by IEEE 754 to be correctly rounded,
+/// so adding, subtracting, multiplying, dividing, and taking the remainder of
+/// floats produces the same results on every platform. What isn't specified
+/// is the bit pattern of any NaN produced, and functions like `sin` or `exp`
+/// which are implemented differently by different platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatMode {
+    /// Use the float arithmetic of the platform as-is.
+    ///
+    /// This is the default mode.
+    Native,
+    /// Make float arithmetic reproducible across platforms, for applications
+    /// like lockstep simulations which need bit-identical results.
+    ///
+    /// Any NaN produced by float arithmetic is replaced with a single
+    /// canonical NaN, and operations which aren't reproducible error with
+    /// [NonDeterministicFloatOperation][VmErrorKind::NonDeterministicFloatOperation].
+    /// See [check_reproducible][Self::check_reproducible].
+    Deterministic,
+}
+
+impl FloatMode {
+    /// Get the float mode of the virtual machine currently running on this
+    /// thread.
+    pub fn current() -> Self {
+        Ambient::with(|ambient| ambient.float_mode)
+    }
+
+    /// Check that an operation with the given name, whose results differ
+    /// between platforms, is permitted by the float mode of the virtual
+    /// machine currently running on this thread.
+    ///
+    /// Native functions implementing such operations, like trigonometric
+    /// functions, should call this before doing any work.
+    pub fn check_reproducible(op: &'static str) -> Result<(), VmError> {
+        match Self::current() {
+            Self::Native => Ok(()),
+            Self::Deterministic => {
+                Err(VmError::from(VmErrorKind::NonDeterministicFloatOperation {
+                    op,
+                }))
+            }
+        }
+    }
+
+    /// Canonicalize the result of a float operation according to the mode.
+    pub(crate) fn canonicalize(self, value: f64) -> f64 {
+        match self {
+            Self::Deterministic if value.is_nan() => f64::NAN,
+            _ => value,
+        }
+    }
+}
+
+impl Default for FloatMode {
+    fn default() -> Self {
+        Self::Native
+    }
+}
@@ -14,6 +14,28 @@ pub struct Function {
 
 impl Function {
     /// Perform a call over the function represented by this function pointer.
+    ///
+    /// Script functions carry the context and unit they belong to, and are
+    /// called in a virtual machine of their own. This makes it safe to call
+    /// back into a script from a native function, since the stack of the
+    /// calling virtual machine is never touched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Function, Value, VmError};
+    ///
+    /// /// A native function which calls the given function for each value.
+    /// fn map(values: Vec<Value>, f: Function) -> Result<Vec<Value>, VmError> {
+    ///     values.into_iter().map(|value| f.call((value,))).collect()
+    /// }
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = runestick::Module::default();
+    /// module.function(&["map"], map)?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn call<A, T>(&self, args: A) -> Result<T, VmError>
     where
         A: Args,
@@ -57,7 +79,7 @@ impl Function {
             }
             Inner::FnOffset(fn_offset) => {
                 if let Some(vm_call) = fn_offset.call_with_vm(vm, args, ())? {
-                    return Ok(Some(VmHalt::VmCall(vm_call)));
+                    return Ok(Some(VmHalt::VmCall(Box::new(vm_call))));
                 }
 
                 None
@@ -68,7 +90,7 @@ impl Function {
                         .fn_offset
                         .call_with_vm(vm, args, (closure.environment.clone(),))?
                 {
-                    return Ok(Some(VmHalt::VmCall(vm_call)));
+                    return Ok(Some(VmHalt::VmCall(Box::new(vm_call))));
                 }
 
                 None
@@ -297,9 +319,9 @@ impl FnOffset {
 
         let mut new_stack = vm.stack_mut().drain_stack_top(args)?.collect::<Stack>();
         extra.into_stack(&mut new_stack)?;
-        let mut vm = Vm::new_with_stack(self.context.clone(), self.unit.clone(), new_stack);
-        vm.set_ip(self.offset);
-        Ok(Some(VmCall::new(self.call, vm)))
+        let mut new_vm = vm.new_child_with(self.context.clone(), self.unit.clone(), new_stack);
+        new_vm.set_ip(self.offset);
+        Ok(Some(VmCall::new(self.call, new_vm)))
     }
 }
 
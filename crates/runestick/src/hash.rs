@@ -2,7 +2,7 @@ use crate::{Component, Type};
 use std::any;
 use std::fmt;
 use std::hash;
-use std::hash::{BuildHasher as _, BuildHasherDefault, Hash as _, Hasher as _};
+use std::hash::{Hash as _, Hasher as _};
 use std::mem;
 use twox_hash::XxHash64;
 
@@ -13,6 +13,29 @@ const GETTER: usize = 3;
 const OBJECT_KEYS: usize = 4;
 
 /// The hash of a primitive thing.
+///
+/// Hashes key every function and type lookup, and are stored in compiled units.
+/// They must therefore be stable across builds and platforms. The hashing
+/// algorithm is [XxHash64] with a seed of `0`, where every integer is fed to
+/// the hasher as little-endian bytes. Integers which are `usize` or `isize` are
+/// widened to 64 bits. Everything else is fed as follows:
+///
+/// * A string is fed as its UTF-8 bytes followed by a single `0xff` byte.
+/// * A [type hash][Hash::type_hash] or a [function hash][Hash::function] feeds
+///   the integer `1` followed by each component of the path. A string
+///   component is fed as the integer `0` followed by the string. A block,
+///   closure, or async block component is fed as the integer `1`, `2`, or `3`
+///   respectively, followed by its index.
+/// * An [instance function][Hash::instance_function] feeds the integer `2`,
+///   the hash of the type, the separator `0x7f`, and the hash of the name.
+/// * A [getter][Hash::getter] is the same as an instance function, but starts
+///   with the integer `3`.
+/// * [Object keys][Hash::object_keys] feeds the integer `4`, followed by the
+///   separator `0x7f` and the string for each key.
+/// * The name of an instance function is hashed with [Hash::of], feeding the
+///   name as a string.
+///
+/// [XxHash64]: https://github.com/Cyan4973/xxHash
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
 pub struct Hash(u64);
@@ -23,6 +46,11 @@ impl Hash {
         Self(hash)
     }
 
+    /// Get the raw value of the hash.
+    pub(crate) const fn into_inner(self) -> u64 {
+        self.0
+    }
+
     /// Construct a hash from the given type id.
     pub fn from_any<T>() -> Self
     where
@@ -89,9 +117,27 @@ impl Hash {
         path.into_hash()
     }
 
+    /// Get the hash of a function at the given path.
+    ///
+    /// This is the same as the [type hash][Hash::type_hash] of the path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::Hash;
+    ///
+    /// assert_eq!(Hash::function(&["main"]), Hash::type_hash(&["main"]));
+    /// ```
+    pub fn function<I>(path: I) -> Self
+    where
+        I: IntoHash,
+    {
+        path.into_hash()
+    }
+
     /// Construct a new hasher.
-    fn new_hasher() -> impl hash::Hasher {
-        BuildHasherDefault::<XxHash64>::default().build_hasher()
+    fn new_hasher() -> Hasher {
+        Hasher(XxHash64::with_seed(0))
     }
 
     /// Construct a hash for an use.
@@ -104,13 +150,71 @@ impl Hash {
         kind.hash(&mut hasher);
 
         for part in path {
-            part.into().hash(&mut hasher);
+            match part.into() {
+                Component::String(string) => {
+                    0usize.hash(&mut hasher);
+                    string.hash(&mut hasher);
+                }
+                Component::Block(index) => {
+                    1usize.hash(&mut hasher);
+                    index.hash(&mut hasher);
+                }
+                Component::Closure(index) => {
+                    2usize.hash(&mut hasher);
+                    index.hash(&mut hasher);
+                }
+                Component::AsyncBlock(index) => {
+                    3usize.hash(&mut hasher);
+                    index.hash(&mut hasher);
+                }
+            }
         }
 
         Self(hasher.finish())
     }
 }
 
+/// The hasher used to construct a [Hash].
+///
+/// This feeds every integer as little-endian bytes, and widens `usize` and
+/// `isize` to 64 bits, so that hashes are the same on every platform.
+struct Hasher(XxHash64);
+
+macro_rules! write_le {
+    ($($method:ident, $ty:ty, $as:ty;)*) => {
+        $(
+            fn $method(&mut self, n: $ty) {
+                self.0.write(&(n as $as).to_le_bytes());
+            }
+        )*
+    };
+}
+
+impl hash::Hasher for Hasher {
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    write_le! {
+        write_u8, u8, u8;
+        write_u16, u16, u16;
+        write_u32, u32, u32;
+        write_u64, u64, u64;
+        write_u128, u128, u128;
+        write_usize, usize, u64;
+        write_i8, i8, i8;
+        write_i16, i16, i16;
+        write_i32, i32, i32;
+        write_i64, i64, i64;
+        write_i128, i128, i128;
+        write_isize, isize, i64;
+    }
+}
+
 impl fmt::Display for Hash {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(fmt, "0x{:x}", self.0)
@@ -144,3 +248,48 @@ where
         Hash::path_hash(TYPE, self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Hash;
+    use crate::{Component, Type, VEC_TYPE};
+
+    #[test]
+    fn test_pinned_hashes() {
+        assert_eq!(Hash::function(&["main"]), Hash::new(0xe7fc1d6083100dcd));
+        assert_eq!(Hash::type_hash(&["main"]), Hash::new(0xe7fc1d6083100dcd));
+
+        assert_eq! {
+            Hash::type_hash(&["std", "vec", "Vec", "new"]),
+            Hash::new(0xb83c19582c506b24),
+        };
+
+        assert_eq! {
+            Hash::type_hash(vec![
+                Component::from("main"),
+                Component::Block(1),
+                Component::Closure(2),
+                Component::AsyncBlock(3),
+            ]),
+            Hash::new(0x89f885053d099a04),
+        };
+
+        assert_eq!(Hash::of("new"), Hash::new(0xe43c39fd65e425f6));
+        assert_eq!(Hash::of(&b"abc"[..]), Hash::new(0xf0545d02a5246fe5));
+
+        assert_eq! {
+            Hash::instance_function(Type::StaticType(VEC_TYPE), Hash::of("len")),
+            Hash::new(0xe5ebb2241869e1db),
+        };
+
+        assert_eq! {
+            Hash::getter(Type::StaticType(VEC_TYPE), Hash::of("len")),
+            Hash::new(0x0c926c566909093d),
+        };
+
+        assert_eq!(
+            Hash::object_keys(&["a", "b"]),
+            Hash::new(0xc6c495b88a786950)
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Bytes, Shared, Tuple, Value, VmError, VmErrorKind};
+use std::fmt;
+
+/// An immutable key constructed from a [Value], used to key a
+/// [Map][crate::Map].
+///
+/// Only values which can be hashed deterministically can be used as keys.
+/// These are units, booleans, bytes, characters, integers, strings, byte
+/// strings, and options, vectors, and tuples of them.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{HashKey, Value};
+///
+/// # fn main() -> runestick::Result<()> {
+/// let key = HashKey::from_value(&Value::from(42i64))?;
+/// assert_eq!(key, HashKey::Integer(42));
+/// assert!(HashKey::from_value(&Value::from(4.2f64)).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashKey {
+    /// The unit value.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// A single byte.
+    Byte(u8),
+    /// A character.
+    Char(char),
+    /// An integer.
+    Integer(i64),
+    /// A UTF-8 string.
+    String(String),
+    /// A byte string.
+    Bytes(Vec<u8>),
+    /// A vector of keys.
+    Vec(Vec<HashKey>),
+    /// A tuple of keys.
+    Tuple(Vec<HashKey>),
+    /// An optional key.
+    Option(Option<Box<HashKey>>),
+}
+
+impl HashKey {
+    /// Construct a key from the given value.
+    ///
+    /// This errors with [UnsupportedHashKey][VmErrorKind::UnsupportedHashKey]
+    /// if the value, or any value it contains, can't be hashed.
+    pub fn from_value(value: &Value) -> Result<Self, VmError> {
+        Ok(match value {
+            Value::Unit => Self::Unit,
+            Value::Bool(b) => Self::Bool(*b),
+            Value::Byte(b) => Self::Byte(*b),
+            Value::Char(c) => Self::Char(*c),
+            Value::Integer(integer) => Self::Integer(*integer),
+            Value::StaticString(string) => Self::String(string.as_str().to_owned()),
+            Value::String(string) => Self::String(string.borrow_ref()?.clone()),
+            Value::Bytes(bytes) => Self::Bytes(bytes.borrow_ref()?.bytes.clone()),
+            Value::Vec(vec) => Self::Vec(Self::from_values(&vec.borrow_ref()?)?),
+            Value::Tuple(tuple) => Self::Tuple(Self::from_values(&tuple.borrow_ref()?)?),
+            Value::Option(option) => Self::Option(match &*option.borrow_ref()? {
+                Some(value) => Some(Box::new(Self::from_value(value)?)),
+                None => None,
+            }),
+            actual => {
+                return Err(VmError::from(VmErrorKind::UnsupportedHashKey {
+                    actual: actual.type_info()?,
+                }));
+            }
+        })
+    }
+
+    fn from_values(values: &[Value]) -> Result<Vec<Self>, VmError> {
+        values.iter().map(Self::from_value).collect()
+    }
+
+    /// Convert the key back into a value.
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Unit => Value::Unit,
+            Self::Bool(b) => Value::Bool(b),
+            Self::Byte(b) => Value::Byte(b),
+            Self::Char(c) => Value::Char(c),
+            Self::Integer(integer) => Value::Integer(integer),
+            Self::String(string) => Value::from(string),
+            Self::Bytes(bytes) => Value::from(Bytes::from_vec(bytes)),
+            Self::Vec(keys) => Value::vec(Self::into_values(keys)),
+            Self::Tuple(keys) => Value::from(Tuple::from(Self::into_values(keys))),
+            Self::Option(key) => Value::from(Shared::new(key.map(|key| key.into_value()))),
+        }
+    }
+
+    fn into_values(keys: Vec<Self>) -> Vec<Value> {
+        keys.into_iter().map(Self::into_value).collect()
+    }
+
+    fn fmt_keys(f: &mut fmt::Formatter<'_>, keys: &[Self]) -> fmt::Result {
+        let mut it = keys.iter().peekable();
+
+        while let Some(key) = it.next() {
+            write!(f, "{}", key)?;
+
+            if it.peek().is_some() {
+                write!(f, ", ")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unit => write!(f, "()"),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Byte(b) => write!(f, "{:?}", b),
+            Self::Char(c) => write!(f, "{:?}", c),
+            Self::Integer(integer) => write!(f, "{}", integer),
+            Self::String(string) => write!(f, "{:?}", string),
+            Self::Bytes(bytes) => write!(f, "{:?}", bytes),
+            Self::Vec(keys) => {
+                write!(f, "[")?;
+                Self::fmt_keys(f, keys)?;
+                write!(f, "]")
+            }
+            Self::Tuple(keys) => {
+                write!(f, "(")?;
+                Self::fmt_keys(f, keys)?;
+                write!(f, ")")
+            }
+            Self::Option(Some(key)) => write!(f, "Some({})", key),
+            Self::Option(None) => write!(f, "None"),
+        }
+    }
+}
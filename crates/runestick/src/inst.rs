@@ -81,16 +81,27 @@ impl fmt::Display for TypeCheck {
 /// An operation in the stack-based virtual machine.
 #[derive(Debug, Clone, Copy)]
 pub enum Inst {
-    /// Not operator. Takes a boolean from the top of the stack  and inverts its
-    /// logical value.
+    /// Not operator. Takes a boolean from the top of the stack and inverts its
+    /// logical value, or takes an integer and produces its bitwise complement.
     ///
     /// # Operation
     ///
     /// ```text
-    /// <bool>
-    /// => <bool>
+    /// <bool | int>
+    /// => <bool | int>
     /// ```
     Not,
+    /// Negate the numerical value on the top of the stack.
+    ///
+    /// This is the result of a `-<a>` expression.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <number>
+    /// => <number>
+    /// ```
+    Neg,
     /// Add two things together.
     ///
     /// This is the result of an `<a> + <b>` expression.
@@ -200,6 +211,9 @@ pub enum Inst {
         hash: Hash,
         /// The number of arguments expected on the stack for this call.
         args: usize,
+        /// The slot of the inline cache used by this call site, see
+        /// [Unit::instance_call_slots][crate::Unit::instance_call_slots].
+        slot: usize,
     },
     /// Lookup the specified instance function and put it on the stack.
     /// This might help in cases where a single instance function is called many
@@ -282,6 +296,41 @@ pub enum Inst {
         /// The index to fetch.
         index: usize,
     },
+    /// Get the given index counted from the back of a tuple from the given
+    /// variable slot, where `0` is the last element. Errors if the item
+    /// doesn't exist or the item is not a tuple.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <value>
+    /// ```
+    TupleIndexGetBackAt {
+        /// The slot offset to load the tuple from.
+        offset: usize,
+        /// The index to fetch, counted from the back.
+        index: usize,
+    },
+    /// Collect the elements of a tuple from the given variable slot into a new
+    /// value, skipping `start` elements at the front and `end` elements at the
+    /// back. Vectors produce a vector, and other tuple-like values produce a
+    /// tuple.
+    ///
+    /// This is used to bind rest patterns like `[a, ..rest]`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <vec|tuple>
+    /// ```
+    TupleSliceAt {
+        /// The slot offset to load the tuple from.
+        offset: usize,
+        /// The number of elements to skip at the front.
+        start: usize,
+        /// The number of elements to skip at the back.
+        end: usize,
+    },
     /// Get the given index out of an object on the top of the stack.
     /// Errors if the item doesn't exist or the item is not an object.
     ///
@@ -336,6 +385,20 @@ pub enum Inst {
         /// The number to push.
         number: f64,
     },
+    /// Push a literal big integer, which is too large to fit in an
+    /// [Integer][Inst::Integer].
+    ///
+    /// The big integer is stored in decimal in the given static string slot.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => <big-int>
+    /// ```
+    BigInt {
+        /// The static string slot of the big integer.
+        slot: usize,
+    },
     /// Await the future that is on the stack and push the value that it
     /// produces.
     ///
@@ -363,6 +426,23 @@ pub enum Inst {
         /// The number of futures to poll.
         len: usize,
     },
+    /// Select over `len` futures on the stack, like [Select][Inst::Select],
+    /// but without blocking.
+    ///
+    /// The futures are polled once. If none of them is ready, a unit is pushed
+    /// on the stack instead of a value and a branch, indicating that the
+    /// default branch should be taken.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <future...>
+    /// => <value> <branch> | <unit>
+    /// ```
+    SelectDefault {
+        /// The number of futures to poll.
+        len: usize,
+    },
     /// Pop the value on the stack, discarding its result.
     ///
     /// # Operation
@@ -422,7 +502,16 @@ pub enum Inst {
         offset: usize,
     },
     /// Drop the value in the given frame offset, cleaning out it's slot in
-    /// memory.
+    /// memory by replacing it with a unit.
+    ///
+    /// If the value is an external type which implements the
+    /// [DROP][crate::DROP] protocol, and the slot held the last reference to
+    /// it, the protocol is called with the dropped value. Values which are
+    /// still referenced elsewhere are left alone.
+    ///
+    /// The compiler only emits this for slots it owns, like the iterator of a
+    /// `for` loop. Locals going out of scope are popped without calling the
+    /// protocol.
     ///
     /// # Operation
     ///
@@ -447,6 +536,23 @@ pub enum Inst {
         /// Offset to swap value from.
         offset: usize,
     },
+    /// Copy the value at the frame offset `from` into the frame offset `to`,
+    /// replacing the value that was there.
+    ///
+    /// This is the fused form of a [Copy][Inst::Copy] directly followed by a
+    /// [Replace][Inst::Replace], as produced for assignments like `a = b`.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// => *noop*
+    /// ```
+    Move {
+        /// Frame offset to copy the value from.
+        from: usize,
+        /// Frame offset to store the value in.
+        to: usize,
+    },
     /// Pop the current stack frame and restore the instruction pointer from it.
     ///
     /// The stack frame will be cleared, and the value on the top of the stack
@@ -590,6 +696,25 @@ pub enum Inst {
         /// The size of the tuple.
         count: usize,
     },
+    /// Construct a push a range onto the stack. The start of the range is only
+    /// popped if `from` is set, and the end of the range is only popped if `to`
+    /// is set. Both bounds must be integers.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <to?>
+    /// <from?>
+    /// => <range>
+    /// ```
+    Range {
+        /// If the range has a start.
+        from: bool,
+        /// If the range has an end.
+        to: bool,
+        /// If the end of the range is inclusive.
+        inclusive: bool,
+    },
     /// Take the tuple that is on top of the stack and push its content onto the
     /// stack.
     ///
@@ -718,6 +843,30 @@ pub enum Inst {
         /// The minimum string size used.
         size_hint: usize,
     },
+    /// Pop the given number of values from the stack, and append their
+    /// display representations to the string in the given frame offset, in
+    /// place.
+    ///
+    /// This is a dedicated optimization for appending a template string to a
+    /// string with `+=`, which doesn't allocate a temporary string like
+    /// [StringConcat][Self::StringConcat] followed by
+    /// [AddAssign][Self::AddAssign] does. If the offset doesn't hold a string,
+    /// it behaves exactly like them.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value...>
+    /// => *noop*
+    /// ```
+    StringConcatAssign {
+        /// The frame offset of the string to append to.
+        offset: usize,
+        /// The number of items to pop from the stack.
+        len: usize,
+        /// The minimum number of bytes to reserve in the string.
+        size_hint: usize,
+    },
     /// Test if the top of the stack is an instance of the second item on the
     /// stack.
     ///
@@ -948,6 +1097,22 @@ pub enum Inst {
         /// The integer to test against.
         integer: i64,
     },
+    /// Test if the top of the stack is a specific float.
+    ///
+    /// This uses exact floating point equality, so the usual precision
+    /// caveats apply. Note that `NaN` is never equal to anything, so it can
+    /// never be matched.
+    ///
+    /// # Operation
+    ///
+    /// ```text
+    /// <value>
+    /// => <boolean>
+    /// ```
+    EqFloat {
+        /// The float to test against.
+        float: f64,
+    },
     /// Compare the top of the stack against a static string slot.
     ///
     /// # Operation
@@ -1048,6 +1213,9 @@ impl fmt::Display for Inst {
             Self::Not => {
                 write!(fmt, "not")?;
             }
+            Self::Neg => {
+                write!(fmt, "neg")?;
+            }
             Self::Add => {
                 write!(fmt, "add")?;
             }
@@ -1081,8 +1249,8 @@ impl fmt::Display for Inst {
             Self::Call { hash, args } => {
                 write!(fmt, "call {}, {}", hash, args)?;
             }
-            Self::CallInstance { hash, args } => {
-                write!(fmt, "call-instance {}, {}", hash, args)?;
+            Self::CallInstance { hash, args, slot } => {
+                write!(fmt, "call-instance {}, {}, {}", hash, args, slot)?;
             }
             Self::Fn { hash } => {
                 write!(fmt, "fn {}", hash)?;
@@ -1108,6 +1276,12 @@ impl fmt::Display for Inst {
             Self::TupleIndexGetAt { offset, index } => {
                 write!(fmt, "tuple-index-get-at {}, {}", offset, index)?;
             }
+            Self::TupleIndexGetBackAt { offset, index } => {
+                write!(fmt, "tuple-index-get-back-at {}, {}", offset, index)?;
+            }
+            Self::TupleSliceAt { offset, start, end } => {
+                write!(fmt, "tuple-slice-at {}, {}, {}", offset, start, end)?;
+            }
             Self::ObjectSlotIndexGet { slot } => {
                 write!(fmt, "object-slot-index-get {}", slot)?;
             }
@@ -1123,12 +1297,18 @@ impl fmt::Display for Inst {
             Self::Float { number } => {
                 write!(fmt, "float {}", number)?;
             }
+            Self::BigInt { slot } => {
+                write!(fmt, "big-int {}", slot)?;
+            }
             Self::Await => {
                 write!(fmt, "await")?;
             }
             Self::Select { len } => {
                 write!(fmt, "select {}", len)?;
             }
+            Self::SelectDefault { len } => {
+                write!(fmt, "select-default {}", len)?;
+            }
             Self::Pop => {
                 write!(fmt, "pop")?;
             }
@@ -1150,6 +1330,9 @@ impl fmt::Display for Inst {
             Self::Replace { offset } => {
                 write!(fmt, "replace {}", offset)?;
             }
+            Self::Move { from, to } => {
+                write!(fmt, "move {}, {}", from, to)?;
+            }
             Self::Return => {
                 write!(fmt, "return")?;
             }
@@ -1198,6 +1381,13 @@ impl fmt::Display for Inst {
             Self::Tuple { count } => {
                 write!(fmt, "tuple {}", count)?;
             }
+            Self::Range {
+                from,
+                to,
+                inclusive,
+            } => {
+                write!(fmt, "range {}, {}, {}", from, to, inclusive)?;
+            }
             Self::PushTuple => {
                 write!(fmt, "push-tuple")?;
             }
@@ -1223,6 +1413,17 @@ impl fmt::Display for Inst {
             Self::StringConcat { len, size_hint } => {
                 write!(fmt, "string-concat {}, {}", len, size_hint)?;
             }
+            Self::StringConcatAssign {
+                offset,
+                len,
+                size_hint,
+            } => {
+                write!(
+                    fmt,
+                    "string-concat-assign {}, {}, {}",
+                    offset, len, size_hint
+                )?;
+            }
             Self::Char { c } => {
                 write!(fmt, "char {:?}", c)?;
             }
@@ -1289,6 +1490,9 @@ impl fmt::Display for Inst {
             Self::EqInteger { integer } => {
                 write!(fmt, "eq-integer {}", integer)?;
             }
+            Self::EqFloat { float } => {
+                write!(fmt, "eq-float {}", float)?;
+            }
             Self::EqStaticString { slot } => {
                 write!(fmt, "eq-static-string {}", slot)?;
             }
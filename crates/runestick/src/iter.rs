@@ -0,0 +1,141 @@
+use crate::{Function, Shared, ToValue, Value, VmError};
+use std::fmt;
+use std::iter;
+
+/// The boxed iterator being wrapped.
+type DynIter = dyn iter::Iterator<Item = Result<Value, VmError>>;
+
+/// A lazy iterator over values, which can be constructed from any Rust
+/// iterator.
+///
+/// This allows the host to hand a lazy data source to a script, which is
+/// consumed as the script iterates over it, like with a `for` loop.
+///
+/// The wrapped iterator must be `'static`, since the script might hold on to
+/// it for an arbitrary amount of time.
+///
+/// # Examples
+///
+/// ```rust
+/// let iter = runestick::Iter::new(0..10i64);
+/// ```
+pub struct Iter {
+    iter: Box<DynIter>,
+}
+
+impl Iter {
+    /// Construct a new iterator out of a Rust iterator.
+    ///
+    /// Each item is converted into a value as it is being produced.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: 'static + IntoIterator,
+        I::Item: ToValue,
+    {
+        Self {
+            iter: Box::new(iter.into_iter().map(ToValue::to_value)),
+        }
+    }
+
+    /// Get the next value out of the iterator.
+    ///
+    /// This errors if producing the value errors, like when calling the
+    /// function passed to [map][Self::map] fails.
+    pub fn try_next(&mut self) -> Result<Option<Value>, VmError> {
+        self.iter.next().transpose()
+    }
+
+    /// Lazily map each value produced by the iterator through the given
+    /// function.
+    ///
+    /// Adapters like this one are fused into the iterator they wrap, so a
+    /// chain like `map(f).filter(g).collect()` makes a single pass over the
+    /// values without building any intermediate collections.
+    pub fn map(self, function: Shared<Function>) -> Self {
+        Self {
+            iter: Box::new(
+                self.iter
+                    .map(move |value| function.borrow_ref()?.call::<_, Value>((value?,))),
+            ),
+        }
+    }
+
+    /// Lazily filter the values produced by the iterator by the given
+    /// predicate.
+    pub fn filter(self, predicate: Shared<Function>) -> Self {
+        Self {
+            iter: Box::new(
+                self.iter
+                    .filter_map(move |value| filter_value(&predicate, value).transpose()),
+            ),
+        }
+    }
+
+    /// Lazily pair up the values produced by the iterator with their indexes,
+    /// as `(index, value)` tuples where the index is an integer starting at
+    /// zero.
+    pub fn enumerate(self) -> Self {
+        Self {
+            iter: Box::new(
+                self.iter.enumerate().map(|(index, value)| {
+                    Ok(Value::tuple(vec![(index as i64).to_value()?, value?]))
+                }),
+            ),
+        }
+    }
+
+    /// Lazily pair up the values produced by the iterator with the values
+    /// produced by another iterator, as two-element tuples.
+    ///
+    /// This stops as soon as either of the iterators runs out of values.
+    pub fn zip(self, other: Self) -> Self {
+        Self {
+            iter: Box::new(
+                self.iter
+                    .zip(other.iter)
+                    .map(|(a, b)| Ok(Value::tuple(vec![a?, b?]))),
+            ),
+        }
+    }
+
+    /// Consume the iterator, folding the remaining values from the left into
+    /// an accumulator by calling the given function with the accumulator and
+    /// each value.
+    pub fn fold(mut self, init: Value, function: Shared<Function>) -> Result<Value, VmError> {
+        let function = function.borrow_ref()?;
+        let mut acc = init;
+
+        while let Some(value) = self.try_next()? {
+            acc = function.call::<_, Value>((acc, value))?;
+        }
+
+        Ok(acc)
+    }
+
+    /// Consume the iterator, collecting the remaining values into a vector.
+    pub fn collect(self) -> Result<Vec<Value>, VmError> {
+        self.iter.collect()
+    }
+}
+
+/// Test a single value produced by an iterator against a predicate.
+fn filter_value(
+    predicate: &Shared<Function>,
+    value: Result<Value, VmError>,
+) -> Result<Option<Value>, VmError> {
+    let value = value?;
+
+    if predicate.borrow_ref()?.call::<_, bool>((value.clone(),))? {
+        Ok(Some(value))
+    } else {
+        Ok(None)
+    }
+}
+
+impl fmt::Debug for Iter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Iter")
+    }
+}
+
+impl_external!(Iter);
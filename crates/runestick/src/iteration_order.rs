@@ -0,0 +1,26 @@
+use crate::ambient::Ambient;
+
+/// The order in which the entries of objects are visited, like when iterating
+/// over them or when they are debug printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationOrder {
+    /// Entries are visited in whichever order is most efficient. This order
+    /// is not stable across runs.
+    Unspecified,
+    /// Entries are visited in the order of their sorted keys.
+    Sorted,
+}
+
+impl IterationOrder {
+    /// Get the iteration order of the virtual machine currently running on
+    /// this thread.
+    pub(crate) fn current() -> Self {
+        Ambient::with(|ambient| ambient.iteration_order)
+    }
+}
+
+impl Default for IterationOrder {
+    fn default() -> Self {
+        Self::Unspecified
+    }
+}
@@ -44,26 +44,37 @@ mod vm;
 #[macro_use]
 mod macros;
 mod access;
+mod ambient;
 mod args;
 mod awaited;
+mod budget_outcome;
 mod bytes;
 mod call;
 mod compile_meta;
 pub mod debug;
+mod float_format;
+mod float_mode;
 mod function;
 mod future;
 mod generator;
 mod generator_state;
 mod hash;
+mod hash_key;
 mod inst;
 mod item;
+mod iter;
+mod iteration_order;
 mod label;
+mod loop_detector;
 pub mod module;
 pub mod modules;
 mod names;
+mod output;
 mod panic;
 mod protocol;
+mod range;
 mod reflection;
+mod rounding_mode;
 mod select;
 mod serde;
 mod shared;
@@ -81,10 +92,16 @@ mod vec_tuple;
 mod vm_call;
 mod vm_error;
 mod vm_execution;
+mod vm_execution_state;
 mod vm_halt;
+mod vm_recording;
+mod vm_snapshot;
+mod vm_state;
 
 impl_external!(anyhow::Error);
 
+pub use num_bigint::BigInt;
+
 /// Exported result type for convenience.
 pub type Result<T, E = anyhow::Error> = std::result::Result<T, E>;
 
@@ -104,9 +121,10 @@ pub use self::source::Source;
 pub use self::span::Span;
 pub use self::static_string::StaticString;
 pub use self::static_type::{
-    StaticType, BOOL_TYPE, BYTES_TYPE, BYTE_TYPE, CHAR_TYPE, FLOAT_TYPE, FUNCTION_TYPE,
-    FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, INTEGER_TYPE, OBJECT_TYPE, OPTION_TYPE,
-    RESULT_TYPE, STREAM_TYPE, STRING_TYPE, TUPLE_TYPE, UNIT_TYPE, VEC_TYPE,
+    StaticType, ANY_TYPE, BIG_INT_TYPE, BOOL_TYPE, BYTES_TYPE, BYTE_TYPE, CHAR_TYPE, FLOAT_TYPE,
+    FUNCTION_TYPE, FUTURE_TYPE, GENERATOR_STATE_TYPE, GENERATOR_TYPE, INTEGER_TYPE, MAP_TYPE,
+    OBJECT_TYPE, OPTION_TYPE, RANGE_TYPE, RESULT_TYPE, STREAM_TYPE, STRING_TYPE, TUPLE_TYPE,
+    UNIT_TYPE, VEC_TYPE,
 };
 pub use self::stream::Stream;
 pub use self::tuple::Tuple;
@@ -118,35 +136,49 @@ pub use crate::access::{
 };
 pub use crate::any::{Any, AnyVtable};
 pub use crate::awaited::Awaited;
+pub use crate::budget_outcome::BudgetOutcome;
 pub use crate::bytes::Bytes;
 pub use crate::call::Call;
 pub use crate::context::{Context, ContextError};
-pub use crate::debug::{DebugInfo, DebugInst};
+pub use crate::debug::{DebugInfo, DebugInst, DebugSignature};
+pub use crate::float_format::FloatFormat;
+pub use crate::float_mode::FloatMode;
 pub use crate::function::Function;
 pub use crate::future::Future;
 pub use crate::hash::{Hash, IntoHash};
+pub use crate::hash_key::HashKey;
 pub use crate::inst::{Inst, PanicReason, TypeCheck};
 pub use crate::item::{Component, Item};
+pub use crate::iter::Iter;
+pub use crate::iteration_order::IterationOrder;
+pub use crate::loop_detector::LoopDetector;
 pub use crate::names::Names;
+pub use crate::output::Output;
 pub use crate::panic::Panic;
 pub use crate::protocol::{
     Protocol, ADD, ADD_ASSIGN, BIT_AND, BIT_AND_ASSIGN, BIT_OR, BIT_OR_ASSIGN, BIT_XOR,
-    BIT_XOR_ASSIGN, DIV, DIV_ASSIGN, INDEX_GET, INDEX_SET, INTO_FUTURE, INTO_ITER, MUL, MUL_ASSIGN,
-    NEXT, REM, REM_ASSIGN, SHL, SHL_ASSIGN, SHR, SHR_ASSIGN, STRING_DISPLAY, SUB, SUB_ASSIGN,
+    BIT_XOR_ASSIGN, CMP, DIV, DIV_ASSIGN, DROP, EQ, GT, GTE, INDEX_GET, INDEX_SET, INTO_FUTURE,
+    INTO_ITER, LT, LTE, MUL, MUL_ASSIGN, NEXT, REM, REM_ASSIGN, SHL, SHL_ASSIGN, SHR, SHR_ASSIGN,
+    STRING_DISPLAY, SUB, SUB_ASSIGN,
 };
+pub use crate::range::Range;
 pub use crate::reflection::{FromValue, ToValue, UnsafeFromValue, ValueType};
+pub use crate::rounding_mode::RoundingMode;
 pub use crate::shared::{OwnedMut, OwnedRef, RawOwnedMut, RawOwnedRef, Shared};
 pub use crate::stack::{Stack, StackError};
-pub use crate::unit::{Unit, UnitFn, UnitTypeInfo};
+pub use crate::unit::{LinkError, Unit, UnitFn, UnitTypeInfo};
 pub use crate::value::{
-    Integer, Object, TupleVariant, TypedObject, TypedTuple, Value, VariantObject,
+    Integer, Map, Object, TupleVariant, TypedObject, TypedTuple, Value, VariantObject,
 };
 pub use crate::vec_tuple::VecTuple;
-pub use crate::vm::{CallFrame, Vm};
+pub use crate::vm::{CallFrame, ErrorHook, TraceHook, Vm};
 pub use crate::vm_call::VmCall;
-pub use crate::vm_error::{VmError, VmErrorKind};
+pub use crate::vm_error::{FrameInfo, VmError, VmErrorKind};
 pub use crate::vm_execution::VmExecution;
+pub use crate::vm_execution_state::VmExecutionState;
 pub use crate::vm_halt::{VmHalt, VmHaltInfo};
+pub use crate::vm_recording::Recording;
+pub use crate::vm_snapshot::VmSnapshot;
 
 mod collections {
     pub use hashbrown::HashMap;
@@ -0,0 +1,91 @@
+/// Detects instruction pointer windows which are repeated over and over, which
+/// is a sign of a likely infinite loop.
+///
+/// A window is detected when execution jumps back to an earlier instruction in
+/// the same call frame, and is repeated when the same backward jump is taken
+/// again without a different one being taken in between. Loops in functions
+/// called from inside of the window don't count as different jumps.
+///
+/// Once a window has been repeated `threshold` times, the virtual machine
+/// reports it to its error hook as a
+/// [LikelyInfiniteLoop][crate::VmErrorKind::LikelyInfiniteLoop] error, but
+/// keeps on running. See [Vm::set_loop_detector][crate::Vm::set_loop_detector].
+#[derive(Debug, Clone, Copy)]
+pub struct LoopDetector {
+    /// The largest window, in number of instructions, which is considered.
+    window: usize,
+    /// The number of repeats after which a window is reported.
+    threshold: usize,
+    /// The previously executed instruction pointer and its call depth.
+    previous: Option<(usize, usize)>,
+    /// The window currently being repeated, as its call depth and the
+    /// instruction pointers it starts and ends at.
+    current: Option<(usize, usize, usize)>,
+    /// The number of times the current window has been repeated.
+    repeats: usize,
+}
+
+impl LoopDetector {
+    /// Construct a new loop detector which considers windows of at most
+    /// `window` instructions, and reports them once they have been repeated
+    /// `threshold` times.
+    pub fn new(window: usize, threshold: usize) -> Self {
+        Self {
+            window,
+            threshold,
+            previous: None,
+            current: None,
+            repeats: 0,
+        }
+    }
+
+    /// Get the largest window, in number of instructions, which is considered.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Get the number of repeats after which a window is reported.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Construct a detector with the same configuration, but none of the
+    /// recorded state.
+    pub(crate) fn reset(&self) -> Self {
+        Self::new(self.window, self.threshold)
+    }
+
+    /// Visit the instruction pointer which is about to be executed at the
+    /// given call depth.
+    ///
+    /// Returns the window, as the instruction pointers it starts and ends at,
+    /// and the number of times it has been repeated once it reaches the
+    /// threshold.
+    pub(crate) fn visit(&mut self, ip: usize, depth: usize) -> Option<(usize, usize, usize)> {
+        let (previous, previous_depth) = self.previous.replace((ip, depth))?;
+
+        if depth != previous_depth || ip > previous || previous - ip >= self.window {
+            return None;
+        }
+
+        let window = (depth, ip, previous);
+
+        match self.current {
+            Some(current) if current == window => {
+                self.repeats += 1;
+            }
+            // NB: a loop in a called function is not a different window.
+            Some((current_depth, ..)) if current_depth < depth => return None,
+            _ => {
+                self.current = Some(window);
+                self.repeats = 1;
+            }
+        }
+
+        if self.repeats == self.threshold {
+            Some((ip, previous, self.repeats))
+        } else {
+            None
+        }
+    }
+}
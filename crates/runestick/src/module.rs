@@ -352,6 +352,12 @@ impl Module {
 
     /// Register a function that cannot error internally.
     ///
+    /// A function returning a `Result<T, E>`, where both `T` and `E` can be
+    /// converted into values, returns a result to the script which it can
+    /// handle like any other. This is how fallible native constructors should
+    /// be registered. Only functions returning a [VmError][crate::VmError] or
+    /// a [Panic][crate::Panic] as their error abort the virtual machine.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -359,6 +365,20 @@ impl Module {
     ///     value + 10
     /// }
     ///
+    /// #[derive(Debug)]
+    /// struct Port(u16);
+    ///
+    /// runestick::impl_external!(Port);
+    ///
+    /// impl Port {
+    ///     fn new(port: i64) -> Result<Self, String> {
+    ///         match port {
+    ///             1..=65535 => Ok(Self(port as u16)),
+    ///             _ => Err(format!("bad port `{}`", port)),
+    ///         }
+    ///     }
+    /// }
+    ///
     /// # fn main() -> runestick::Result<()> {
     /// let mut module = runestick::Module::default();
     ///
@@ -366,6 +386,9 @@ impl Module {
     /// module.function(&["empty"], || Ok::<_, runestick::Error>(()))?;
     /// module.function(&["string"], |a: String| Ok::<_, runestick::Error>(()))?;
     /// module.function(&["optional"], |a: Option<String>| Ok::<_, runestick::Error>(()))?;
+    ///
+    /// module.ty(&["Port"]).build::<Port>()?;
+    /// module.function(&["Port", "new"], Port::new)?;
     /// # Ok(())
     /// # }
     /// ```
@@ -437,7 +460,11 @@ impl Module {
         Ok(())
     }
 
-    /// Register a function.
+    /// Register an asynchronous function.
+    ///
+    /// Calling the function from a script produces a future which can be
+    /// awaited. Arguments must be `'static`, since they are moved into the
+    /// future and can't borrow from the stack it outlives.
     ///
     /// # Examples
     ///
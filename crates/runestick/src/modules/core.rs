@@ -1,8 +1,7 @@
 //! The core `std` module.
 
+use crate::output::with_output;
 use crate::{ContextError, Module, Panic, Stack, Value, VmError};
-use std::io;
-use std::io::Write as _;
 
 /// Construct the `std` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -12,6 +11,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty(&["bool"]).build::<bool>()?;
     module.ty(&["char"]).build::<char>()?;
     module.ty(&["byte"]).build::<u8>()?;
+    module.ty(&["any"]).build::<Value>()?;
 
     module.function(&["print"], print_impl)?;
     module.function(&["println"], println_impl)?;
@@ -63,34 +63,32 @@ fn drop_impl(value: Value) -> Result<(), VmError> {
 }
 
 fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-
-    for _ in 0..args {
-        match stack.pop() {
-            Ok(value) => {
-                writeln!(stdout, "{:?}", value).map_err(VmError::panic)?;
-            }
-            Err(e) => {
-                writeln!(stdout, "{}", e).map_err(VmError::panic)?;
+    with_output(|out| {
+        for _ in 0..args {
+            match stack.pop() {
+                Ok(value) => {
+                    writeln!(out, "{:?}", value)?;
+                }
+                Err(e) => {
+                    writeln!(out, "{}", e)?;
+                }
             }
         }
-    }
+
+        Ok(())
+    })
+    .map_err(VmError::panic)?;
 
     stack.push(Value::Unit);
     Ok(())
 }
 
 fn print_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    write!(stdout, "{}", m).map_err(Panic::custom)
+    with_output(|out| write!(out, "{}", m)).map_err(Panic::custom)
 }
 
 fn println_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    writeln!(stdout, "{}", m).map_err(Panic::custom)
+    with_output(|out| writeln!(out, "{}", m)).map_err(Panic::custom)
 }
 
 fn panic_impl(m: &str) -> Result<(), Panic> {
@@ -1,6 +1,6 @@
 //! The `std::float` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Module, RoundingMode};
 use std::num::ParseFloatError;
 
 /// Parse an integer.
@@ -8,12 +8,31 @@ fn parse(s: &str) -> Result<f64, ParseFloatError> {
     Ok(str::parse::<f64>(s)?)
 }
 
-/// Convert a float to a whole number.
+/// Convert a float to a whole number, using the rounding mode of the
+/// virtual machine.
 fn to_integer(value: f64) -> i64 {
-    value as i64
+    RoundingMode::current().round(value) as i64
+}
+
+/// An error raised when a float can't be represented as a whole number.
+#[derive(Debug)]
+pub struct ToIntegerError;
+
+/// Convert a float to a whole number, using the rounding mode of the
+/// virtual machine. Errors if the float is not finite or out of range.
+fn try_to_integer(value: f64) -> Result<i64, ToIntegerError> {
+    let rounded = RoundingMode::current().round(value);
+
+    // NB: `i64::MAX as f64` rounds up to 2^63, which is out of bounds.
+    if rounded.is_finite() && rounded >= i64::MIN as f64 && rounded < i64::MAX as f64 {
+        Ok(rounded as i64)
+    } else {
+        Err(ToIntegerError)
+    }
 }
 
 impl_external!(ParseFloatError);
+impl_external!(ToIntegerError);
 
 /// Install the core package into the given functions namespace.
 pub fn module() -> Result<Module, ContextError> {
@@ -23,8 +42,12 @@ pub fn module() -> Result<Module, ContextError> {
     module
         .ty(&["float", "ParseFloatError"])
         .build::<ParseFloatError>()?;
+    module
+        .ty(&["float", "ToIntegerError"])
+        .build::<ToIntegerError>()?;
     module.function(&["float", "parse"], parse)?;
     module.inst_fn("to_integer", to_integer)?;
+    module.inst_fn("try_to_integer", try_to_integer)?;
 
     Ok(module)
 }
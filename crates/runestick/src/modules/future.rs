@@ -8,6 +8,7 @@ pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::new(&["std", "future"]);
     module.ty(&["Future"]).build::<Future>()?;
     module.raw_fn(&["join"], raw_join)?;
+    module.raw_fn(&["race"], raw_race)?;
     Ok(module)
 }
 
@@ -67,3 +68,62 @@ fn raw_join(stack: &mut Stack, args: usize) -> Result<(), VmError> {
     stack.push(value);
     Ok(())
 }
+
+/// Race the given futures, producing the index and the value of the first one
+/// to complete.
+///
+/// Futures which have already completed are skipped, since they can't produce
+/// their value again.
+async fn race_impl<'a, I>(values: I) -> Result<(usize, Value), VmError>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    use futures::StreamExt as _;
+
+    let mut futures = futures::stream::FuturesUnordered::new();
+
+    for (index, value) in values.into_iter().enumerate() {
+        let future = match value {
+            Value::Future(future) => future.clone().owned_mut()?,
+            value => return Err(VmError::bad_argument::<Future>(index, value)?),
+        };
+
+        if !future.is_completed() {
+            futures.push(SelectFuture::new(index, future));
+        }
+    }
+
+    match futures.next().await {
+        Some(result) => result,
+        None => Err(VmError::from(VmErrorKind::NoPendingFutures)),
+    }
+}
+
+async fn race(value: Value) -> Result<(usize, Value), VmError> {
+    match value {
+        Value::Tuple(tuple) => {
+            let tuple = tuple.borrow_ref()?;
+            Ok(race_impl(tuple.iter()).await?)
+        }
+        Value::Vec(vec) => {
+            let vec = vec.borrow_ref()?;
+            Ok(race_impl(vec.iter()).await?)
+        }
+        value => Err(VmError::bad_argument::<Vec<Value>>(0, &value)?),
+    }
+}
+
+/// The race implementation.
+fn raw_race(stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    if args != 1 {
+        return Err(VmError::from(VmErrorKind::BadArgumentCount {
+            actual: args,
+            expected: 1,
+        }));
+    }
+
+    let value = stack.pop()?;
+    let value = Value::Future(Shared::new(Future::new(race(value))));
+    stack.push(value);
+    Ok(())
+}
@@ -1,6 +1,8 @@
 //! The `std::int` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Module, Panic};
+use std::char::CharTryFromError;
+use std::convert::TryFrom as _;
 use std::num::ParseIntError;
 
 /// Construct the `std::int` module.
@@ -11,9 +13,17 @@ pub fn module() -> Result<Module, ContextError> {
     module
         .ty(&["int", "ParseIntError"])
         .build::<ParseIntError>()?;
+    module
+        .ty(&["int", "ParseRadixError"])
+        .build::<ParseRadixError>()?;
+    module
+        .ty(&["int", "CharTryFromError"])
+        .build::<CharTryFromError>()?;
     module.function(&["int", "parse"], parse)?;
+    module.function(&["int", "parse_radix"], parse_radix)?;
 
     module.inst_fn("to_float", to_float)?;
+    module.inst_fn("to_char", to_char)?;
 
     module.inst_fn("checked_add", i64::checked_add)?;
     module.inst_fn("checked_sub", i64::checked_sub)?;
@@ -34,6 +44,7 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("saturating_pow", i64::saturating_pow)?;
 
     module.inst_fn("pow", i64::pow)?;
+    module.inst_fn("to_radix", to_radix)?;
     Ok(module)
 }
 
@@ -42,9 +53,68 @@ fn parse(s: &str) -> Result<i64, ParseIntError> {
     Ok(str::parse::<i64>(s)?)
 }
 
+/// An error raised when parsing an integer in a given radix.
+#[derive(Debug)]
+pub enum ParseRadixError {
+    /// The radix is not between 2 and 36.
+    InvalidRadix(u32),
+    /// The string is not a valid integer in the given radix.
+    ParseIntError(ParseIntError),
+}
+
+/// Parse an integer in the given radix, which must be between 2 and 36.
+fn parse_radix(s: &str, radix: u32) -> Result<i64, ParseRadixError> {
+    if !(2..=36).contains(&radix) {
+        return Err(ParseRadixError::InvalidRadix(radix));
+    }
+
+    i64::from_str_radix(s, radix).map_err(ParseRadixError::ParseIntError)
+}
+
 /// Convert a whole number to float.
 fn to_float(value: i64) -> f64 {
     value as f64
 }
 
+/// Convert an integer to the character with the corresponding code point.
+fn to_char(value: i64) -> Result<char, CharTryFromError> {
+    // NB: `u32::MAX` is not a valid code point, so values which don't fit in a
+    // `u32` fail the same way.
+    let value = u32::try_from(value).unwrap_or(u32::MAX);
+    char::try_from(value)
+}
+
+/// Render the integer as a string in the given radix, which must be between
+/// 2 and 36. Digits above 9 are rendered as lowercase letters.
+fn to_radix(value: i64, radix: u32) -> Result<String, Panic> {
+    if !(2..=36).contains(&radix) {
+        return Err(Panic::custom(format!(
+            "invalid radix `{}`, expected a radix between 2 and 36",
+            radix
+        )));
+    }
+
+    // NB: i128 is used so that the absolute value of `i64::MIN` fits.
+    let mut n = (value as i128).abs();
+    let mut digits = Vec::new();
+
+    loop {
+        let digit = (n % radix as i128) as u32;
+        digits.push(std::char::from_digit(digit, radix).expect("digit in radix"));
+        n /= radix as i128;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    if value < 0 {
+        digits.push('-');
+    }
+
+    Ok(digits.into_iter().rev().collect())
+}
+
 impl_external!(ParseIntError);
+impl_external!(ParseRadixError);
+impl_external!(CharTryFromError);
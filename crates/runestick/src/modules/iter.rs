@@ -1,6 +1,6 @@
 //! The `std::iter` module.
 
-use crate::{ContextError, Module};
+use crate::{ContextError, Iter, Module, Value, VmError};
 
 /// Construct the `std::iter` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -13,6 +13,17 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("rev", Range::rev)?;
     module.inst_fn(crate::INTO_ITER, Rev::into_iter)?;
     module.inst_fn(crate::NEXT, Rev::next)?;
+
+    module.ty(&["Iter"]).build::<Iter>()?;
+    module.inst_fn("next", Iter::try_next)?;
+    module.inst_fn(crate::NEXT, Iter::try_next)?;
+    module.inst_fn(crate::INTO_ITER, iter_into_iter)?;
+    module.inst_fn("map", Iter::map)?;
+    module.inst_fn("filter", Iter::filter)?;
+    module.inst_fn("enumerate", Iter::enumerate)?;
+    module.inst_fn("zip", iter_zip)?;
+    module.inst_fn("fold", Iter::fold)?;
+    module.inst_fn("collect", Iter::collect)?;
     Ok(module)
 }
 
@@ -72,5 +83,36 @@ impl Iterator for Range {
     }
 }
 
+/// Pair up the values of an iterator with the values of another iterator or
+/// vector, see [Iter::zip].
+fn iter_zip(iter: Iter, other: Value) -> Result<Iter, VmError> {
+    Ok(iter.zip(into_iter(other)?))
+}
+
+/// Convert a value which can be iterated over without calling into the script
+/// into an iterator.
+pub(crate) fn into_iter(value: Value) -> Result<Iter, VmError> {
+    match value {
+        Value::Vec(vec) => Ok(Iter::new(vec.borrow_ref()?.clone())),
+        Value::Any(any) => {
+            if any.borrow_ref()?.is::<Iter>() {
+                return Ok(any.take_downcast::<Iter>()?);
+            }
+
+            if any.borrow_ref()?.is::<super::vec::Iter>() {
+                return Ok(Iter::new(any.take_downcast::<super::vec::Iter>()?));
+            }
+
+            Err(VmError::bad_argument::<Iter>(1, &Value::Any(any))?)
+        }
+        value => Err(VmError::bad_argument::<Iter>(1, &value)?),
+    }
+}
+
+/// An iterator is its own iterator.
+fn iter_into_iter(iter: Iter) -> Iter {
+    iter
+}
+
 impl_external!(Range);
 impl_external!(Rev);
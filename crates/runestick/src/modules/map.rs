@@ -0,0 +1,52 @@
+//! The `std::map` module.
+
+use crate::{ContextError, HashKey, Iter, IterationOrder, Map, Module, Value, VmError};
+
+/// Construct the `std::map` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["std", "map"]);
+
+    module.ty(&["Map"]).build::<Map<Value>>()?;
+
+    module.function(&["Map", "new"], Map::<Value>::new)?;
+    module.inst_fn("len", Map::<Value>::len)?;
+    module.inst_fn("is_empty", Map::<Value>::is_empty)?;
+    module.inst_fn("clear", Map::<Value>::clear)?;
+    module.inst_fn("insert", insert)?;
+    module.inst_fn("get", get)?;
+    module.inst_fn("contains_key", contains_key)?;
+    module.inst_fn("remove", remove)?;
+    module.inst_fn("iter", map_iter)?;
+    module.inst_fn(crate::INTO_ITER, map_iter)?;
+    Ok(module)
+}
+
+fn insert(map: &mut Map<Value>, key: Value, value: Value) -> Result<Option<Value>, VmError> {
+    Ok(map.insert(HashKey::from_value(&key)?, value))
+}
+
+fn get(map: &Map<Value>, key: Value) -> Result<Option<Value>, VmError> {
+    Ok(map.get(&HashKey::from_value(&key)?).cloned())
+}
+
+fn contains_key(map: &Map<Value>, key: Value) -> Result<bool, VmError> {
+    Ok(map.contains_key(&HashKey::from_value(&key)?))
+}
+
+fn remove(map: &mut Map<Value>, key: Value) -> Result<Option<Value>, VmError> {
+    Ok(map.remove(&HashKey::from_value(&key)?))
+}
+
+/// Iterate over the entries of the map as `(key, value)` tuples.
+fn map_iter(map: &Map<Value>) -> Iter {
+    let mut entries = map
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Vec<_>>();
+
+    if let IterationOrder::Sorted = IterationOrder::current() {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
+    Iter::new(entries.into_iter().map(|(k, v)| (k.into_value(), v)))
+}
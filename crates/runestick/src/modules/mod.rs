@@ -10,7 +10,9 @@ pub mod generator;
 pub mod int;
 pub mod io;
 pub mod iter;
+pub mod map;
 pub mod object;
+pub mod ops;
 pub mod option;
 pub mod result;
 pub mod stream;
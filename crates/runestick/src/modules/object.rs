@@ -1,6 +1,6 @@
 //! The `std::object` module.
 
-use crate::{ContextError, Module, Object, Value};
+use crate::{ContextError, IterationOrder, Module, Object, Value};
 use std::iter::Rev;
 
 /// Construct the `std::object` module.
@@ -51,12 +51,17 @@ impl DoubleEndedIterator for Iter {
 }
 
 fn object_iter(object: &Object<Value>) -> Iter {
+    let mut entries = object
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<Vec<_>>();
+
+    if let IterationOrder::Sorted = IterationOrder::current() {
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+
     Iter {
-        iter: object
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect::<Vec<_>>()
-            .into_iter(),
+        iter: entries.into_iter(),
     }
 }
 
@@ -0,0 +1,13 @@
+//! The `std::ops` module.
+
+use crate::{ContextError, Module, Range};
+
+/// Construct the `std::ops` module.
+pub fn module() -> Result<Module, ContextError> {
+    let mut module = Module::new(&["std", "ops"]);
+    module.ty(&["Range"]).build::<Range>()?;
+    module.inst_fn("contains", Range::contains)?;
+    module.inst_fn("iter", Range::iter)?;
+    module.inst_fn(crate::INTO_ITER, Range::iter)?;
+    Ok(module)
+}
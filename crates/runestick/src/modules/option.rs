@@ -8,11 +8,13 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("is_none", Option::<Value>::is_none)?;
     module.inst_fn("is_some", Option::<Value>::is_some)?;
     module.inst_fn("unwrap_or_else", unwrap_or_else_impl)?;
+    module.inst_fn("expect", expect_impl)?;
     module.inst_fn("transpose", transpose_impl)?;
+    module.inst_fn("flatten", flatten_impl)?;
     Ok(module)
 }
 
-use crate::{ContextError, Function, Module, Shared, Value, VmError};
+use crate::{ContextError, Function, Module, Panic, Shared, Value, VmError};
 
 fn unwrap_or_else_impl(this: &Option<Value>, default: Function) -> Result<Value, VmError> {
     if let Some(this) = this {
@@ -22,6 +24,14 @@ fn unwrap_or_else_impl(this: &Option<Value>, default: Function) -> Result<Value,
     Ok(default.call(())?)
 }
 
+/// Unwrap the option, panicking with the given message if it is `None`.
+fn expect_impl(this: &Option<Value>, message: &str) -> Result<Value, Panic> {
+    match this {
+        Some(value) => Ok(value.clone()),
+        None => Err(Panic::custom(message.to_owned())),
+    }
+}
+
 /// Transpose functions, translates an Option<Result<T, E>> into a `Result<Option<T>, E>`.
 fn transpose_impl(this: &Option<Value>) -> Result<Value, VmError> {
     Ok(Value::from(Shared::new(match this.clone() {
@@ -32,3 +42,12 @@ fn transpose_impl(this: &Option<Value>) -> Result<Value, VmError> {
         None => Ok(Value::from(Shared::new(None::<Value>))),
     })))
 }
+
+/// Flatten a nested option, translating an `Option<Option<T>>` into an
+/// `Option<T>`.
+fn flatten_impl(this: &Option<Value>) -> Result<Option<Value>, VmError> {
+    Ok(match this.clone() {
+        Some(some) => some.into_option()?.borrow_ref()?.clone(),
+        None => None,
+    })
+}
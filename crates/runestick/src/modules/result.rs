@@ -1,6 +1,6 @@
 //! The `std::result` module.
 
-use crate::{ContextError, Module, Value};
+use crate::{ContextError, Module, Panic, Value, VmError};
 
 /// Construct the `std::result` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -8,6 +8,8 @@ pub fn module() -> Result<Module, ContextError> {
     module.result(&["Result"])?;
     module.inst_fn("is_ok", is_ok)?;
     module.inst_fn("is_err", is_err)?;
+    module.inst_fn("expect", expect)?;
+    module.inst_fn("flatten", flatten)?;
     Ok(module)
 }
 
@@ -18,3 +20,20 @@ fn is_ok(result: &Result<Value, Value>) -> bool {
 fn is_err(result: &Result<Value, Value>) -> bool {
     result.is_err()
 }
+
+/// Unwrap the result, panicking with the given message if it is `Err`.
+fn expect(result: &Result<Value, Value>, message: &str) -> Result<Value, Panic> {
+    match result {
+        Ok(value) => Ok(value.clone()),
+        Err(..) => Err(Panic::custom(message.to_owned())),
+    }
+}
+
+/// Flatten a nested result, translating a `Result<Result<T, E>, E>` into a
+/// `Result<T, E>`.
+fn flatten(result: &Result<Value, Value>) -> Result<Result<Value, Value>, VmError> {
+    Ok(match result.clone() {
+        Ok(ok) => ok.into_result()?.borrow_ref()?.clone(),
+        Err(err) => Err(err),
+    })
+}
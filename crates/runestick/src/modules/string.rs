@@ -1,6 +1,7 @@
 //! The `std::string` module.
 
 use crate::{Bytes, ContextError, Module};
+use std::convert::TryFrom as _;
 
 /// Construct the `std::string` module.
 pub fn module() -> Result<Module, ContextError> {
@@ -23,25 +24,59 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("clone", String::clone)?;
     module.inst_fn("shrink_to_fit", String::shrink_to_fit)?;
     module.inst_fn("char_at", char_at)?;
+    module.inst_fn("pad_left", pad_left)?;
+    module.inst_fn("pad_right", pad_right)?;
+    module.inst_fn("lines", lines)?;
     module.inst_fn(crate::ADD, add)?;
     module.inst_fn(crate::ADD_ASSIGN, String::push_str)?;
     Ok(module)
 }
 
-#[derive(Debug, Clone, Copy)]
-struct NotCharBoundary(());
-
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
 }
 
-fn char_at(s: &str, index: usize) -> Result<Option<char>, NotCharBoundary> {
-    if !s.is_char_boundary(index) {
-        return Err(NotCharBoundary(()));
-    }
+/// Get the character at the given character index, counting characters and
+/// not bytes. Returns `None` if the index is out of range.
+fn char_at(s: &str, index: i64) -> Option<char> {
+    let index = usize::try_from(index).ok()?;
+    s.chars().nth(index)
+}
+
+/// Pad the start of the string with the given character until it is the
+/// given width, counting characters and not bytes. Strings which are already
+/// wide enough are never truncated.
+fn pad_left(s: &str, width: i64, c: char) -> String {
+    let mut string = padding(s, width, c);
+    string.push_str(s);
+    string
+}
 
-    Ok(s[index..].chars().next())
+/// Pad the end of the string with the given character until it is the given
+/// width, counting characters and not bytes. Strings which are already wide
+/// enough are never truncated.
+fn pad_right(s: &str, width: i64, c: char) -> String {
+    let mut string = s.to_owned();
+    string.push_str(&padding(s, width, c));
+    string
+}
+
+/// The padding needed to make the string the given width.
+fn padding(s: &str, width: i64, c: char) -> String {
+    // NB: a negative width is narrower than any string.
+    let width = usize::try_from(width).unwrap_or_default();
+    let len = s.chars().count();
+    c.to_string().repeat(width.saturating_sub(len))
+}
+
+/// Split the string into lines.
+///
+/// Lines are terminated by either `\n` or `\r\n`, which are not included in
+/// the produced lines. A trailing newline does not produce a trailing empty
+/// line, so `"a\nb\n"` and `"a\nb"` both produce `["a", "b"]`.
+fn lines(s: &str) -> Vec<String> {
+    s.lines().map(String::from).collect()
 }
 
 /// The add operation for strings.
@@ -51,5 +86,3 @@ fn add(a: &str, b: &str) -> String {
     string.push_str(b);
     string
 }
-
-impl_external!(NotCharBoundary);
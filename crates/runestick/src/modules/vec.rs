@@ -1,6 +1,9 @@
 //! The `std::vec` module.
 
-use crate::{ContextError, Module, Value};
+use crate::{
+    Context, ContextError, Function, Module, Shared, Value, ValueType as _, VmError, VmErrorKind,
+};
+use std::cmp::Ordering;
 use std::iter::Rev;
 
 /// Construct the `std::vec` module.
@@ -14,14 +17,26 @@ pub fn module() -> Result<Module, ContextError> {
     module.function(&["Vec", "new"], Vec::<Value>::new)?;
     module.inst_fn("iter", vec_iter)?;
     module.inst_fn("len", Vec::<Value>::len)?;
+    module.inst_fn("capacity", Vec::<Value>::capacity)?;
     module.inst_fn("push", Vec::<Value>::push)?;
     module.inst_fn("clear", Vec::<Value>::clear)?;
     module.inst_fn("pop", Vec::<Value>::pop)?;
+    module.inst_fn("slice", vec_slice)?;
+    module.inst_fn("contains_all", vec_contains_all)?;
+    module.inst_fn("dedup", vec_dedup)?;
+    module.inst_fn("join", vec_join)?;
+    module.inst_fn("sort", vec_sort)?;
 
     module.inst_fn(crate::INTO_ITER, vec_iter)?;
     module.inst_fn("next", Iter::next)?;
     module.inst_fn(crate::NEXT, Iter::next)?;
     module.inst_fn(crate::INTO_ITER, Iter::into_iter)?;
+    module.inst_fn("map", iter_map)?;
+    module.inst_fn("filter", iter_filter)?;
+    module.inst_fn("enumerate", iter_enumerate)?;
+    module.inst_fn("zip", iter_zip)?;
+    module.inst_fn("fold", iter_fold)?;
+    module.inst_fn("collect", Iter::collect::<Vec<Value>>)?;
 
     module.inst_fn("rev", Iter::rev)?;
     module.inst_fn("next", Rev::<Iter>::next)?;
@@ -56,5 +71,174 @@ fn vec_iter(vec: &[Value]) -> Iter {
     }
 }
 
+/// Lazily map the values of a vector iterator, see [crate::Iter::map].
+fn iter_map(iter: Iter, function: Shared<Function>) -> crate::Iter {
+    crate::Iter::new(iter).map(function)
+}
+
+/// Lazily filter the values of a vector iterator, see [crate::Iter::filter].
+fn iter_filter(iter: Iter, predicate: Shared<Function>) -> crate::Iter {
+    crate::Iter::new(iter).filter(predicate)
+}
+
+/// Pair up the values of a vector iterator with their indexes, see
+/// [crate::Iter::enumerate].
+fn iter_enumerate(iter: Iter) -> crate::Iter {
+    crate::Iter::new(iter).enumerate()
+}
+
+/// Pair up the values of a vector iterator with the values of another iterator
+/// or vector, see [crate::Iter::zip].
+fn iter_zip(iter: Iter, other: Value) -> Result<crate::Iter, VmError> {
+    Ok(crate::Iter::new(iter).zip(super::iter::into_iter(other)?))
+}
+
+/// Fold the values of a vector iterator, see [crate::Iter::fold].
+fn iter_fold(iter: Iter, init: Value, function: Shared<Function>) -> Result<Value, VmError> {
+    crate::Iter::new(iter).fold(init, function)
+}
+
+/// Construct a new vector out of the `[start, end)` range of the given vector.
+///
+/// This copies the values in the range, which means that heap allocated values
+/// like strings and vectors are shared with the original vector, while
+/// primitive values like integers are not.
+fn vec_slice(vec: &[Value], start: usize, end: usize) -> Result<Vec<Value>, VmError> {
+    match vec.get(start..end) {
+        Some(values) => Ok(values.to_vec()),
+        None => Err(VmError::from(VmErrorKind::OutOfRange {
+            start,
+            end,
+            len: vec.len(),
+        })),
+    }
+}
+
+/// Test if every value in `other` is present in the given vector.
+///
+/// Values are compared by value, like with `==`. This is `O(n * m)` in the
+/// length of the two vectors.
+fn vec_contains_all(vec: &[Value], other: &[Value]) -> Result<bool, VmError> {
+    for b in other {
+        if !vec_contains(vec, b)? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Construct a new vector with all duplicate values removed, keeping the
+/// first occurrence of each value.
+///
+/// Values are compared by value, like with `==`. This is `O(n²)` in the length
+/// of the vector.
+fn vec_dedup(vec: &[Value]) -> Result<Vec<Value>, VmError> {
+    let mut output = Vec::with_capacity(vec.len());
+
+    for value in vec {
+        if !vec_contains(&output, value)? {
+            output.push(value.clone());
+        }
+    }
+
+    Ok(output)
+}
+
+/// Join a vector of strings into a single string, with the given separator
+/// between each element.
+fn vec_join(vec: &[Value], separator: &str) -> Result<String, VmError> {
+    let mut size_hint = separator.len() * vec.len().saturating_sub(1);
+
+    for (index, value) in vec.iter().enumerate() {
+        size_hint += match value {
+            Value::String(string) => string.borrow_ref()?.len(),
+            Value::StaticString(string) => string.len(),
+            actual => {
+                return Err(VmError::from(VmErrorKind::BadElementType {
+                    index,
+                    expected: String::type_info(),
+                    actual: actual.type_info()?,
+                }));
+            }
+        };
+    }
+
+    let mut buf = String::with_capacity(size_hint);
+
+    for (index, value) in vec.iter().enumerate() {
+        if index > 0 {
+            buf.push_str(separator);
+        }
+
+        match value {
+            Value::String(string) => buf.push_str(&*string.borrow_ref()?),
+            Value::StaticString(string) => buf.push_str(string.as_ref()),
+            _ => (),
+        }
+    }
+
+    Ok(buf)
+}
+
+/// Sort the vector in place.
+///
+/// Values are compared by value, where external values are compared using the
+/// [CMP][crate::CMP] protocol. Comparing values of different types, or floats
+/// which are NaN, is an error.
+fn vec_sort(vec: &mut [Value]) -> Result<(), VmError> {
+    let context = Context::current();
+    merge_sort(vec, &mut |a, b| Value::value_cmp(context.as_deref(), a, b))
+}
+
+/// A stable merge sort which stops at the first comparison that errors.
+///
+/// This is used instead of the sorts in std, since those may panic if the
+/// comparison isn't a total order, which a [CMP][crate::CMP] protocol
+/// implemented outside of Rust can't promise.
+fn merge_sort<F>(values: &mut [Value], cmp: &mut F) -> Result<(), VmError>
+where
+    F: FnMut(&Value, &Value) -> Result<Ordering, VmError>,
+{
+    let len = values.len();
+
+    if len <= 1 {
+        return Ok(());
+    }
+
+    let mid = len / 2;
+    merge_sort(&mut values[..mid], cmp)?;
+    merge_sort(&mut values[mid..], cmp)?;
+
+    let mut merged = Vec::with_capacity(len);
+    let (mut a, mut b) = (0, mid);
+
+    while a < mid && b < len {
+        if cmp(&values[b], &values[a])? == Ordering::Less {
+            merged.push(values[b].clone());
+            b += 1;
+        } else {
+            merged.push(values[a].clone());
+            a += 1;
+        }
+    }
+
+    merged.extend_from_slice(&values[a..mid]);
+    merged.extend_from_slice(&values[b..]);
+    values.clone_from_slice(&merged);
+    Ok(())
+}
+
+/// Test if the given value is present in the vector.
+fn vec_contains(vec: &[Value], value: &Value) -> Result<bool, VmError> {
+    for a in vec {
+        if Value::value_ptr_eq(a, value)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
 impl_external!(Iter);
 impl_external!(Rev<Iter>);
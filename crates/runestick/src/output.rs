@@ -0,0 +1,68 @@
+use crate::ambient::Ambient;
+use std::cell::RefCell;
+use std::fmt;
+use std::io;
+use std::rc::Rc;
+
+/// A buffer capturing the output of scripts, like what is written through
+/// `print` or `dbg`.
+///
+/// Output is a cheap handle to a shared buffer, so a clone of it can be kept
+/// by the host to access captured output after the virtual machine it's
+/// installed in has completed.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{Context, Output, Unit, Vm};
+/// use std::sync::Arc;
+///
+/// let context = Arc::new(Context::new());
+/// let unit = Arc::new(Unit::default());
+///
+/// let output = Output::new();
+/// let mut vm = Vm::new(context, unit);
+/// vm.set_output(output.clone());
+/// ```
+#[derive(Clone, Default)]
+pub struct Output {
+    buf: Rc<RefCell<Vec<u8>>>,
+}
+
+impl Output {
+    /// Construct a new empty output buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take all output captured so far, leaving the buffer empty.
+    pub fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.buf.borrow_mut())
+    }
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Output")
+            .field("len", &self.buf.borrow().len())
+            .finish()
+    }
+}
+
+/// Write to the output of the virtual machine currently running on this
+/// thread, falling back to stdout if it doesn't capture output.
+pub(crate) fn with_output<F, O>(f: F) -> io::Result<O>
+where
+    F: FnOnce(&mut dyn io::Write) -> io::Result<O>,
+{
+    let output = Ambient::with(|ambient| ambient.output.clone());
+
+    match output {
+        Some(output) => f(&mut *output.buf.borrow_mut()),
+        None => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            f(&mut stdout)
+        }
+    }
+}
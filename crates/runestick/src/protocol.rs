@@ -40,13 +40,22 @@ impl fmt::Display for Protocol {
     }
 }
 
-/// The function to access an index.
+/// The function to access an index, as in `value[index]`.
+///
+/// The handler is called with the value being indexed and the index, which can
+/// be any value, including a [Range][crate::Range] for expressions like
+/// `value[1..3]`. A handler which supports several kinds of indexes can take
+/// the index as a [Value][crate::Value] and match on it.
 pub const INDEX_GET: Protocol = Protocol {
     name: "index_get",
     hash: Hash::new(0xadb5b27e2a4d2dec),
 };
 
-/// The function to set an index.
+/// The function to set an index, as in `value[index] = other`.
+///
+/// The handler is called with the value being indexed, the index, and the
+/// value being assigned. Like with [INDEX_GET], the index can be any value,
+/// including a [Range][crate::Range].
 pub const INDEX_SET: Protocol = Protocol {
     name: "index_set",
     hash: Hash::new(0x162943f7bd03ad36),
@@ -172,6 +181,36 @@ pub const SHR_ASSIGN: Protocol = Protocol {
     hash: Hash::new(0x61ff7c46ff00e74a),
 };
 
+/// The function to implement for the equality operation.
+pub const EQ: Protocol = Protocol {
+    name: "==",
+    hash: Hash::new(0x418f5becbf885806),
+};
+
+/// The function to implement for the greater than operation.
+pub const GT: Protocol = Protocol {
+    name: ">",
+    hash: Hash::new(0xa9ba9c2298b8f027),
+};
+
+/// The function to implement for the greater than or equal operation.
+pub const GTE: Protocol = Protocol {
+    name: ">=",
+    hash: Hash::new(0x385cf6504c55fe06),
+};
+
+/// The function to implement for the less than operation.
+pub const LT: Protocol = Protocol {
+    name: "<",
+    hash: Hash::new(0x29a01901544b9a76),
+};
+
+/// The function to implement for the less than or equal operation.
+pub const LTE: Protocol = Protocol {
+    name: "<=",
+    hash: Hash::new(0xd909da61a2fb454c),
+};
+
 /// Protocol function used by template strings.
 pub const STRING_DISPLAY: Protocol = Protocol {
     name: "string_display",
@@ -195,3 +234,26 @@ pub const INTO_FUTURE: Protocol = Protocol {
     name: "into_future",
     hash: Hash::new(0x596e6428deabfda2),
 };
+
+/// The function to implement for comparing two values, like when sorting.
+///
+/// It's also used by the comparison operators `<`, `<=`, `>`, and `>=` for
+/// types which don't implement the protocol of the specific operator.
+///
+/// The function is expected to return a negative integer if the first value
+/// is less than the second, zero if they're equal, and a positive integer
+/// otherwise.
+pub const CMP: Protocol = Protocol {
+    name: "cmp",
+    hash: Hash::new(0x2f4a9b1e6d83c057),
+};
+
+/// Function called when the iterator of a `for` loop goes out of scope, if
+/// the loop held the last reference to it.
+///
+/// This is not a general finalizer: locals going out of scope and values
+/// released by the host are not passed to it.
+pub const DROP: Protocol = Protocol {
+    name: "drop",
+    hash: Hash::new(0x7b5c8a2a3c1d9e64),
+};
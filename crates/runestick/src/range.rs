@@ -0,0 +1,152 @@
+use crate::{
+    FromValue, Iter, OwnedRef, RawOwnedRef, Shared, UnsafeFromValue, Value, VmError, VmErrorKind,
+};
+use std::convert::TryFrom as _;
+use std::fmt;
+use std::ops;
+
+/// A range of integers, as constructed by the range expressions `a..b`,
+/// `a..=b`, `a..`, `..b`, and `..`.
+///
+/// # Examples
+///
+/// ```rust
+/// let range = runestick::Range::new(Some(1), Some(3), false);
+/// assert!(range.contains(2));
+/// assert!(!range.contains(3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    /// The start of the range, if any.
+    pub start: Option<i64>,
+    /// The end of the range, if any.
+    pub end: Option<i64>,
+    /// If the end of the range is inclusive.
+    pub inclusive: bool,
+}
+
+impl Range {
+    /// Construct a new range.
+    pub fn new(start: Option<i64>, end: Option<i64>, inclusive: bool) -> Self {
+        Self {
+            start,
+            end,
+            inclusive,
+        }
+    }
+
+    /// Test if the range contains the given integer.
+    pub fn contains(&self, n: i64) -> bool {
+        if let Some(start) = self.start {
+            if n < start {
+                return false;
+            }
+        }
+
+        match self.end {
+            Some(end) if self.inclusive => n <= end,
+            Some(end) => n < end,
+            None => true,
+        }
+    }
+
+    /// Iterate over the range.
+    ///
+    /// This errors if the range doesn't have a start. A range without an end
+    /// stops after [i64::MAX].
+    pub fn iter(&self) -> Result<Iter, VmError> {
+        let start = match self.start {
+            Some(start) => start,
+            None => {
+                return Err(VmError::from(VmErrorKind::UnsupportedRangeIter {
+                    range: *self,
+                }))
+            }
+        };
+
+        Ok(match self.end {
+            Some(end) if self.inclusive => Iter::new(start..=end),
+            Some(end) => Iter::new(start..end),
+            None => Iter::new(start..=i64::MAX),
+        })
+    }
+
+    /// Resolve the range into a range of indexes into a sequence of the given
+    /// length.
+    ///
+    /// Returns `None` if the range is out of bounds or decreasing.
+    pub(crate) fn to_slice_range(self, len: usize) -> Option<ops::Range<usize>> {
+        let start = match self.start {
+            Some(start) => usize::try_from(start).ok()?,
+            None => 0,
+        };
+
+        let end = match self.end {
+            Some(end) => {
+                let end = usize::try_from(end).ok()?;
+
+                if self.inclusive {
+                    end.checked_add(1)?
+                } else {
+                    end
+                }
+            }
+            None => len,
+        };
+
+        if start > end || end > len {
+            return None;
+        }
+
+        Some(start..end)
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(start) = self.start {
+            write!(f, "{}", start)?;
+        }
+
+        if self.inclusive {
+            write!(f, "..=")?;
+        } else {
+            write!(f, "..")?;
+        }
+
+        if let Some(end) = self.end {
+            write!(f, "{}", end)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromValue for Shared<Range> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_range()
+    }
+}
+
+impl FromValue for Range {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        let range = value.into_range()?;
+        let range = *range.borrow_ref()?;
+        Ok(range)
+    }
+}
+
+impl UnsafeFromValue for &Range {
+    type Output = *const Range;
+    type Guard = RawOwnedRef;
+
+    unsafe fn unsafe_from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let range = value.into_range()?;
+        let (range, guard) = OwnedRef::into_raw(range.owned_ref()?);
+        Ok((range, guard))
+    }
+
+    unsafe fn to_arg(output: Self::Output) -> Self {
+        &*output
+    }
+}
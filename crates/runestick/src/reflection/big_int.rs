@@ -0,0 +1,13 @@
+//! Big integer trait implementations.
+
+use crate::{BigInt, FromValue, Value, VmError};
+
+impl FromValue for BigInt {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        match value {
+            Value::BigInt(big_int) => Ok((*big_int).clone()),
+            Value::Integer(integer) => Ok(BigInt::from(integer)),
+            actual => Err(VmError::expected::<BigInt>(actual.type_info()?)),
+        }
+    }
+}
@@ -8,12 +8,14 @@ macro_rules! impl_map {
         {
             fn from_value(value: $crate::Value) -> Result<Self, $crate::VmError> {
                 let object = value.into_object()?;
-                let object = object.take()?;
+                // NB: the object might still be used elsewhere, so its entries
+                // are cloned rather than taken.
+                let object = object.borrow_ref()?;
 
                 let mut output = <$ty>::with_capacity(object.len());
 
-                for (key, value) in object {
-                    output.insert(key, T::from_value(value)?);
+                for (key, value) in object.iter() {
+                    output.insert(key.clone(), T::from_value(value.clone())?);
                 }
 
                 Ok(output)
@@ -0,0 +1,72 @@
+use crate::{
+    FromValue, Map, OwnedMut, OwnedRef, RawOwnedMut, RawOwnedRef, Shared, ToValue, UnsafeFromValue,
+    Value, VmError,
+};
+
+impl<T> FromValue for Map<T>
+where
+    T: FromValue,
+{
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        let map = value.into_map()?;
+        let map = map.take()?;
+        let mut output = Map::with_capacity(map.len());
+
+        for (key, value) in map {
+            output.insert(key, T::from_value(value)?);
+        }
+
+        Ok(output)
+    }
+}
+
+impl FromValue for Shared<Map<Value>> {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        value.into_map()
+    }
+}
+
+impl UnsafeFromValue for &Map<Value> {
+    type Output = *const Map<Value>;
+    type Guard = RawOwnedRef;
+
+    unsafe fn unsafe_from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let map = value.into_map()?;
+        let map = map.owned_ref()?;
+        Ok(OwnedRef::into_raw(map))
+    }
+
+    unsafe fn to_arg(output: Self::Output) -> Self {
+        &*output
+    }
+}
+
+impl UnsafeFromValue for &mut Map<Value> {
+    type Output = *mut Map<Value>;
+    type Guard = RawOwnedMut;
+
+    unsafe fn unsafe_from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let map = value.into_map()?;
+        let map = map.owned_mut()?;
+        Ok(OwnedMut::into_raw(map))
+    }
+
+    unsafe fn to_arg(output: Self::Output) -> Self {
+        &mut *output
+    }
+}
+
+impl<T> ToValue for Map<T>
+where
+    T: ToValue,
+{
+    fn to_value(self) -> Result<Value, VmError> {
+        let mut map = Map::with_capacity(self.len());
+
+        for (key, value) in self {
+            map.insert(key, value.to_value()?);
+        }
+
+        Ok(Value::from(Shared::new(map)))
+    }
+}
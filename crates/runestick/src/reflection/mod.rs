@@ -1,7 +1,9 @@
 use crate::{Type, TypeInfo, Value, VmError};
 
+mod big_int;
 mod bytes;
 mod hash_map;
+mod map;
 mod object;
 mod option;
 mod primitive;
@@ -9,12 +9,14 @@ where
 {
     fn from_value(value: Value) -> Result<Self, VmError> {
         let vec = value.into_vec()?;
-        let vec = vec.take()?;
+        // NB: the vector might still be used elsewhere, so its elements are
+        // cloned rather than taken.
+        let vec = vec.borrow_ref()?;
 
         let mut output = Vec::with_capacity(vec.len());
 
-        for value in vec {
-            output.push(T::from_value(value)?);
+        for value in vec.iter() {
+            output.push(T::from_value(value.clone())?);
         }
 
         Ok(output)
@@ -36,6 +38,21 @@ impl<'a> UnsafeFromValue for &'a [Value] {
     }
 }
 
+impl<'a> UnsafeFromValue for &'a mut [Value] {
+    type Output = *mut [Value];
+    type Guard = RawOwnedMut;
+
+    unsafe fn unsafe_from_value(value: Value) -> Result<(Self::Output, Self::Guard), VmError> {
+        let vec = value.into_vec()?;
+        let (vec, guard) = OwnedMut::into_raw(vec.owned_mut()?);
+        Ok((&mut **vec, guard))
+    }
+
+    unsafe fn to_arg(output: Self::Output) -> Self {
+        &mut *output
+    }
+}
+
 impl<'a> UnsafeFromValue for &'a Vec<Value> {
     type Output = *const Vec<Value>;
     type Guard = RawOwnedRef;
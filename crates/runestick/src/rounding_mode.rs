@@ -0,0 +1,49 @@
+use crate::ambient::Ambient;
+
+/// The rounding mode used by the virtual machine when converting floats to
+/// integers, like through `float::to_integer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round towards zero, so `2.7` becomes `2` and `-2.7` becomes `-2`. This
+    /// matches the behavior of `as` casts in Rust.
+    ///
+    /// This is the default mode.
+    Truncate,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round to the nearest integer, with ties rounding to the nearest even
+    /// integer. So `2.5` becomes `2` and `3.5` becomes `4`.
+    RoundHalfEven,
+}
+
+impl RoundingMode {
+    /// Round the given float according to the mode.
+    pub fn round(self, value: f64) -> f64 {
+        match self {
+            Self::Truncate => value.trunc(),
+            Self::Floor => value.floor(),
+            Self::Ceil => value.ceil(),
+            Self::RoundHalfEven => {
+                if (value - value.trunc()).abs() == 0.5 {
+                    2.0 * (value / 2.0).round()
+                } else {
+                    value.round()
+                }
+            }
+        }
+    }
+
+    /// Get the rounding mode of the virtual machine currently running on
+    /// this thread.
+    pub(crate) fn current() -> Self {
+        Ambient::with(|ambient| ambient.rounding_mode)
+    }
+}
+
+impl Default for RoundingMode {
+    fn default() -> Self {
+        Self::Truncate
+    }
+}
@@ -2,7 +2,9 @@ use crate::bytes::Bytes;
 use crate::collections::HashMap;
 use crate::shared::Shared;
 use crate::value::Value;
+use crate::BigInt;
 use serde::{de, ser};
+use std::convert::TryInto;
 use std::fmt;
 
 /// Deserialize implementation for value pointers.
@@ -31,6 +33,7 @@ impl ser::Serialize for Value {
             Value::Byte(c) => serializer.serialize_u8(*c),
             Value::Integer(integer) => serializer.serialize_i64(*integer),
             Value::Float(float) => serializer.serialize_f64(*float),
+            Value::BigInt(big_int) => serializer.serialize_str(&big_int.to_string()),
             Value::StaticString(string) => serializer.serialize_str(string.as_ref()),
             Value::String(string) => {
                 let string = string.borrow_ref().map_err(ser::Error::custom)?;
@@ -70,15 +73,35 @@ impl ser::Serialize for Value {
 
                 serializer.end()
             }
+            Value::Map(map) => {
+                let map = map.borrow_ref().map_err(ser::Error::custom)?;
+                let mut serializer = serializer.serialize_map(Some(map.len()))?;
+
+                for (key, value) in &*map {
+                    serializer.serialize_entry(&key.clone().into_value(), value)?;
+                }
+
+                serializer.end()
+            }
             Value::Option(option) => {
                 let option = option.borrow_ref().map_err(ser::Error::custom)?;
                 <Option<Value>>::serialize(&*option, serializer)
             }
             Value::TypedTuple(..) => Err(ser::Error::custom("cannot serialize tuple types")),
             Value::TupleVariant(..) => Err(ser::Error::custom("cannot serialize variant tuples")),
-            Value::TypedObject(..) => Err(ser::Error::custom("cannot serialize object types")),
+            Value::TypedObject(object) => {
+                let object = object.borrow_ref().map_err(ser::Error::custom)?;
+                let mut serializer = serializer.serialize_map(Some(object.object.len()))?;
+
+                for (key, value) in &object.object {
+                    serializer.serialize_entry(key, value)?;
+                }
+
+                serializer.end()
+            }
             Value::VariantObject(..) => Err(ser::Error::custom("cannot serialize variant objects")),
             Value::Result(..) => Err(ser::Error::custom("cannot serialize results")),
+            Value::Range(..) => Err(ser::Error::custom("cannot serialize ranges")),
             Value::Type(..) => Err(ser::Error::custom("cannot serialize types")),
             Value::Future(..) => Err(ser::Error::custom("cannot serialize futures")),
             Value::Stream(..) => Err(ser::Error::custom("cannot serialize streams")),
@@ -92,6 +115,18 @@ impl ser::Serialize for Value {
     }
 }
 
+/// Construct an integer value, falling back to a big integer if it doesn't
+/// fit in an `i64`.
+fn integer<T>(v: T) -> Value
+where
+    T: Copy + TryInto<i64> + Into<BigInt>,
+{
+    match v.try_into() {
+        Ok(integer) => Value::Integer(integer),
+        Err(..) => Value::from(v.into()),
+    }
+}
+
 struct VmVisitor;
 
 impl<'de> de::Visitor<'de> for VmVisitor {
@@ -170,7 +205,7 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        Ok(integer(v))
     }
 
     #[inline]
@@ -202,7 +237,7 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        Ok(integer(v))
     }
 
     #[inline]
@@ -210,7 +245,23 @@ impl<'de> de::Visitor<'de> for VmVisitor {
     where
         E: de::Error,
     {
-        Ok(Value::Integer(v as i64))
+        Ok(integer(v))
+    }
+
+    #[inline]
+    fn visit_f32<E>(self, v: f32) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v as f64))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Value::Float(v))
     }
 
     #[inline]
@@ -254,6 +254,36 @@ impl<T> Shared<T> {
 }
 
 impl<T: ?Sized> Shared<T> {
+    /// Test if two shared values point to the same underlying value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::Shared;
+    ///
+    /// let a = Shared::new(1);
+    /// let b = Shared::new(1);
+    ///
+    /// assert!(Shared::ptr_eq(&a, &a.clone()));
+    /// assert!(!Shared::ptr_eq(&a, &b));
+    /// ```
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        std::ptr::eq(a.inner.as_ptr() as *const (), b.inner.as_ptr() as *const ())
+    }
+
+    /// Get the address of the underlying value, which identifies it in the
+    /// same way as [ptr_eq][Self::ptr_eq].
+    pub(crate) fn as_ptr(this: &Self) -> *const () {
+        this.inner.as_ptr() as *const u8 as *const ()
+    }
+
+    /// Test if this is the only reference to the underlying value.
+    pub(crate) fn is_unique(this: &Self) -> bool {
+        // Safety: by virtue of holding onto a shared we can safely access
+        // `inner` because it must outlive any `Shared` instances.
+        unsafe { this.inner.as_ref().count.get() == 1 }
+    }
+
     /// Get a reference to the interior value while checking for shared access.
     ///
     /// This prevents other exclusive accesses from being performed while the
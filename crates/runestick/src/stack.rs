@@ -29,6 +29,15 @@ impl Stack {
         }
     }
 
+    /// Construct a stack from its values and the bottom of the current stack
+    /// frame.
+    pub(crate) fn from_parts(stack: Vec<Value>, stack_bottom: usize) -> Self {
+        Self {
+            stack,
+            stack_bottom,
+        }
+    }
+
     /// Extend the current stack.
     pub fn extend<I>(&mut self, iter: I)
     where
@@ -82,6 +82,14 @@ pub static FLOAT_TYPE: &StaticType = &StaticType {
 impl_static_type!(f32 => crate::FLOAT_TYPE);
 impl_static_type!(f64 => crate::FLOAT_TYPE);
 
+/// The specialized type information for a big integer type.
+pub static BIG_INT_TYPE: &StaticType = &StaticType {
+    name: "BigInt",
+    hash: Hash::new(0x4c6d1f2c9e3a7b85),
+};
+
+impl_static_type!(num_bigint::BigInt => crate::BIG_INT_TYPE);
+
 /// The specialized type information for a string type.
 pub static STRING_TYPE: &StaticType = &StaticType {
     name: "String",
@@ -118,6 +126,14 @@ pub static TUPLE_TYPE: &StaticType = &StaticType {
 
 impl_static_type!(crate::Tuple => TUPLE_TYPE);
 
+/// The specialized type information for a range type.
+pub static RANGE_TYPE: &StaticType = &StaticType {
+    name: "Range",
+    hash: Hash::new(0x3b5e8ab22b2e54d1),
+};
+
+impl_static_type!(crate::Range => RANGE_TYPE);
+
 /// The specialized type information for an anonymous object type.
 pub static OBJECT_TYPE: &StaticType = &StaticType {
     name: "Object",
@@ -126,6 +142,14 @@ pub static OBJECT_TYPE: &StaticType = &StaticType {
 
 impl_static_type!(impl<T> crate::Object<T> => OBJECT_TYPE);
 
+/// The specialized type information for a map type.
+pub static MAP_TYPE: &StaticType = &StaticType {
+    name: "Map",
+    hash: Hash::new(0x8f3a1d2c6b5e4970),
+};
+
+impl_static_type!(impl<T> crate::Map<T> => MAP_TYPE);
+
 /// The specialized type information for a future type.
 pub static FUTURE_TYPE: &StaticType = &StaticType {
     name: "Future",
@@ -183,3 +207,15 @@ pub static FUNCTION_TYPE: &StaticType = &StaticType {
 impl_static_type!(crate::Function => FUNCTION_TYPE);
 impl_static_type!(crate::Shared<crate::Function> => FUNCTION_TYPE);
 impl_static_type!(crate::OwnedRef<crate::Function> => FUNCTION_TYPE);
+
+/// The type information for any value.
+///
+/// Instance functions registered for this type, by taking [Value][crate::Value]
+/// as the instance, can be called on values of any type which doesn't have a
+/// more specific instance function registered with the same name.
+pub static ANY_TYPE: &StaticType = &StaticType {
+    name: "any",
+    hash: Hash::new(0xb0eb0bfb6394229f),
+};
+
+impl_static_type!(crate::Value => ANY_TYPE);
@@ -4,10 +4,17 @@
 //! metadata like function locations.
 
 use crate::collections::HashMap;
-use crate::{Call, DebugInfo, Hash, Inst, StaticString, Type, VmError, VmErrorKind};
+use crate::{
+    Call, DebugInfo, DebugSignature, Hash, Inst, Span, StaticString, Type, VmError, VmErrorKind,
+};
 use std::fmt;
 use std::sync::Arc;
 
+mod link;
+mod optimize;
+
+pub use self::link::LinkError;
+
 /// Instructions from a single source file.
 #[derive(Debug, Default)]
 pub struct Unit {
@@ -30,6 +37,13 @@ pub struct Unit {
     static_object_keys: Vec<Box<[String]>>,
     /// Debug info if available for unit.
     debug: Option<Box<DebugInfo>>,
+    /// The instruction ranges covered by functions, as the offset each range
+    /// starts at and the function it belongs to, sorted by offset.
+    ///
+    /// A range ends where the next one starts.
+    function_ranges: Vec<(usize, Hash)>,
+    /// The number of inline cache slots used by instance calls.
+    instance_call_slots: usize,
 }
 
 impl Unit {
@@ -43,7 +57,7 @@ impl Unit {
         static_object_keys: Vec<Box<[String]>>,
         debug: Option<Box<DebugInfo>>,
     ) -> Self {
-        Self {
+        let mut unit = Self {
             instructions,
             functions,
             types,
@@ -51,7 +65,26 @@ impl Unit {
             static_bytes,
             static_object_keys,
             debug,
-        }
+            function_ranges: Vec::new(),
+            instance_call_slots: 0,
+        };
+
+        unit.index_function_ranges();
+        unit.index_instance_call_slots();
+        unit
+    }
+
+    /// Link separately compiled units into a single unit.
+    ///
+    /// Instructions are concatenated in the order the units are provided,
+    /// function offsets are adjusted accordingly, and static strings, byte
+    /// strings, and object keys are merged into shared pools with duplicates
+    /// removed. Instance call slots are renumbered so that they stay unique.
+    /// Debug information is only preserved if every unit has it.
+    ///
+    /// Errors if more than one unit declares the same function or type.
+    pub fn link(units: Vec<Unit>) -> Result<Unit, LinkError> {
+        link::link(units)
     }
 
     /// Access the type for the given language item.
@@ -65,11 +98,150 @@ impl Unit {
         Some(&**debug)
     }
 
+    /// Strip all debug information from the unit.
+    ///
+    /// This drops the per-instruction span table and function signatures,
+    /// which reduces the size of the unit without affecting how it runs.
+    /// Errors raised while running a stripped unit will still report the
+    /// instruction pointer at which they happened, but diagnostics will no
+    /// longer be able to point to the source.
+    pub fn strip_debug(&mut self) {
+        self.debug = None;
+    }
+
+    /// Optimize the unit by removing redundant instructions.
+    ///
+    /// This currently removes jumps to the next instruction, values which are
+    /// pushed onto the stack only to be immediately popped, like a `copy`
+    /// directly followed by a `pop`, fuses a `copy` directly followed by a
+    /// `replace` into a single [move][crate::Inst::Move], folds constant
+    /// expressions like [fold_constants][Self::fold_constants], and removes
+    /// unreachable instructions like
+    /// [eliminate_dead_code][Self::eliminate_dead_code].
+    /// This is repeated until nothing more can be removed.
+    ///
+    /// The behavior of the unit is preserved, and jumps, function offsets, and
+    /// debug information are adjusted to account for removed instructions.
+    pub fn optimize(&mut self) {
+        loop {
+            let mut changed = false;
+            changed |= optimize::remove_nop_jumps(self);
+            changed |= optimize::elide_redundant_pops(self);
+            changed |= optimize::fuse_moves(self);
+            changed |= optimize::fold_constants(self);
+            changed |= optimize::eliminate_dead_code(self);
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Fold arithmetic, bitwise, and comparison operations whose operands are
+    /// constants into a single constant. So `2 + 3 * 4` is compiled into a
+    /// single push of `14`.
+    ///
+    /// Operations which would fail at runtime, like an addition that
+    /// overflows, are left as-is so that they still raise an error when
+    /// executed.
+    pub fn fold_constants(&mut self) {
+        while optimize::fold_constants(self) {}
+    }
+
+    /// Remove instructions which can never be executed, like instructions
+    /// following a `return` which can't be jumped to.
+    pub fn eliminate_dead_code(&mut self) {
+        while optimize::eliminate_dead_code(self) {}
+    }
+
     /// Get the instruction at the given instruction pointer.
     pub fn instruction_at(&self, ip: usize) -> Option<&Inst> {
         self.instructions.get(ip)
     }
 
+    /// Get the span of the source which the instruction at the given
+    /// instruction pointer was compiled from.
+    ///
+    /// This is looked up in the per-instruction span table of the
+    /// [debug information][Self::debug_info], so it returns `None` if the
+    /// unit has been stripped of it. Together with [VmError::trace] this can
+    /// be used to point to where in the source an error happened.
+    ///
+    /// [VmError::trace]: crate::VmError::trace
+    pub fn span_at(&self, ip: usize) -> Option<Span> {
+        Some(self.debug_info()?.instruction_at(ip)?.span)
+    }
+
+    /// Get the function whose instructions contain the given instruction
+    /// pointer.
+    ///
+    /// Unlike [DebugInfo::function_at], this doesn't require the instruction
+    /// pointer to be at the start of the function, so it can be used from a
+    /// trace hook to attribute samples to functions when profiling. The
+    /// signature is looked up in the [debug information][Self::debug_info],
+    /// so it returns `None` if the unit has been stripped of it.
+    pub fn function_at(&self, ip: usize) -> Option<(Hash, &DebugSignature)> {
+        if ip >= self.instructions.len() {
+            return None;
+        }
+
+        let index = match self
+            .function_ranges
+            .binary_search_by(|(offset, _)| offset.cmp(&ip))
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+
+        let (_, hash) = self.function_ranges[index];
+        let signature = self.debug_info()?.functions.get(&hash)?;
+        Some((hash, signature))
+    }
+
+    /// Index the instruction ranges covered by functions, which must be done
+    /// whenever function offsets change.
+    ///
+    /// NB: functions are compiled one after another, so each one covers the
+    /// instructions up until the next one starts.
+    fn index_function_ranges(&mut self) {
+        let mut ranges = self
+            .functions
+            .iter()
+            .filter_map(|(hash, unit_fn)| match unit_fn {
+                UnitFn::Offset { offset, .. } => Some((*offset, *hash)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        ranges.sort_by_key(|(offset, _)| *offset);
+        ranges.dedup_by_key(|(offset, _)| *offset);
+        self.function_ranges = ranges;
+    }
+
+    /// Count the inline cache slots used by instance calls, which must be done
+    /// whenever the slots of instructions change.
+    fn index_instance_call_slots(&mut self) {
+        self.instance_call_slots = self
+            .instructions
+            .iter()
+            .filter_map(|inst| match inst {
+                Inst::CallInstance { slot, .. } => Some(*slot + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+    }
+
+    /// The number of inline cache slots used by
+    /// [instance calls][crate::Inst::CallInstance] in the unit.
+    ///
+    /// Every call site is assigned its own slot, which the virtual machine
+    /// uses to remember the function it last resolved to.
+    pub fn instance_call_slots(&self) -> usize {
+        self.instance_call_slots
+    }
+
     /// Iterate over all static strings in the unit.
     pub fn iter_static_strings(&self) -> impl Iterator<Item = &Arc<StaticString>> + '_ {
         self.static_strings.iter()
@@ -0,0 +1,162 @@
+//! Linking of separately compiled units.
+
+use crate::collections::HashMap;
+use crate::debug::DebugInfo;
+use crate::{Hash, Inst, StaticString, Unit, UnitFn};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// An error raised while linking units.
+#[derive(Debug, Error)]
+pub enum LinkError {
+    /// More than one unit declares a function with the same hash.
+    #[error("conflicting function hash `{hash}` declared in more than one unit")]
+    ConflictingFunction {
+        /// The hash of the conflicting function.
+        hash: Hash,
+    },
+    /// More than one unit declares a type with the same hash.
+    #[error("conflicting type hash `{hash}` declared in more than one unit")]
+    ConflictingType {
+        /// The hash of the conflicting type.
+        hash: Hash,
+    },
+}
+
+/// Link the given units into one.
+pub(super) fn link(units: Vec<Unit>) -> Result<Unit, LinkError> {
+    let mut linked = Unit::default();
+    let mut debug = Some(Box::new(DebugInfo::default()));
+
+    let mut strings = HashMap::<String, usize>::new();
+    let mut bytes = HashMap::<Vec<u8>, usize>::new();
+    let mut object_keys = HashMap::<Box<[String]>, usize>::new();
+
+    for unit in units {
+        let base = linked.instructions.len();
+        let instance_call_base = linked.instance_call_slots;
+
+        let string_slots = unit
+            .static_strings
+            .into_iter()
+            .map(|s| intern_string(&mut linked.static_strings, &mut strings, s))
+            .collect::<Vec<_>>();
+
+        let bytes_slots = unit
+            .static_bytes
+            .into_iter()
+            .map(|b| intern(&mut linked.static_bytes, &mut bytes, b))
+            .collect::<Vec<_>>();
+
+        let object_keys_slots = unit
+            .static_object_keys
+            .into_iter()
+            .map(|k| intern(&mut linked.static_object_keys, &mut object_keys, k))
+            .collect::<Vec<_>>();
+
+        for mut inst in unit.instructions {
+            // NB: jumps are relative, so only slots need to be relocated.
+            match &mut inst {
+                Inst::ObjectSlotIndexGet { slot }
+                | Inst::ObjectSlotIndexGetAt { slot, .. }
+                | Inst::String { slot }
+                | Inst::BigInt { slot }
+                | Inst::EqStaticString { slot } => {
+                    *slot = relocate(&string_slots, *slot);
+                }
+                Inst::Bytes { slot } => {
+                    *slot = relocate(&bytes_slots, *slot);
+                }
+                Inst::Object { slot }
+                | Inst::TypedObject { slot, .. }
+                | Inst::VariantObject { slot, .. }
+                | Inst::MatchObject { slot, .. } => {
+                    *slot = relocate(&object_keys_slots, *slot);
+                }
+                Inst::CallInstance { slot, .. } => {
+                    *slot += instance_call_base;
+                }
+                _ => (),
+            }
+
+            linked.instructions.push(inst);
+        }
+
+        linked.instance_call_slots += unit.instance_call_slots;
+
+        for (hash, mut unit_fn) in unit.functions {
+            if let UnitFn::Offset { offset, .. } = &mut unit_fn {
+                *offset += base;
+            }
+
+            if linked.functions.insert(hash, unit_fn).is_some() {
+                return Err(LinkError::ConflictingFunction { hash });
+            }
+        }
+
+        for (hash, info) in unit.types {
+            if linked.types.insert(hash, info).is_some() {
+                return Err(LinkError::ConflictingType { hash });
+            }
+        }
+
+        // NB: debug information is only preserved if every unit has it.
+        debug = match (debug, unit.debug) {
+            (Some(mut debug), Some(unit_debug)) => {
+                let unit_debug = *unit_debug;
+                debug.instructions.extend(unit_debug.instructions);
+                debug.functions.extend(unit_debug.functions);
+                debug.functions_rev.extend(
+                    unit_debug
+                        .functions_rev
+                        .into_iter()
+                        .map(|(ip, hash)| (ip + base, hash)),
+                );
+                Some(debug)
+            }
+            _ => None,
+        };
+    }
+
+    linked.debug = debug;
+    linked.index_function_ranges();
+    Ok(linked)
+}
+
+/// Intern a static string, reusing the slot of an existing string with the
+/// same content.
+fn intern_string(
+    pool: &mut Vec<Arc<StaticString>>,
+    existing: &mut HashMap<String, usize>,
+    string: Arc<StaticString>,
+) -> usize {
+    if let Some(slot) = existing.get(string.as_str()) {
+        return *slot;
+    }
+
+    let slot = pool.len();
+    existing.insert((**string).clone(), slot);
+    pool.push(string);
+    slot
+}
+
+/// Intern a value, reusing the slot of an existing equal value.
+fn intern<T>(pool: &mut Vec<T>, existing: &mut HashMap<T, usize>, value: T) -> usize
+where
+    T: Clone + Eq + std::hash::Hash,
+{
+    if let Some(slot) = existing.get(&value) {
+        return *slot;
+    }
+
+    let slot = pool.len();
+    existing.insert(value.clone(), slot);
+    pool.push(value);
+    slot
+}
+
+/// Relocate a slot. Slots which are out of bounds are relocated to a slot
+/// which doesn't exist, so that they still error when used.
+fn relocate(slots: &[usize], slot: usize) -> usize {
+    slots.get(slot).copied().unwrap_or(usize::MAX)
+}
@@ -0,0 +1,400 @@
+//! Optimization passes for units.
+//!
+//! All passes operate on the final instructions of the unit, so care has to be
+//! taken to adjust the relative offsets of jumps, the offsets of functions,
+//! and debug information when instructions are removed.
+
+use crate::collections::HashSet;
+use crate::unit::{Unit, UnitFn};
+use crate::Inst;
+use std::mem;
+
+/// Remove instructions which push a value onto the stack that is immediately
+/// popped. Returns `true` if any instructions were removed.
+pub(super) fn elide_redundant_pops(unit: &mut Unit) -> bool {
+    let entries = match entry_points(unit) {
+        Some(entries) => entries,
+        None => return false,
+    };
+
+    let len = unit.instructions.len();
+    let mut keep = vec![true; len];
+    let mut ip = 0;
+
+    while ip + 1 < len {
+        // NB: the pop must not be entered from anywhere else, since it would
+        // then be popping a different value.
+        if is_pure_push(&unit.instructions[ip])
+            && matches!(unit.instructions[ip + 1], Inst::Pop)
+            && !entries.contains(&(ip + 1))
+        {
+            keep[ip] = false;
+            keep[ip + 1] = false;
+            ip += 2;
+            continue;
+        }
+
+        ip += 1;
+    }
+
+    retain(unit, &keep)
+}
+
+/// Fuse a `copy` directly followed by a `replace` into a single `move`, which
+/// copies a value from one frame offset into another without going through
+/// the stack. Returns `true` if anything was fused.
+pub(super) fn fuse_moves(unit: &mut Unit) -> bool {
+    let entries = match entry_points(unit) {
+        Some(entries) => entries,
+        None => return false,
+    };
+
+    let len = unit.instructions.len();
+    let mut keep = vec![true; len];
+    let mut ip = 0;
+
+    while ip + 1 < len {
+        // NB: the replace must not be entered from anywhere else, since it
+        // would then be replacing with a different value.
+        if let (Inst::Copy { offset: from }, Inst::Replace { offset: to }) =
+            (unit.instructions[ip], unit.instructions[ip + 1])
+        {
+            if !entries.contains(&(ip + 1)) {
+                unit.instructions[ip] = Inst::Move { from, to };
+                keep[ip + 1] = false;
+                ip += 2;
+                continue;
+            }
+        }
+
+        ip += 1;
+    }
+
+    retain(unit, &keep)
+}
+
+/// Remove unconditional jumps to the next instruction, which have no effect.
+/// Returns `true` if any instructions were removed.
+pub(super) fn remove_nop_jumps(unit: &mut Unit) -> bool {
+    let keep = unit
+        .instructions
+        .iter()
+        .map(|inst| !matches!(inst, Inst::Jump { offset: 0 }))
+        .collect::<Vec<_>>();
+
+    retain(unit, &keep)
+}
+
+/// Remove instructions which can never be executed, since they follow an
+/// instruction that never continues to the next one, like a `return`, and
+/// can't be entered from anywhere else. Returns `true` if any instructions
+/// were removed.
+pub(super) fn eliminate_dead_code(unit: &mut Unit) -> bool {
+    let entries = match entry_points(unit) {
+        Some(entries) => entries,
+        None => return false,
+    };
+
+    let mut keep = Vec::with_capacity(unit.instructions.len());
+    let mut reachable = true;
+
+    for (ip, inst) in unit.instructions.iter().enumerate() {
+        reachable = reachable || entries.contains(&ip);
+        keep.push(reachable);
+
+        if reachable && is_terminator(inst) {
+            reachable = false;
+        }
+    }
+
+    retain(unit, &keep)
+}
+
+/// Test if the instruction never continues to the next instruction.
+fn is_terminator(inst: &Inst) -> bool {
+    matches!(
+        inst,
+        Inst::Return | Inst::ReturnUnit | Inst::Jump { .. } | Inst::Panic { .. }
+    )
+}
+
+/// Fold operations whose operands are constants into a single constant, like
+/// `integer 2`, `integer 3`, `add` into `integer 5`. Returns `true` if anything
+/// was folded.
+///
+/// Operations which would fail at runtime, like an addition that overflows,
+/// are left as-is so that they still raise an error when executed.
+pub(super) fn fold_constants(unit: &mut Unit) -> bool {
+    let entries = match entry_points(unit) {
+        Some(entries) => entries,
+        None => return false,
+    };
+
+    let len = unit.instructions.len();
+    let mut keep = vec![true; len];
+    let mut ip = 0;
+
+    while ip < len {
+        // NB: only the first instruction in a folded sequence may be entered
+        // from somewhere else, since it is the one replaced.
+        if ip + 2 < len && !entries.contains(&(ip + 1)) && !entries.contains(&(ip + 2)) {
+            let insts = &unit.instructions[ip..ip + 3];
+
+            if let Some(folded) = fold_binary(insts[0], insts[1], insts[2]) {
+                unit.instructions[ip] = folded;
+                keep[ip + 1] = false;
+                keep[ip + 2] = false;
+                ip += 3;
+                continue;
+            }
+        }
+
+        if ip + 1 < len && !entries.contains(&(ip + 1)) {
+            let insts = &unit.instructions[ip..ip + 2];
+
+            if let Some(folded) = fold_unary(insts[0], insts[1]) {
+                unit.instructions[ip] = folded;
+                keep[ip + 1] = false;
+                ip += 2;
+                continue;
+            }
+        }
+
+        ip += 1;
+    }
+
+    retain(unit, &keep)
+}
+
+/// Fold a binary operation on two constants, mirroring the behavior of the
+/// virtual machine.
+fn fold_binary(a: Inst, b: Inst, op: Inst) -> Option<Inst> {
+    use std::convert::TryFrom as _;
+
+    Some(match (a, b) {
+        (Inst::Integer { number: a }, Inst::Integer { number: b }) => {
+            let number = match op {
+                Inst::Add => a.checked_add(b)?,
+                Inst::Sub => a.checked_sub(b)?,
+                Inst::Mul => a.checked_mul(b)?,
+                Inst::Div => a.checked_div(b)?,
+                Inst::Rem => a.checked_rem(b)?,
+                Inst::BitAnd => a & b,
+                Inst::BitXor => a ^ b,
+                Inst::BitOr => a | b,
+                Inst::Shl => a.checked_shl(u32::try_from(b).ok()?)?,
+                Inst::Shr => a.checked_shr(u32::try_from(b).ok()?)?,
+                op => return fold_comparison(a, b, op),
+            };
+
+            Inst::Integer { number }
+        }
+        (Inst::Float { number: a }, Inst::Float { number: b }) => {
+            let number = match op {
+                Inst::Add => a + b,
+                Inst::Sub => a - b,
+                Inst::Mul => a * b,
+                Inst::Div => a / b,
+                Inst::Rem => a % b,
+                op => return fold_comparison(a, b, op),
+            };
+
+            Inst::Float { number }
+        }
+        (Inst::Bool { value: a }, Inst::Bool { value: b }) => {
+            let value = match op {
+                Inst::And => a && b,
+                Inst::Or => a || b,
+                Inst::Eq => a == b,
+                Inst::Neq => a != b,
+                _ => return None,
+            };
+
+            Inst::Bool { value }
+        }
+        _ => return None,
+    })
+}
+
+/// Fold a comparison between two constants.
+fn fold_comparison<T>(a: T, b: T, op: Inst) -> Option<Inst>
+where
+    T: PartialOrd,
+{
+    let value = match op {
+        Inst::Lt => a < b,
+        Inst::Gt => a > b,
+        Inst::Lte => a <= b,
+        Inst::Gte => a >= b,
+        Inst::Eq => a == b,
+        Inst::Neq => a != b,
+        _ => return None,
+    };
+
+    Some(Inst::Bool { value })
+}
+
+/// Fold a unary operation on a constant, mirroring the behavior of the
+/// virtual machine.
+fn fold_unary(a: Inst, op: Inst) -> Option<Inst> {
+    Some(match (a, op) {
+        (Inst::Bool { value }, Inst::Not) => Inst::Bool { value: !value },
+        (Inst::Integer { number }, Inst::Not) => Inst::Integer { number: !number },
+        (Inst::Integer { number }, Inst::Neg) => Inst::Integer {
+            number: number.checked_neg()?,
+        },
+        (Inst::Float { number }, Inst::Neg) => Inst::Float { number: -number },
+        _ => return None,
+    })
+}
+
+/// Test if the instruction only pushes a single value onto the stack without
+/// any other side effects.
+fn is_pure_push(inst: &Inst) -> bool {
+    matches!(
+        inst,
+        Inst::Copy { .. }
+            | Inst::Dup
+            | Inst::Unit
+            | Inst::Bool { .. }
+            | Inst::Integer { .. }
+            | Inst::Float { .. }
+            | Inst::Char { .. }
+            | Inst::Byte { .. }
+            | Inst::String { .. }
+    )
+}
+
+/// Get the absolute instruction pointer that the instruction at `ip` might
+/// jump to, if it is a jump.
+fn jump_target(ip: usize, inst: &Inst) -> Option<isize> {
+    let offset = match *inst {
+        Inst::Jump { offset }
+        | Inst::JumpIf { offset }
+        | Inst::JumpIfNot { offset }
+        | Inst::JumpIfBranch { offset, .. }
+        | Inst::PopAndJumpIfNot { offset, .. } => offset,
+        _ => return None,
+    };
+
+    // NB: the instruction pointer is advanced after the jump.
+    Some((ip as isize).wrapping_add(offset).wrapping_add(1))
+}
+
+/// Access the relative jump offset of the instruction, if it is a jump.
+fn jump_offset_mut(inst: &mut Inst) -> Option<&mut isize> {
+    match inst {
+        Inst::Jump { offset }
+        | Inst::JumpIf { offset }
+        | Inst::JumpIfNot { offset }
+        | Inst::JumpIfBranch { offset, .. }
+        | Inst::PopAndJumpIfNot { offset, .. } => Some(offset),
+        _ => None,
+    }
+}
+
+/// Collect every instruction pointer which can be entered from somewhere
+/// other than the instruction preceding it, like through a jump or a function
+/// call.
+///
+/// Returns `None` if a jump is out of bounds, in which case the unit can't be
+/// safely optimized.
+fn entry_points(unit: &Unit) -> Option<HashSet<usize>> {
+    let len = unit.instructions.len();
+    let mut entries = HashSet::new();
+
+    for (ip, inst) in unit.instructions.iter().enumerate() {
+        if let Some(target) = jump_target(ip, inst) {
+            if target < 0 || target as usize > len {
+                return None;
+            }
+
+            entries.insert(target as usize);
+        }
+    }
+
+    for unit_fn in unit.functions.values() {
+        if let UnitFn::Offset { offset, .. } = unit_fn {
+            if *offset > len {
+                return None;
+            }
+
+            entries.insert(*offset);
+        }
+    }
+
+    Some(entries)
+}
+
+/// Retain only the instructions marked with `keep`, adjusting jumps, function
+/// offsets, and debug information. Returns `true` if any instructions were
+/// removed.
+///
+/// Any jump or function pointing to a removed instruction will point to the
+/// next retained instruction instead.
+fn retain(unit: &mut Unit, keep: &[bool]) -> bool {
+    debug_assert_eq!(keep.len(), unit.instructions.len());
+
+    if keep.iter().all(|keep| *keep) {
+        return false;
+    }
+
+    // Map from old instruction pointers to new ones. Includes the pointer
+    // right after the last instruction.
+    let mut new_ips = Vec::with_capacity(keep.len() + 1);
+    let mut count = 0;
+
+    for keep in keep {
+        new_ips.push(count);
+
+        if *keep {
+            count += 1;
+        }
+    }
+
+    new_ips.push(count);
+
+    let old = mem::take(&mut unit.instructions);
+    unit.instructions.reserve(count);
+
+    for (ip, mut inst) in old.into_iter().enumerate() {
+        if !keep[ip] {
+            continue;
+        }
+
+        if let Some(target) = jump_target(ip, &inst) {
+            let target = new_ips[target as usize] as isize;
+            let base = new_ips[ip] as isize;
+
+            if let Some(offset) = jump_offset_mut(&mut inst) {
+                *offset = target - base - 1;
+            }
+        }
+
+        unit.instructions.push(inst);
+    }
+
+    for unit_fn in unit.functions.values_mut() {
+        if let UnitFn::Offset { offset, .. } = unit_fn {
+            *offset = new_ips[*offset];
+        }
+    }
+
+    if let Some(debug) = &mut unit.debug {
+        let mut ip = 0;
+
+        debug.instructions.retain(|_| {
+            let retain = keep.get(ip).copied().unwrap_or(true);
+            ip += 1;
+            retain
+        });
+
+        debug.functions_rev = mem::take(&mut debug.functions_rev)
+            .into_iter()
+            .map(|(ip, hash)| (new_ips.get(ip).copied().unwrap_or(ip), hash))
+            .collect();
+    }
+
+    unit.index_function_ranges();
+    true
+}
@@ -1,14 +1,27 @@
 use crate::{
-    Any, Bytes, Function, Future, Generator, GeneratorState, Hash, OwnedMut, OwnedRef, RawOwnedMut,
-    RawOwnedRef, Shared, StaticString, Stream, Tuple, Type, TypeInfo, VmError,
+    Any, BigInt, Bytes, Context, FloatFormat, FromValue, Function, Future, Generator,
+    GeneratorState, Hash, HashKey, IntoHash, IterationOrder, OwnedMut, OwnedRef, Range,
+    RawOwnedMut, RawOwnedRef, Shared, Stack, StaticString, Stream, Tuple, Type, TypeInfo, VmError,
+    VmErrorKind,
 };
 use std::any;
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash;
 use std::sync::Arc;
 
+/// The maximum depth to which values may be nested when they are traversed
+/// recursively, like when they are saved or copied, so that a deeply nested
+/// value can't overflow the stack.
+pub(crate) const MAX_VALUE_DEPTH: usize = 256;
+
 /// The type of an object.
 pub type Object<T> = crate::collections::HashMap<String, T>;
 
+/// The type of a map, which unlike an [Object] can be keyed by any value
+/// which can be converted into a [HashKey].
+pub type Map<T> = crate::collections::HashMap<HashKey, T>;
+
 /// A tuple with a well-defined type.
 #[derive(Debug)]
 pub struct TypedTuple {
@@ -92,6 +105,8 @@ pub enum Value {
     Integer(i64),
     /// A float.
     Float(f64),
+    /// An integer which doesn't fit in an [Integer][Value::Integer].
+    BigInt(Arc<BigInt>),
     /// A type hash. Describes a type in the virtual machine.
     Type(Hash),
     /// A static string.
@@ -111,8 +126,12 @@ pub enum Value {
     Vec(Shared<Vec<Value>>),
     /// A tuple.
     Tuple(Shared<Tuple>),
+    /// A range of integers.
+    Range(Shared<Range>),
     /// An object.
     Object(Shared<Object<Value>>),
+    /// A map keyed by hashable values.
+    Map(Shared<Map<Value>>),
     /// A stored future.
     Future(Shared<Future>),
     /// A Stream.
@@ -221,6 +240,15 @@ impl Value {
         }
     }
 
+    /// Try to coerce value into a big integer.
+    #[inline]
+    pub fn into_big_int(self) -> Result<Arc<BigInt>, VmError> {
+        match self {
+            Self::BigInt(big_int) => Ok(big_int),
+            actual => Err(VmError::expected::<BigInt>(actual.type_info()?)),
+        }
+    }
+
     /// Try to coerce value into a result.
     #[inline]
     pub fn into_result(self) -> Result<Shared<Result<Value, Value>>, VmError> {
@@ -232,6 +260,15 @@ impl Value {
         }
     }
 
+    /// Try to coerce value into a range.
+    #[inline]
+    pub fn into_range(self) -> Result<Shared<Range>, VmError> {
+        match self {
+            Self::Range(range) => Ok(range),
+            actual => Err(VmError::expected::<Range>(actual.type_info()?)),
+        }
+    }
+
     /// Try to coerce value into a future.
     #[inline]
     pub fn into_future(self) -> Result<Shared<Future>, VmError> {
@@ -322,6 +359,113 @@ impl Value {
         }
     }
 
+    /// Try to coerce value into a map.
+    #[inline]
+    pub fn into_map(self) -> Result<Shared<Map<Value>>, VmError> {
+        match self {
+            Self::Map(map) => Ok(map),
+            actual => Err(VmError::expected::<Map<Value>>(actual.type_info()?)),
+        }
+    }
+
+    /// Feed the value into the given hasher.
+    ///
+    /// Values which are equal are hashed the same. This errors with
+    /// [UnsupportedHashKey][VmErrorKind::UnsupportedHashKey] if the value
+    /// can't be hashed, see [HashKey] for which values can.
+    pub fn hash_value(&self, state: &mut impl hash::Hasher) -> Result<(), VmError> {
+        hash::Hash::hash(&HashKey::from_value(self)?, state);
+        Ok(())
+    }
+
+    /// Format the value for display, like it would be when used in a template
+    /// string.
+    ///
+    /// Strings and numbers are formatted directly, floats using the default
+    /// [FloatFormat]. Any other value is formatted by calling its
+    /// [STRING_DISPLAY][crate::STRING_DISPLAY] instance function registered in
+    /// the given context, erroring with
+    /// [MissingProtocol][VmErrorKind::MissingProtocol] if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::{Context, Value};
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let context = Context::with_default_modules()?;
+    /// assert_eq!(Value::from(42i64).format_display(&context)?, "42");
+    /// assert!(Value::Unit.format_display(&context).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn format_display(&self, context: &Context) -> Result<String, VmError> {
+        let mut buf = String::new();
+
+        if self.format_primitive_into(FloatFormat::default(), &mut buf)? {
+            return Ok(buf);
+        }
+
+        let name = crate::STRING_DISPLAY.into_hash();
+        let hash = Hash::instance_function(self.value_type()?, name);
+
+        let handler = match crate::Vm::lookup_instance_handler(context, hash, name) {
+            Some(handler) => handler,
+            None => {
+                return Err(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: crate::STRING_DISPLAY,
+                    actual: self.type_info()?,
+                }));
+            }
+        };
+
+        let b = Shared::new(buf);
+        let mut stack = Stack::with_capacity(2);
+        stack.push(self.clone());
+        stack.push(Value::String(b.clone()));
+        handler(&mut stack, 2)?;
+
+        if let Err(fmt::Error) = fmt::Result::from_value(stack.pop()?)? {
+            return Err(VmError::from(VmErrorKind::FormatError));
+        }
+
+        Ok(b.take()?)
+    }
+
+    /// Append strings and numbers to the given buffer, returning `false`
+    /// without touching the buffer for any other value.
+    pub(crate) fn format_primitive_into(
+        &self,
+        float_format: FloatFormat,
+        buf: &mut String,
+    ) -> Result<bool, VmError> {
+        match self {
+            Self::String(string) => {
+                buf.push_str(&*string.borrow_ref()?);
+            }
+            Self::StaticString(string) => {
+                buf.push_str(string.as_ref());
+            }
+            Self::Integer(integer) => {
+                let mut buffer = itoa::Buffer::new();
+                buf.push_str(buffer.format(*integer));
+            }
+            Self::Float(float) => {
+                float_format.format_into(*float, buf);
+            }
+            Self::BigInt(big_int) => {
+                use std::fmt::Write as _;
+
+                if write!(buf, "{}", big_int).is_err() {
+                    return Err(VmError::from(VmErrorKind::FormatError));
+                }
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
     /// Try to coerce value into a function pointer.
     #[inline]
     pub fn into_function(self) -> Result<Shared<Function>, VmError> {
@@ -397,12 +541,15 @@ impl Value {
             Self::Char(..) => Type::StaticType(crate::CHAR_TYPE),
             Self::Integer(..) => Type::StaticType(crate::INTEGER_TYPE),
             Self::Float(..) => Type::StaticType(crate::FLOAT_TYPE),
+            Self::BigInt(..) => Type::StaticType(crate::BIG_INT_TYPE),
             Self::StaticString(..) => Type::StaticType(crate::STRING_TYPE),
             Self::String(..) => Type::StaticType(crate::STRING_TYPE),
             Self::Bytes(..) => Type::StaticType(crate::BYTES_TYPE),
             Self::Vec(..) => Type::StaticType(crate::VEC_TYPE),
             Self::Tuple(..) => Type::StaticType(crate::TUPLE_TYPE),
+            Self::Range(..) => Type::StaticType(crate::RANGE_TYPE),
             Self::Object(..) => Type::StaticType(crate::OBJECT_TYPE),
+            Self::Map(..) => Type::StaticType(crate::MAP_TYPE),
             Self::Future(..) => Type::StaticType(crate::FUTURE_TYPE),
             Self::Stream(..) => Type::StaticType(crate::STREAM_TYPE),
             Self::Generator(..) => Type::StaticType(crate::GENERATOR_TYPE),
@@ -434,12 +581,15 @@ impl Value {
             Self::Char(..) => TypeInfo::StaticType(crate::CHAR_TYPE),
             Self::Integer(..) => TypeInfo::StaticType(crate::INTEGER_TYPE),
             Self::Float(..) => TypeInfo::StaticType(crate::FLOAT_TYPE),
+            Self::BigInt(..) => TypeInfo::StaticType(crate::BIG_INT_TYPE),
             Self::StaticString(..) => TypeInfo::StaticType(crate::STRING_TYPE),
             Self::String(..) => TypeInfo::StaticType(crate::STRING_TYPE),
             Self::Bytes(..) => TypeInfo::StaticType(crate::BYTES_TYPE),
             Self::Vec(..) => TypeInfo::StaticType(crate::VEC_TYPE),
             Self::Tuple(..) => TypeInfo::StaticType(crate::TUPLE_TYPE),
+            Self::Range(..) => TypeInfo::StaticType(crate::RANGE_TYPE),
             Self::Object(..) => TypeInfo::StaticType(crate::OBJECT_TYPE),
+            Self::Map(..) => TypeInfo::StaticType(crate::MAP_TYPE),
             Self::Future(..) => TypeInfo::StaticType(crate::FUTURE_TYPE),
             Self::Stream(..) => TypeInfo::StaticType(crate::STREAM_TYPE),
             Self::Generator(..) => TypeInfo::StaticType(crate::GENERATOR_TYPE),
@@ -459,50 +609,86 @@ impl Value {
     /// Optimized function to test if two value pointers are deeply equal to
     /// each other.
     ///
-    /// This is the basis for the eq operation (`==`).
+    /// External values are compared like with [value_eq][Self::value_eq],
+    /// using the context of the virtual machine currently running on this
+    /// thread. Outside of a virtual machine they are only equal if they are
+    /// the same value.
     pub(crate) fn value_ptr_eq(a: &Value, b: &Value) -> Result<bool, VmError> {
+        let context = Context::current();
+        Self::value_eq_with(context.as_deref(), a, b)
+    }
+
+    /// Test if two values are deeply equal to each other, comparing external
+    /// values using the [EQ][crate::EQ] protocol registered in the given
+    /// context.
+    ///
+    /// This is the basis for the eq operation (`==`).
+    pub(crate) fn value_eq(context: &Context, a: &Value, b: &Value) -> Result<bool, VmError> {
+        Self::value_eq_with(Some(context), a, b)
+    }
+
+    fn value_eq_with(context: Option<&Context>, a: &Value, b: &Value) -> Result<bool, VmError> {
         Ok(match (a, b) {
             (Self::Unit, Self::Unit) => true,
             (Self::Char(a), Self::Char(b)) => a == b,
             (Self::Bool(a), Self::Bool(b)) => a == b,
             (Self::Integer(a), Self::Integer(b)) => a == b,
             (Self::Float(a), Self::Float(b)) => a == b,
+            (Self::BigInt(..), _) | (_, Self::BigInt(..)) => {
+                Self::big_int_cmp(a, b) == Some(Ordering::Equal)
+            }
             (Self::Vec(a), Self::Vec(b)) => {
+                Self::slice_eq_with(context, &*a.borrow_ref()?, &*b.borrow_ref()?)?
+            }
+            (Self::Tuple(a), Self::Tuple(b)) => {
+                Self::slice_eq_with(context, &*a.borrow_ref()?, &*b.borrow_ref()?)?
+            }
+            (Self::Range(a), Self::Range(b)) => *a.borrow_ref()? == *b.borrow_ref()?,
+            (Self::TypedTuple(a), Self::TypedTuple(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
 
-                if a.len() != b.len() {
+                if a.hash != b.hash {
                     return Ok(false);
                 }
 
-                for (a, b) in a.iter().zip(b.iter()) {
-                    if !Self::value_ptr_eq(a, b)? {
-                        return Ok(false);
-                    }
+                Self::slice_eq_with(context, &a.tuple, &b.tuple)?
+            }
+            (Self::TupleVariant(a), Self::TupleVariant(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
+
+                if a.hash != b.hash {
+                    return Ok(false);
                 }
 
-                true
+                Self::slice_eq_with(context, &a.tuple, &b.tuple)?
             }
             (Self::Object(a), Self::Object(b)) => {
+                Self::object_eq_with(context, &*a.borrow_ref()?, &*b.borrow_ref()?)?
+            }
+            (Self::Map(a), Self::Map(b)) => {
+                Self::map_eq_with(context, &*a.borrow_ref()?, &*b.borrow_ref()?)?
+            }
+            (Self::TypedObject(a), Self::TypedObject(b)) => {
                 let a = a.borrow_ref()?;
                 let b = b.borrow_ref()?;
 
-                if a.len() != b.len() {
+                if a.hash != b.hash {
                     return Ok(false);
                 }
 
-                for (key, a) in a.iter() {
-                    let b = match b.get(key) {
-                        Some(b) => b,
-                        None => return Ok(false),
-                    };
+                Self::object_eq_with(context, &a.object, &b.object)?
+            }
+            (Self::VariantObject(a), Self::VariantObject(b)) => {
+                let a = a.borrow_ref()?;
+                let b = b.borrow_ref()?;
 
-                    if !Self::value_ptr_eq(a, b)? {
-                        return Ok(false);
-                    }
+                if a.hash != b.hash {
+                    return Ok(false);
                 }
 
-                true
+                Self::object_eq_with(context, &a.object, &b.object)?
             }
             (Self::String(a), Self::String(b)) => {
                 let a = a.borrow_ref()?;
@@ -518,13 +704,221 @@ impl Value {
                 *a == ***b
             }
             // fast string comparison: exact string slot.
-            (Self::StaticString(a), Self::StaticString(b)) => ***a == ***b,
-            // fast external comparison by slot.
-            // TODO: implement ptr equals.
-            // (Self::Any(a), Self::Any(b)) => a == b,
+            (Self::StaticString(a), Self::StaticString(b)) => {
+                Arc::ptr_eq(a, b) || (a.hash() == b.hash() && ***a == ***b)
+            }
+            (Self::Any(a), Self::Any(b)) => {
+                if Shared::ptr_eq(a, b) {
+                    return Ok(true);
+                }
+
+                let context = match context {
+                    Some(context) => context,
+                    None => return Ok(false),
+                };
+
+                let type_hash = a.borrow_ref()?.type_hash();
+
+                if type_hash != b.borrow_ref()?.type_hash() {
+                    return Ok(false);
+                }
+
+                let hash = Hash::instance_function(Type::Hash(type_hash), crate::EQ);
+
+                let handler = match context.lookup(hash) {
+                    Some(handler) => handler,
+                    None => return Ok(false),
+                };
+
+                let mut stack = Stack::with_capacity(2);
+                stack.push(Value::Any(a.clone()));
+                stack.push(Value::Any(b.clone()));
+                handler(&mut stack, 2)?;
+                bool::from_value(stack.pop()?)?
+            }
             _ => false,
         })
     }
+
+    /// Numerically compare two values if one of them is a big integer and the
+    /// other is an integer of any size.
+    pub(crate) fn big_int_cmp(a: &Value, b: &Value) -> Option<Ordering> {
+        Some(match (a, b) {
+            (Self::BigInt(a), Self::BigInt(b)) => (**a).cmp(&**b),
+            (Self::BigInt(a), Self::Integer(b)) => (**a).cmp(&BigInt::from(*b)),
+            (Self::Integer(a), Self::BigInt(b)) => BigInt::from(*a).cmp(&**b),
+            _ => return None,
+        })
+    }
+
+    /// Compare two values, comparing external values using the
+    /// [CMP][crate::CMP] protocol registered in the given context.
+    ///
+    /// This is the basis for sorting.
+    pub(crate) fn value_cmp(
+        context: Option<&Context>,
+        a: &Value,
+        b: &Value,
+    ) -> Result<Ordering, VmError> {
+        if let Some(ordering) = Self::big_int_cmp(a, b) {
+            return Ok(ordering);
+        }
+
+        let ordering = match (a, b) {
+            (Self::Unit, Self::Unit) => Some(Ordering::Equal),
+            (Self::Bool(a), Self::Bool(b)) => Some(a.cmp(b)),
+            (Self::Byte(a), Self::Byte(b)) => Some(a.cmp(b)),
+            (Self::Char(a), Self::Char(b)) => Some(a.cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::String(a), Self::String(b)) => Some(a.borrow_ref()?.cmp(&*b.borrow_ref()?)),
+            (Self::StaticString(a), Self::String(b)) => Some((***a).cmp(&*b.borrow_ref()?)),
+            (Self::String(a), Self::StaticString(b)) => Some(a.borrow_ref()?.cmp(&***b)),
+            (Self::StaticString(a), Self::StaticString(b)) => Some((***a).cmp(&***b)),
+            (Self::Vec(a), Self::Vec(b)) => Some(Self::slice_cmp(
+                context,
+                &a.borrow_ref()?,
+                &b.borrow_ref()?,
+            )?),
+            (Self::Tuple(a), Self::Tuple(b)) => Some(Self::slice_cmp(
+                context,
+                &a.borrow_ref()?,
+                &b.borrow_ref()?,
+            )?),
+            (Self::Any(a), Self::Any(b)) => {
+                let ordering = match context {
+                    Some(context) => Self::any_cmp(context, a, b)?,
+                    None => None,
+                };
+
+                match ordering {
+                    Some(ordering) => Some(ordering),
+                    None if a.borrow_ref()?.type_hash() == b.borrow_ref()?.type_hash() => {
+                        return Err(VmError::from(VmErrorKind::MissingProtocol {
+                            protocol: crate::CMP,
+                            actual: Self::Any(a.clone()).type_info()?,
+                        }));
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        };
+
+        match ordering {
+            Some(ordering) => Ok(ordering),
+            None => Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                op: "cmp",
+                lhs: a.type_info()?,
+                rhs: b.type_info()?,
+            })),
+        }
+    }
+
+    /// Compare two external values using the [CMP][crate::CMP] protocol
+    /// registered in the given context.
+    ///
+    /// Returns `None` if the values are of different types, or if their type
+    /// doesn't implement the protocol.
+    pub(crate) fn any_cmp(
+        context: &Context,
+        a: &Shared<Any>,
+        b: &Shared<Any>,
+    ) -> Result<Option<Ordering>, VmError> {
+        let type_hash = a.borrow_ref()?.type_hash();
+
+        if type_hash != b.borrow_ref()?.type_hash() {
+            return Ok(None);
+        }
+
+        let hash = Hash::instance_function(Type::Hash(type_hash), crate::CMP);
+
+        let handler = match context.lookup(hash) {
+            Some(handler) => handler,
+            None => return Ok(None),
+        };
+
+        let mut stack = Stack::with_capacity(2);
+        stack.push(Value::Any(a.clone()));
+        stack.push(Value::Any(b.clone()));
+        handler(&mut stack, 2)?;
+        Ok(Some(i64::from_value(stack.pop()?)?.cmp(&0)))
+    }
+
+    /// Lexicographically compare two slices of values.
+    fn slice_cmp(context: Option<&Context>, a: &[Value], b: &[Value]) -> Result<Ordering, VmError> {
+        for (a, b) in a.iter().zip(b.iter()) {
+            match Self::value_cmp(context, a, b)? {
+                Ordering::Equal => (),
+                ordering => return Ok(ordering),
+            }
+        }
+
+        Ok(a.len().cmp(&b.len()))
+    }
+
+    /// Test if two slices of values are elementwise equal.
+    fn slice_eq_with(context: Option<&Context>, a: &[Value], b: &[Value]) -> Result<bool, VmError> {
+        if a.len() != b.len() {
+            return Ok(false);
+        }
+
+        for (a, b) in a.iter().zip(b.iter()) {
+            if !Self::value_eq_with(context, a, b)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Test if two objects have equal fields.
+    fn object_eq_with(
+        context: Option<&Context>,
+        a: &Object<Value>,
+        b: &Object<Value>,
+    ) -> Result<bool, VmError> {
+        if a.len() != b.len() {
+            return Ok(false);
+        }
+
+        for (key, a) in a.iter() {
+            let b = match b.get(key) {
+                Some(b) => b,
+                None => return Ok(false),
+            };
+
+            if !Self::value_eq_with(context, a, b)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Test if two maps have equal entries.
+    fn map_eq_with(
+        context: Option<&Context>,
+        a: &Map<Value>,
+        b: &Map<Value>,
+    ) -> Result<bool, VmError> {
+        if a.len() != b.len() {
+            return Ok(false);
+        }
+
+        for (key, a) in a.iter() {
+            let b = match b.get(key) {
+                Some(b) => b,
+                None => return Ok(false),
+            };
+
+            if !Self::value_eq_with(context, a, b)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
 }
 
 impl fmt::Debug for Value {
@@ -546,7 +940,12 @@ impl fmt::Debug for Value {
                 write!(f, "{:?}", value)?;
             }
             Value::Float(value) => {
-                write!(f, "{:?}", value)?;
+                let mut buf = String::new();
+                FloatFormat::current().format_into(*value, &mut buf);
+                f.write_str(&buf)?;
+            }
+            Value::BigInt(value) => {
+                write!(f, "{}", value)?;
             }
             Value::Type(value) => {
                 write!(f, "Type({})", value)?;
@@ -566,9 +965,29 @@ impl fmt::Debug for Value {
             Value::Tuple(value) => {
                 write!(f, "{:?}", value)?;
             }
-            Value::Object(value) => {
+            Value::Range(value) => {
                 write!(f, "{:?}", value)?;
             }
+            Value::Object(value) => match value.borrow_ref() {
+                Ok(object) if IterationOrder::current() == IterationOrder::Sorted => {
+                    let mut entries = object.iter().collect::<Vec<_>>();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    f.debug_map().entries(entries).finish()?;
+                }
+                _ => {
+                    write!(f, "{:?}", value)?;
+                }
+            },
+            Value::Map(value) => match value.borrow_ref() {
+                Ok(map) if IterationOrder::current() == IterationOrder::Sorted => {
+                    let mut entries = map.iter().collect::<Vec<_>>();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+                    f.debug_map().entries(entries).finish()?;
+                }
+                _ => {
+                    write!(f, "{:?}", value)?;
+                }
+            },
             Value::Future(value) => {
                 write!(f, "{:?}", value)?;
             }
@@ -633,6 +1052,13 @@ impl_from!(char, Char);
 impl_from!(i64, Integer);
 impl_from!(f64, Float);
 impl_from!(Arc<StaticString>, StaticString);
+impl_from!(Arc<BigInt>, BigInt);
+
+impl From<BigInt> for Value {
+    fn from(value: BigInt) -> Self {
+        Self::BigInt(Arc::new(value))
+    }
+}
 
 macro_rules! impl_from_shared {
     (Shared<$ty:ty>, $variant:ident) => {
@@ -650,7 +1076,9 @@ impl_from_shared!(Shared<Bytes>, Bytes);
 impl_from_shared!(Shared<String>, String);
 impl_from!(Shared<Vec<Value>>, Vec);
 impl_from_shared!(Shared<Tuple>, Tuple);
+impl_from_shared!(Shared<Range>, Range);
 impl_from!(Shared<Object<Value>>, Object);
+impl_from!(Shared<Map<Value>>, Map);
 impl_from_shared!(Shared<Future>, Future);
 impl_from_shared!(Shared<Stream>, Stream);
 impl_from_shared!(Shared<Generator>, Generator);
@@ -715,6 +1143,8 @@ impl fmt::Display for Integer {
 #[cfg(test)]
 mod tests {
     use super::Value;
+    use crate::{Shared, StaticString};
+    use std::sync::Arc;
 
     #[test]
     fn test_size() {
@@ -724,4 +1154,76 @@ mod tests {
             16,
         };
     }
+
+    #[test]
+    fn test_static_string_eq() {
+        let slot = Arc::new(StaticString::new("hello"));
+        let a = Value::StaticString(slot.clone());
+        let b = Value::StaticString(slot);
+        assert!(Value::value_ptr_eq(&a, &b).unwrap());
+
+        let c = Value::StaticString(Arc::new(StaticString::new("hello")));
+        assert!(Value::value_ptr_eq(&a, &c).unwrap());
+
+        let d = Value::StaticString(Arc::new(StaticString::new("world")));
+        assert!(!Value::value_ptr_eq(&a, &d).unwrap());
+
+        let e = Value::String(Shared::new(String::from("hello")));
+        assert!(Value::value_ptr_eq(&a, &e).unwrap());
+        assert!(Value::value_ptr_eq(&e, &a).unwrap());
+    }
+
+    #[test]
+    fn test_hash_value() {
+        use crate::Shared;
+        use std::hash::Hasher as _;
+
+        fn hash(value: &Value) -> Result<u64, crate::VmError> {
+            let mut hasher = twox_hash::XxHash64::with_seed(0);
+            value.hash_value(&mut hasher)?;
+            Ok(hasher.finish())
+        }
+
+        let a = Value::tuple(vec![Value::Integer(1), Value::from(String::from("a"))]);
+        let b = Value::tuple(vec![Value::Integer(1), Value::from(String::from("a"))]);
+        let c = Value::tuple(vec![Value::Integer(1), Value::from(String::from("b"))]);
+
+        assert_eq!(hash(&a).unwrap(), hash(&b).unwrap());
+        assert_ne!(hash(&a).unwrap(), hash(&c).unwrap());
+
+        let future = Value::from(Shared::new(crate::Future::new(async {
+            Ok::<_, crate::VmError>(())
+        })));
+
+        assert!(hash(&Value::vec(vec![future])).is_err());
+        assert!(hash(&Value::Float(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_typed_object_eq() {
+        use crate::{Hash, Object, TypedObject};
+
+        fn typed(hash: Hash, field: Value) -> Value {
+            let mut object = Object::new();
+            object.insert(String::from("field"), field);
+            Value::from(TypedObject { hash, object })
+        }
+
+        let foo = Hash::type_hash(&["Foo"]);
+        let bar = Hash::type_hash(&["Bar"]);
+
+        let a = typed(foo, Value::Integer(1));
+        assert!(Value::value_ptr_eq(&a, &typed(foo, Value::Integer(1))).unwrap());
+        assert!(!Value::value_ptr_eq(&a, &typed(foo, Value::Integer(2))).unwrap());
+        assert!(!Value::value_ptr_eq(&a, &typed(bar, Value::Integer(1))).unwrap());
+
+        // Fields of differently typed objects are never inspected, so an
+        // exclusively borrowed field doesn't cause an error.
+        let field = Shared::new(vec![Value::Integer(1)]);
+        let guard = field.borrow_mut().unwrap();
+        let b = typed(foo, Value::Vec(field.clone()));
+        assert!(!Value::value_ptr_eq(&b, &typed(bar, Value::Vec(field.clone()))).unwrap());
+        assert!(Value::value_ptr_eq(&b, &typed(foo, Value::Vec(field.clone()))).is_err());
+        drop(guard);
+    }
 }
@@ -1,13 +1,32 @@
+use crate::ambient::Ambient;
+use crate::collections::HashSet;
+use crate::context::Handler;
 use crate::future::SelectFuture;
 use crate::unit::UnitFn;
 use crate::{
-    Args, Awaited, Bytes, Call, Context, FromValue, Function, Future, Generator, Hash, Inst,
-    Integer, IntoHash, Object, Panic, Select, Shared, Stack, Stream, Tuple, TypeCheck, TypedObject,
-    Unit, Value, VariantObject, VmError, VmErrorKind, VmExecution, VmHalt,
+    Args, Awaited, BigInt, BorrowRef, Bytes, Call, Context, FloatFormat, FloatMode, FrameInfo,
+    FromValue, Function, Future, Generator, Hash, HashKey, Inst, Integer, IntoHash, IterationOrder,
+    LoopDetector, Object, Output, Panic, Range, RoundingMode, Select, Shared, Stack, Stream,
+    ToValue, Tuple, Type, TypeCheck, TypedObject, Unit, Value, VariantObject, VmError, VmErrorKind,
+    VmExecution, VmHalt, VmSnapshot,
 };
+use std::cell::RefCell;
 use std::fmt;
 use std::mem;
+use std::rc::Rc;
+use std::str::FromStr as _;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// A hook which is called with every error raised by the virtual machine.
+pub type ErrorHook = Box<dyn FnMut(&VmError)>;
+
+/// A hook which is called with the instruction pointer and instruction of
+/// every instruction before it's executed by the virtual machine.
+pub type TraceHook = Box<dyn FnMut(usize, &Inst)>;
+
+/// The default maximum number of call frames of a virtual machine.
+const DEFAULT_MAX_CALL_FRAMES: usize = 512;
 
 /// A stack which references variables indirectly from a slab.
 #[derive(Debug, Clone)]
@@ -22,6 +41,39 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: Vec<CallFrame>,
+    /// The policy used when converting floats to strings.
+    float_format: FloatFormat,
+    /// Hook called with every raised error.
+    error_hook: Option<SharedErrorHook>,
+    /// Hook called before every executed instruction.
+    trace_hook: Option<SharedTraceHook>,
+    /// Where output produced by the virtual machine is captured.
+    output: Option<Output>,
+    /// The order in which the entries of objects are visited.
+    object_iteration_order: IterationOrder,
+    /// The rounding mode used when converting floats to integers.
+    rounding_mode: RoundingMode,
+    /// The mode used for float arithmetic.
+    float_mode: FloatMode,
+    /// The maximum number of call frames permitted.
+    max_call_frames: usize,
+    /// The maximum amount of time a single await is permitted to block.
+    await_timeout: Option<Duration>,
+    /// Instruction pointers at which execution is suspended.
+    breakpoints: Option<HashSet<usize>>,
+    /// If execution is suspended at the breakpoint at the current instruction
+    /// pointer, which is skipped when resuming.
+    at_breakpoint: bool,
+    /// Detector for likely infinite loops.
+    loop_detector: Option<Box<LoopDetector>>,
+    /// If instance calls are cached.
+    instance_caching: bool,
+    /// Inline cache of instance calls, indexed by the slot of each call site.
+    /// Each entry holds the type of the instance and the function it was last
+    /// resolved to.
+    ///
+    /// Allocated the first time an instance call is resolved.
+    instance_cache: Option<Box<[Option<InstanceCacheEntry>]>>,
 }
 
 impl Vm {
@@ -38,6 +90,20 @@ impl Vm {
             ip: 0,
             stack,
             call_frames: Vec::new(),
+            float_format: FloatFormat::Shortest,
+            error_hook: None,
+            trace_hook: None,
+            output: None,
+            object_iteration_order: IterationOrder::Unspecified,
+            rounding_mode: RoundingMode::Truncate,
+            float_mode: FloatMode::Native,
+            max_call_frames: DEFAULT_MAX_CALL_FRAMES,
+            await_timeout: None,
+            breakpoints: None,
+            at_breakpoint: false,
+            loop_detector: None,
+            instance_caching: true,
+            instance_cache: None,
         }
     }
 
@@ -64,6 +130,7 @@ impl Vm {
     #[inline]
     pub fn set_ip(&mut self, ip: usize) {
         self.ip = ip;
+        self.at_breakpoint = false;
     }
 
     /// Get the stack.
@@ -94,11 +161,291 @@ impl Vm {
         &self.unit
     }
 
+    /// Get the policy used when converting floats to strings.
+    pub fn float_format(&self) -> FloatFormat {
+        self.float_format
+    }
+
+    /// Set the policy used when converting floats to strings.
+    ///
+    /// This covers template strings as well as values which are debug
+    /// printed, like through `dbg`.
+    pub fn set_float_format(&mut self, float_format: FloatFormat) {
+        self.float_format = float_format;
+    }
+
+    /// Set a hook which is called with every error raised by the virtual
+    /// machine, before it's propagated.
+    ///
+    /// Errors which are propagated from another virtual machine are only
+    /// reported by the virtual machine which raised them.
+    pub fn set_error_hook(&mut self, hook: ErrorHook) {
+        self.error_hook = Some(SharedErrorHook(Rc::new(RefCell::new(hook))));
+    }
+
+    /// Clear the error hook, if one is set.
+    pub fn clear_error_hook(&mut self) {
+        self.error_hook = None;
+    }
+
+    /// Set a hook which is called before every instruction executed by the
+    /// virtual machine, with the instruction pointer and the instruction.
+    ///
+    /// Note that instruction pointers are relative to the unit of the virtual
+    /// machine executing them.
+    pub fn set_trace_hook(&mut self, hook: TraceHook) {
+        self.trace_hook = Some(SharedTraceHook(Rc::new(RefCell::new(hook))));
+    }
+
+    /// Clear the trace hook, if one is set.
+    pub fn clear_trace_hook(&mut self) {
+        self.trace_hook = None;
+    }
+
+    /// Capture any output produced by the virtual machine, like through
+    /// `print` or `dbg`, in the given output buffer instead of writing it to
+    /// stdout.
+    pub fn set_output(&mut self, output: Output) {
+        self.output = Some(output);
+    }
+
+    /// Take all output captured so far, if the virtual machine is capturing
+    /// output.
+    ///
+    /// See [set_output][Self::set_output].
+    pub fn take_output(&self) -> Option<Vec<u8>> {
+        Some(self.output.as_ref()?.take())
+    }
+
+    /// Get the order in which the entries of objects are visited.
+    pub fn object_iteration_order(&self) -> IterationOrder {
+        self.object_iteration_order
+    }
+
+    /// Set the order in which the entries of objects are visited, like when
+    /// iterating over them or when they are debug printed.
+    ///
+    /// Using [IterationOrder::Sorted] makes the behavior of scripts which
+    /// depend on the order of objects reproducible, at the cost of sorting
+    /// entries every time they are visited.
+    pub fn set_object_iteration_order(&mut self, order: IterationOrder) {
+        self.object_iteration_order = order;
+    }
+
+    /// Get the rounding mode used when converting floats to integers.
+    pub fn rounding_mode(&self) -> RoundingMode {
+        self.rounding_mode
+    }
+
+    /// Set the rounding mode used when converting floats to integers.
+    pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) {
+        self.rounding_mode = rounding_mode;
+    }
+
+    /// Get the mode used for float arithmetic.
+    pub fn float_mode(&self) -> FloatMode {
+        self.float_mode
+    }
+
+    /// Set the mode used for float arithmetic, see [FloatMode].
+    pub fn set_float_mode(&mut self, float_mode: FloatMode) {
+        self.float_mode = float_mode;
+    }
+
+    /// Get the maximum number of call frames permitted.
+    pub fn max_call_frames(&self) -> usize {
+        self.max_call_frames
+    }
+
+    /// Set the maximum number of call frames permitted, after which calling a
+    /// function errors with
+    /// [CallFrameOverflow][VmErrorKind::CallFrameOverflow].
+    ///
+    /// Defaults to 512.
+    pub fn set_max_call_frames(&mut self, max_call_frames: usize) {
+        self.max_call_frames = max_call_frames;
+    }
+
+    /// Get the maximum amount of time a single await is permitted to block.
+    pub fn await_timeout(&self) -> Option<Duration> {
+        self.await_timeout
+    }
+
+    /// Set the maximum amount of time a single await is permitted to block,
+    /// after which the await errors with
+    /// [AwaitTimeout][VmErrorKind::AwaitTimeout].
+    ///
+    /// This covers awaiting futures and select expressions. On timeout the
+    /// awaited futures are dropped without being polled again, and every
+    /// virtual machine in the execution is cleared.
+    ///
+    /// Defaults to no timeout.
+    pub fn set_await_timeout(&mut self, await_timeout: Option<Duration>) {
+        self.await_timeout = await_timeout;
+    }
+
+    /// Set a breakpoint at the given instruction pointer.
+    ///
+    /// When the virtual machine reaches the instruction pointer, it halts
+    /// before executing the instruction there. See
+    /// [VmExecution::run_to_breakpoint].
+    ///
+    /// Unlike other settings, breakpoints are not inherited by virtual
+    /// machines spawned from this one. Those can't be suspended, so a
+    /// breakpoint reached in them would be an error.
+    pub fn set_breakpoint(&mut self, ip: usize) {
+        self.breakpoints.get_or_insert_with(HashSet::new).insert(ip);
+    }
+
+    /// Clear the breakpoint at the given instruction pointer, if one is set.
+    pub fn clear_breakpoint(&mut self, ip: usize) {
+        if let Some(breakpoints) = &mut self.breakpoints {
+            breakpoints.remove(&ip);
+
+            if breakpoints.is_empty() {
+                self.breakpoints = None;
+                self.at_breakpoint = false;
+            }
+        }
+    }
+
+    /// Set the detector used to diagnose likely infinite loops, or `None` to
+    /// disable it.
+    ///
+    /// When the detector finds a window of instructions which is repeated
+    /// beyond its threshold, it's reported once to the error hook as a
+    /// [LikelyInfiniteLoop][VmErrorKind::LikelyInfiniteLoop] error. Execution
+    /// is not stopped, use a budget to bound how long a script can run.
+    ///
+    /// Disabled by default, since it adds overhead to every instruction.
+    pub fn set_loop_detector(&mut self, loop_detector: Option<LoopDetector>) {
+        self.loop_detector = loop_detector.map(Box::new);
+    }
+
+    /// Enable or disable the inline cache used for instance calls.
+    ///
+    /// When enabled, every call site remembers the function it last resolved
+    /// to together with the type of the instance, so that calling the same
+    /// instance function on the same type skips looking it up again.
+    ///
+    /// Enabled by default.
+    pub fn set_instance_cache(&mut self, enabled: bool) {
+        self.instance_caching = enabled;
+
+        if !enabled {
+            self.instance_cache = None;
+        }
+    }
+
     /// Reset this virtual machine, freeing all memory used.
     pub fn clear(&mut self) {
         self.ip = 0;
+        self.at_breakpoint = false;
         self.stack.clear();
         self.call_frames.clear();
+
+        if let Some(loop_detector) = &mut self.loop_detector {
+            **loop_detector = loop_detector.reset();
+        }
+    }
+
+    /// Save the execution state of the virtual machine as bytes.
+    ///
+    /// This includes the instruction pointer, the call frames, and every
+    /// value on the stack, and can be used to persist a suspended execution,
+    /// like one which has yielded, and resume it later with
+    /// [restore_state][Self::restore_state]. Values which are shared between
+    /// multiple places are restored as shared.
+    ///
+    /// Values which can't be saved, like futures, streams, generators,
+    /// functions, and external types, cause an
+    /// [UnsupportedVmStateValue][VmErrorKind::UnsupportedVmStateValue] error.
+    /// Values which are nested more than 256 levels deep cause a
+    /// [ValueTooDeep][VmErrorKind::ValueTooDeep] error.
+    pub fn save_state(&self) -> Result<Vec<u8>, VmError> {
+        crate::vm_state::encode(
+            self.ip,
+            &self.stack,
+            self.call_frames.iter().map(|f| (f.ip, f.stack_bottom)),
+        )
+    }
+
+    /// Restore an execution state saved with [save_state][Self::save_state],
+    /// replacing the current state of the virtual machine.
+    ///
+    /// The state must be restored into a virtual machine with the same unit
+    /// as the one it was saved from. A state which is malformed, which nests
+    /// values more than 256 levels deep, or which points to instructions
+    /// outside of the unit, causes a [BadVmState][VmErrorKind::BadVmState]
+    /// error.
+    ///
+    /// Use [into_execution][Self::into_execution] to continue running the
+    /// virtual machine from the restored state.
+    pub fn restore_state(&mut self, bytes: &[u8]) -> Result<(), VmError> {
+        let state = crate::vm_state::decode(bytes)?;
+
+        let len = state.stack.len();
+        let in_unit = |ip| self.unit.instruction_at(ip).is_some();
+
+        let is_valid = in_unit(state.ip)
+            && state.stack_bottom <= len
+            && state
+                .call_frames
+                .iter()
+                .all(|&(ip, stack_bottom)| in_unit(ip) && stack_bottom <= len);
+
+        if !is_valid {
+            return Err(VmError::from(VmErrorKind::BadVmState));
+        }
+
+        self.ip = state.ip;
+        self.at_breakpoint = false;
+        self.stack = Stack::from_parts(state.stack, state.stack_bottom);
+        self.call_frames = state
+            .call_frames
+            .into_iter()
+            .map(|(ip, stack_bottom)| CallFrame { ip, stack_bottom })
+            .collect();
+
+        Ok(())
+    }
+
+    /// Take a snapshot of the execution state of the virtual machine.
+    ///
+    /// This includes the instruction pointer, the call frames, and a copy of
+    /// every value on the stack, so that the virtual machine can be rolled
+    /// back to it with [restore][Self::restore] after running, like when a
+    /// statement entered into a REPL errors. Values which are shared between
+    /// multiple places are copied once and stay shared in the snapshot.
+    ///
+    /// Futures, streams, generators, functions, and external types can't be
+    /// copied, so they are shared between the snapshot and the virtual
+    /// machine instead. Values which are nested more than 256 levels deep
+    /// cause a [ValueTooDeep][VmErrorKind::ValueTooDeep] error.
+    pub fn snapshot(&self) -> Result<VmSnapshot, VmError> {
+        Ok(VmSnapshot {
+            ip: self.ip,
+            stack: crate::vm_snapshot::copy_stack(&self.stack)?,
+            call_frames: self.call_frames.clone(),
+        })
+    }
+
+    /// Roll the virtual machine back to a [snapshot][Self::snapshot],
+    /// replacing its current execution state.
+    ///
+    /// The snapshot must be restored into the virtual machine it was taken
+    /// from, or one with the same unit.
+    pub fn restore(&mut self, snapshot: VmSnapshot) {
+        self.ip = snapshot.ip;
+        self.at_breakpoint = false;
+        self.stack = snapshot.stack;
+        self.call_frames = snapshot.call_frames;
+    }
+
+    /// Convert the virtual machine into an execution which continues running
+    /// from the current instruction pointer.
+    pub fn into_execution(self) -> VmExecution {
+        VmExecution::new(self)
     }
 
     /// Access the current instruction pointer.
@@ -226,6 +573,31 @@ impl Vm {
         Ok(Some(Select::new(futures)))
     }
 
+    /// Select over the futures on the stack, but only poll them once.
+    ///
+    /// If no future is ready, a unit is pushed instead of a branch.
+    fn op_select_default(&mut self, len: usize) -> Result<(), VmError> {
+        use futures::FutureExt as _;
+
+        let select = match self.op_select(len)? {
+            Some(select) => select,
+            None => return Ok(()),
+        };
+
+        match select.now_or_never() {
+            Some(result) => {
+                let (branch, value) = result?;
+                self.stack.push(value);
+                self.stack.push(ToValue::to_value(branch)?);
+            }
+            None => {
+                self.stack.push(());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Helper function to call an instance function.
     fn call_instance_fn<H, A>(&mut self, target: &Value, hash: H, args: A) -> Result<bool, VmError>
     where
@@ -233,7 +605,8 @@ impl Vm {
         A: Args,
     {
         let count = A::count() + 1;
-        let hash = Hash::instance_function(target.value_type()?, hash.into_hash());
+        let name = hash.into_hash();
+        let hash = Hash::instance_function(target.value_type()?, name);
 
         if let Some(UnitFn::Offset {
             offset,
@@ -248,7 +621,7 @@ impl Vm {
             return Ok(true);
         }
 
-        let handler = match self.context.lookup(hash) {
+        let handler = match Self::lookup_instance_handler(&self.context, hash, name) {
             Some(handler) => handler,
             None => return Ok(false),
         };
@@ -310,14 +683,34 @@ impl Vm {
     /// Copy a value from a position relative to the top of the stack, to the
     /// top of the stack.
     fn op_copy(&mut self, offset: usize) -> Result<(), VmError> {
+        self.check_offset(offset)?;
         let value = self.stack.at_offset(offset)?.clone();
         self.stack.push(value);
         Ok(())
     }
 
+    /// Drop the value at the given offset, calling its drop protocol if it
+    /// has one and the slot held the last reference to it.
     #[inline]
     fn op_drop(&mut self, offset: usize) -> Result<(), VmError> {
-        let _ = self.stack.at_offset(offset)?;
+        self.check_offset(offset)?;
+        let value = mem::replace(self.stack.at_offset_mut(offset)?, Value::Unit);
+
+        let type_hash = match &value {
+            // NB: a value which is still referenced elsewhere is alive, so
+            // it's not finalized.
+            Value::Any(any) if Shared::is_unique(any) => any.borrow_ref()?.type_hash(),
+            _ => return Ok(()),
+        };
+
+        let hash = Hash::instance_function(crate::Type::Hash(type_hash), crate::DROP);
+
+        if let Some(handler) = self.context.lookup(hash) {
+            self.stack.push(value);
+            handler(&mut self.stack, 1)?;
+            self.stack.pop()?;
+        }
+
         Ok(())
     }
 
@@ -332,53 +725,117 @@ impl Vm {
     /// top of the stack.
     fn op_replace(&mut self, offset: usize) -> Result<(), VmError> {
         let mut value = self.stack.pop()?;
+        self.check_offset(offset)?;
         let stack_value = self.stack.at_offset_mut(offset)?;
         mem::swap(stack_value, &mut value);
         Ok(())
     }
 
-    fn internal_boolean_ops(
+    /// Copy a value from one frame offset into another.
+    fn op_move(&mut self, from: usize, to: usize) -> Result<(), VmError> {
+        self.check_offset(from)?;
+        self.check_offset(to)?;
+        let value = self.stack.at_offset(from)?.clone();
+        *self.stack.at_offset_mut(to)? = value;
+        Ok(())
+    }
+
+    fn internal_boolean_ops<H>(
         &mut self,
+        hash: H,
         int_op: impl FnOnce(i64, i64) -> bool,
         float_op: impl FnOnce(f64, f64) -> bool,
+        str_op: impl FnOnce(&str, &str) -> bool,
         op: &'static str,
-    ) -> Result<(), VmError> {
+    ) -> Result<(), VmError>
+    where
+        H: IntoHash,
+    {
         let rhs = self.stack.pop()?;
         let lhs = self.stack.pop()?;
 
-        let out = match (lhs, rhs) {
-            (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
-            (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
-            (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
+        let (lhs, rhs) = match (lhs, rhs) {
+            (Value::Integer(lhs), Value::Integer(rhs)) => {
+                self.stack.push(int_op(lhs, rhs));
+                return Ok(());
+            }
+            (Value::Float(lhs), Value::Float(rhs)) => {
+                self.stack.push(float_op(lhs, rhs));
+                return Ok(());
             }
+            (lhs, rhs) => (lhs, rhs),
         };
 
-        self.stack.push(out);
-        Ok(())
+        // NB: comparing the ordering against zero gives the same result as
+        // comparing the big integers themselves.
+        if let Some(ordering) = Value::big_int_cmp(&lhs, &rhs) {
+            self.stack.push(int_op(ordering as i64, 0));
+            return Ok(());
+        }
+
+        if let (Some(a), Some(b)) = (Self::string_ref(&lhs)?, Self::string_ref(&rhs)?) {
+            self.stack.push(str_op(&a, &b));
+            return Ok(());
+        }
+
+        if self.call_instance_fn(&lhs, hash, (&rhs,))? {
+            return Ok(());
+        }
+
+        // NB: external types which only implement the CMP protocol can still
+        // be compared.
+        if let (Value::Any(a), Value::Any(b)) = (&lhs, &rhs) {
+            if let Some(ordering) = Value::any_cmp(&self.context, a, b)? {
+                self.stack.push(int_op(ordering as i64, 0));
+                return Ok(());
+            }
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+            op,
+            lhs: lhs.type_info()?,
+            rhs: rhs.type_info()?,
+        }))
+    }
+
+    /// Access the given value as a string, if it is one.
+    fn string_ref(value: &Value) -> Result<Option<StringRef<'_>>, VmError> {
+        Ok(match value {
+            Value::String(string) => Some(StringRef::Shared(string.borrow_ref()?)),
+            Value::StaticString(string) => Some(StringRef::Static(string.as_ref())),
+            _ => None,
+        })
     }
 
     fn op_gt(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">")?;
+        self.internal_boolean_ops(crate::GT, |a, b| a > b, |a, b| a > b, |a, b| a > b, ">")?;
         Ok(())
     }
 
     fn op_gte(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=")?;
+        self.internal_boolean_ops(
+            crate::GTE,
+            |a, b| a >= b,
+            |a, b| a >= b,
+            |a, b| a >= b,
+            ">=",
+        )?;
         Ok(())
     }
 
     fn op_lt(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<")?;
+        self.internal_boolean_ops(crate::LT, |a, b| a < b, |a, b| a < b, |a, b| a < b, "<")?;
         Ok(())
     }
 
     fn op_lte(&mut self) -> Result<(), VmError> {
-        self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=")?;
+        self.internal_boolean_ops(
+            crate::LTE,
+            |a, b| a <= b,
+            |a, b| a <= b,
+            |a, b| a <= b,
+            "<=",
+        )?;
         Ok(())
     }
 
@@ -387,6 +844,12 @@ impl Vm {
     /// This will cause the `args` number of elements on the stack to be
     /// associated and accessible to the new call frame.
     pub(crate) fn push_call_frame(&mut self, ip: usize, args: usize) -> Result<(), VmError> {
+        if self.call_frames.len() >= self.max_call_frames {
+            return Err(VmError::from(VmErrorKind::CallFrameOverflow {
+                depth: self.max_call_frames,
+            }));
+        }
+
         let stack_top = self.stack.swap_stack_bottom(args)?;
 
         self.call_frames.push(CallFrame {
@@ -418,7 +881,7 @@ impl Vm {
     fn op_eq(&mut self) -> Result<(), VmError> {
         let b = self.stack.pop()?;
         let a = self.stack.pop()?;
-        self.stack.push(Value::value_ptr_eq(&a, &b)?);
+        self.stack.push(Value::value_eq(&self.context, &a, &b)?);
         Ok(())
     }
 
@@ -427,7 +890,7 @@ impl Vm {
     fn op_neq(&mut self) -> Result<(), VmError> {
         let b = self.stack.pop()?;
         let a = self.stack.pop()?;
-        self.stack.push(!Value::value_ptr_eq(&a, &b)?);
+        self.stack.push(!Value::value_eq(&self.context, &a, &b)?);
         Ok(())
     }
 
@@ -487,6 +950,25 @@ impl Vm {
         Ok(())
     }
 
+    /// Construct a range out of the bounds on the stack.
+    #[inline]
+    fn op_range(&mut self, from: bool, to: bool, inclusive: bool) -> Result<(), VmError> {
+        let end = if to {
+            Some(i64::from_value(self.stack.pop()?)?)
+        } else {
+            None
+        };
+
+        let start = if from {
+            Some(i64::from_value(self.stack.pop()?)?)
+        } else {
+            None
+        };
+
+        self.stack.push(Range::new(start, end, inclusive));
+        Ok(())
+    }
+
     /// Push the tuple that is on top of the stack.
     #[inline]
     fn op_push_tuple(&mut self) -> Result<(), VmError> {
@@ -515,6 +997,30 @@ impl Vm {
         Ok(())
     }
 
+    #[inline]
+    fn op_neg(&mut self) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        let value = match value {
+            Value::Integer(value) => Value::from(
+                value
+                    .checked_neg()
+                    .ok_or_else(|| VmError::from(VmErrorKind::Overflow))?,
+            ),
+            Value::Float(value) => Value::from(self.float_mode.canonicalize(-value)),
+            other => {
+                let operand = other.type_info()?;
+                return Err(VmError::from(VmErrorKind::UnsupportedUnaryOperation {
+                    op: "-",
+                    operand,
+                }));
+            }
+        };
+
+        self.stack.push(value);
+        Ok(())
+    }
+
     #[inline]
     fn op_add(&mut self) -> Result<(), VmError> {
         self.internal_num(
@@ -577,28 +1083,44 @@ impl Vm {
 
     #[inline]
     fn op_bit_and(&mut self) -> Result<(), VmError> {
-        self.internal_infallible_bitwise(crate::BIT_AND, std::ops::BitAnd::bitand, "&")?;
+        self.internal_boolean_bitwise(
+            crate::BIT_AND,
+            std::ops::BitAnd::bitand,
+            std::ops::BitAnd::bitand,
+            "&",
+        )?;
         Ok(())
     }
 
     #[inline]
     fn op_bit_xor(&mut self) -> Result<(), VmError> {
-        self.internal_infallible_bitwise(crate::BIT_XOR, std::ops::BitXor::bitxor, "^")?;
+        self.internal_boolean_bitwise(
+            crate::BIT_XOR,
+            std::ops::BitXor::bitxor,
+            std::ops::BitXor::bitxor,
+            "^",
+        )?;
         Ok(())
     }
 
     #[inline]
     fn op_bit_or(&mut self) -> Result<(), VmError> {
-        self.internal_infallible_bitwise(crate::BIT_OR, std::ops::BitOr::bitor, "|")?;
+        self.internal_boolean_bitwise(
+            crate::BIT_OR,
+            std::ops::BitOr::bitor,
+            std::ops::BitOr::bitor,
+            "|",
+        )?;
         Ok(())
     }
 
     #[inline]
     fn op_bit_and_assign(&mut self, offset: usize) -> Result<(), VmError> {
-        self.internal_infallible_bitwise_assign(
+        self.internal_boolean_bitwise_assign(
             offset,
             crate::BIT_AND_ASSIGN,
             std::ops::BitAndAssign::bitand_assign,
+            std::ops::BitAndAssign::bitand_assign,
             "&=",
         )?;
         Ok(())
@@ -606,10 +1128,11 @@ impl Vm {
 
     #[inline]
     fn op_bit_xor_assign(&mut self, offset: usize) -> Result<(), VmError> {
-        self.internal_infallible_bitwise_assign(
+        self.internal_boolean_bitwise_assign(
             offset,
             crate::BIT_XOR_ASSIGN,
             std::ops::BitXorAssign::bitxor_assign,
+            std::ops::BitXorAssign::bitxor_assign,
             "^=",
         )?;
         Ok(())
@@ -617,10 +1140,11 @@ impl Vm {
 
     #[inline]
     fn op_bit_or_assign(&mut self, offset: usize) -> Result<(), VmError> {
-        self.internal_infallible_bitwise_assign(
+        self.internal_boolean_bitwise_assign(
             offset,
             crate::BIT_OR_ASSIGN,
             std::ops::BitOrAssign::bitor_assign,
+            std::ops::BitOrAssign::bitor_assign,
             "|=",
         )?;
         Ok(())
@@ -642,7 +1166,15 @@ impl Vm {
 
     #[inline]
     fn op_shr(&mut self) -> Result<(), VmError> {
-        self.internal_infallible_bitwise(crate::SHR, std::ops::Shr::shr, ">>")?;
+        use std::convert::TryFrom as _;
+
+        self.internal_bitwise(
+            crate::SHR,
+            || VmError::from(VmErrorKind::Overflow),
+            |a, b| a.checked_shr(u32::try_from(b).ok()?),
+            ">>",
+        )?;
+
         Ok(())
     }
 
@@ -663,12 +1195,16 @@ impl Vm {
 
     #[inline]
     fn op_shr_assign(&mut self, offset: usize) -> Result<(), VmError> {
-        self.internal_infallible_bitwise_assign(
+        use std::convert::TryFrom as _;
+
+        self.internal_bitwise_assign(
             offset,
             crate::SHR_ASSIGN,
-            std::ops::ShrAssign::shr_assign,
+            || VmError::from(VmErrorKind::Overflow),
+            |a, b| a.checked_shr(u32::try_from(b).ok()?),
             ">>=",
         )?;
+
         Ok(())
     }
 
@@ -744,6 +1280,20 @@ impl Vm {
         let index = self.stack.pop()?;
         let value = self.stack.pop()?;
 
+        // NB: storing a collection inside of itself would create a reference
+        // cycle. Only direct self-references are detected.
+        if Self::is_same_collection(&target, &value) {
+            return Err(VmError::from(VmErrorKind::IndexSetCycle {
+                target: target.type_info()?,
+            }));
+        }
+
+        if let Value::Map(map) = &target {
+            let key = HashKey::from_value(&index)?;
+            map.borrow_mut()?.insert(key, value);
+            return Ok(());
+        }
+
         // This is a useful pattern.
         #[allow(clippy::never_loop)]
         loop {
@@ -803,9 +1353,23 @@ impl Vm {
             }));
         }
 
+        // NB: discard the value returned by the handler.
+        self.stack.pop()?;
         Ok(())
     }
 
+    /// Test if the two values are the same shared collection.
+    fn is_same_collection(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Vec(a), Value::Vec(b)) => Shared::ptr_eq(a, b),
+            (Value::Object(a), Value::Object(b)) => Shared::ptr_eq(a, b),
+            (Value::Map(a), Value::Map(b)) => Shared::ptr_eq(a, b),
+            (Value::TypedObject(a), Value::TypedObject(b)) => Shared::ptr_eq(a, b),
+            (Value::VariantObject(a), Value::VariantObject(b)) => Shared::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
     #[inline]
     fn op_return(&mut self) -> Result<bool, VmError> {
         let return_value = self.stack.pop()?;
@@ -921,9 +1485,9 @@ impl Vm {
         let value = match value {
             Some(value) => value,
             None => {
-                return Err(VmError::from(VmErrorKind::MissingIndex {
-                    target: target.type_info()?,
+                return Err(VmError::from(VmErrorKind::IndexOutOfBounds {
                     index: Integer::Usize(index),
+                    len: Self::tuple_like_len(target)?.unwrap_or_default(),
                 }));
             }
         };
@@ -931,6 +1495,22 @@ impl Vm {
         Ok(Some(value))
     }
 
+    /// Get the number of elements in a tuple-like type, used when reporting
+    /// out of bounds errors.
+    fn tuple_like_len(target: &Value) -> Result<Option<usize>, VmError> {
+        Ok(Some(match target {
+            Value::Unit => 0,
+            Value::Tuple(tuple) => tuple.borrow_ref()?.len(),
+            Value::Vec(vec) => vec.borrow_ref()?.len(),
+            Value::Result(..) => 1,
+            Value::Option(option) => usize::from(option.borrow_ref()?.is_some()),
+            Value::GeneratorState(..) => 1,
+            Value::TypedTuple(typed_tuple) => typed_tuple.borrow_ref()?.tuple.len(),
+            Value::TupleVariant(variant_tuple) => variant_tuple.borrow_ref()?.tuple.len(),
+            _ => return Ok(None),
+        }))
+    }
+
     /// Implementation of getting a string index on an object-like type.
     fn try_tuple_like_index_set(
         target: &Value,
@@ -1012,6 +1592,18 @@ impl Vm {
         let target = self.stack.pop()?;
         let index = self.stack.pop()?;
 
+        if let Value::Map(map) = &target {
+            let key = HashKey::from_value(&index)?;
+
+            let value = match map.borrow_ref()?.get(&key) {
+                Some(value) => value.clone(),
+                None => return Err(VmError::from(VmErrorKind::MissingKey { key })),
+            };
+
+            self.stack.push(value);
+            return Ok(());
+        }
+
         // This is a useful pattern.
         #[allow(clippy::never_loop)]
         loop {
@@ -1028,17 +1620,33 @@ impl Vm {
                         return Ok(());
                     }
                 }
+                Value::Range(range) => {
+                    let range = *range.borrow_ref()?;
+
+                    if let Some(value) = Self::try_range_index_get(&target, range)? {
+                        self.stack.push(value);
+                        return Ok(());
+                    }
+                }
                 Value::Integer(index) => {
                     use std::convert::TryInto as _;
 
+                    if let Some(value) = Self::try_string_index_get(&target, *index)? {
+                        self.stack.push(value);
+                        return Ok(());
+                    }
+
                     let index = match (*index).try_into() {
                         Ok(index) => index,
-                        Err(..) => {
-                            return Err(VmError::from(VmErrorKind::MissingIndex {
-                                target: target.type_info()?,
-                                index: Integer::I64(*index),
-                            }));
-                        }
+                        Err(..) => match Self::tuple_like_len(&target)? {
+                            Some(len) => {
+                                return Err(VmError::from(VmErrorKind::IndexOutOfBounds {
+                                    index: Integer::I64(*index),
+                                    len,
+                                }));
+                            }
+                            None => break,
+                        },
                     };
 
                     if let Some(value) = Self::try_tuple_like_index_get(&target, index)? {
@@ -1048,6 +1656,8 @@ impl Vm {
                 }
                 _ => break,
             };
+
+            break;
         }
 
         if !self.call_instance_fn(&target, crate::INDEX_GET, (&index,))? {
@@ -1060,6 +1670,70 @@ impl Vm {
         Ok(())
     }
 
+    /// Try to slice the given target with a range.
+    fn try_range_index_get(target: &Value, range: Range) -> Result<Option<Value>, VmError> {
+        Ok(Some(match target {
+            Value::Vec(vec) => {
+                let vec = vec.borrow_ref()?;
+                let range = Self::slice_range(range, vec.len())?;
+                Value::Vec(Shared::new(vec[range].to_vec()))
+            }
+            Value::String(string) => {
+                let string = string.borrow_ref()?;
+                Value::from(Self::string_slice(string.as_str(), range)?)
+            }
+            Value::StaticString(string) => Value::from(Self::string_slice(string.as_str(), range)?),
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Resolve a range into indexes into a sequence of the given length.
+    fn slice_range(range: Range, len: usize) -> Result<std::ops::Range<usize>, VmError> {
+        match range.to_slice_range(len) {
+            Some(range) => Ok(range),
+            None => Err(VmError::from(VmErrorKind::IndexRangeOutOfBounds {
+                range,
+                len,
+            })),
+        }
+    }
+
+    /// Slice a string by a range of bytes, which must fall on character
+    /// boundaries.
+    fn string_slice(s: &str, range: Range) -> Result<String, VmError> {
+        match s.get(Self::slice_range(range, s.len())?) {
+            Some(s) => Ok(s.to_owned()),
+            None => Err(VmError::from(VmErrorKind::StringSliceNotCharBoundary {
+                range,
+            })),
+        }
+    }
+
+    /// Try to index into the given target by character, if it's a string.
+    fn try_string_index_get(target: &Value, index: i64) -> Result<Option<Value>, VmError> {
+        let c = match target {
+            Value::String(string) => Self::string_index(string.borrow_ref()?.as_str(), index)?,
+            Value::StaticString(string) => Self::string_index(string.as_str(), index)?,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Value::Char(c)))
+    }
+
+    /// Get the character at the given index of a string, erroring if it's out
+    /// of bounds.
+    fn string_index(s: &str, index: i64) -> Result<char, VmError> {
+        use std::convert::TryFrom as _;
+
+        match usize::try_from(index).ok().and_then(|n| s.chars().nth(n)) {
+            Some(c) => Ok(c),
+            None => Err(VmError::from(VmErrorKind::StringIndexOutOfBounds {
+                index,
+                len: s.chars().count(),
+            })),
+        }
+    }
+
     /// Perform an index get operation specialized for tuples.
     #[inline]
     fn op_tuple_index_get(&mut self, index: usize) -> Result<(), VmError> {
@@ -1105,6 +1779,71 @@ impl Vm {
         }))
     }
 
+    /// Perform an index get operation counted from the back, specialized for
+    /// tuples.
+    #[inline]
+    fn op_tuple_index_get_back_at(&mut self, offset: usize, index: usize) -> Result<(), VmError> {
+        let value = self.stack.at_offset(offset)?;
+
+        if let Some(len) = Self::tuple_like_len(value)? {
+            let index = match len.checked_sub(index + 1) {
+                Some(index) => index,
+                None => {
+                    return Err(VmError::from(VmErrorKind::IndexOutOfBounds {
+                        index: Integer::I64(-(index as i64) - 1),
+                        len,
+                    }));
+                }
+            };
+
+            if let Some(value) = Self::try_tuple_like_index_get(value, index)? {
+                self.stack.push(value);
+                return Ok(());
+            }
+        }
+
+        Err(VmError::from(VmErrorKind::UnsupportedTupleIndexGet {
+            target: value.type_info()?,
+        }))
+    }
+
+    /// Collect a range of elements out of a tuple into a new vector or tuple.
+    #[inline]
+    fn op_tuple_slice_at(
+        &mut self,
+        offset: usize,
+        start: usize,
+        end: usize,
+    ) -> Result<(), VmError> {
+        let value = self.stack.at_offset(offset)?;
+
+        let len = match Self::tuple_like_len(value)? {
+            Some(len) => len,
+            None => {
+                return Err(VmError::from(VmErrorKind::UnsupportedTupleIndexGet {
+                    target: value.type_info()?,
+                }));
+            }
+        };
+
+        let end = len.saturating_sub(end);
+        let mut values = Vec::with_capacity(end.saturating_sub(start));
+
+        for index in start..end {
+            if let Some(value) = Self::try_tuple_like_index_get(value, index)? {
+                values.push(value);
+            }
+        }
+
+        let slice = match value {
+            Value::Vec(..) => Value::vec(values),
+            _ => Value::tuple(values),
+        };
+
+        self.stack.push(slice);
+        Ok(())
+    }
+
     /// Implementation of getting a string index on an object-like type.
     fn try_object_slot_index_get(
         &mut self,
@@ -1273,6 +2012,17 @@ impl Vm {
         Ok(())
     }
 
+    #[inline]
+    fn op_big_int(&mut self, slot: usize) -> Result<(), VmError> {
+        let string = self.unit.lookup_string(slot)?;
+
+        let big_int = BigInt::from_str(string.as_str())
+            .map_err(|_| VmError::from(VmErrorKind::BadBigInt { slot }))?;
+
+        self.stack.push(big_int);
+        Ok(())
+    }
+
     #[inline]
     fn op_bytes(&mut self, slot: usize) -> Result<(), VmError> {
         let bytes = self.unit.lookup_bytes(slot)?.to_owned();
@@ -1287,50 +2037,90 @@ impl Vm {
         let values = self.stack.drain_stack_top(len)?.collect::<Vec<_>>();
 
         for value in values {
-            match value {
-                Value::String(string) => {
-                    buf.push_str(&*string.borrow_ref()?);
-                }
-                Value::StaticString(string) => {
-                    buf.push_str(string.as_ref());
-                }
-                Value::Integer(integer) => {
-                    let mut buffer = itoa::Buffer::new();
-                    buf.push_str(buffer.format(integer));
-                }
-                Value::Float(float) => {
-                    let mut buffer = ryu::Buffer::new();
-                    buf.push_str(buffer.format(float));
-                }
-                actual => {
-                    let b = Shared::new(std::mem::take(&mut buf));
-
-                    if !self.call_instance_fn(
-                        &actual,
-                        crate::STRING_DISPLAY,
-                        (Value::String(b.clone()),),
-                    )? {
-                        return Err(VmError::from(VmErrorKind::MissingProtocol {
-                            protocol: crate::STRING_DISPLAY,
-                            actual: actual.type_info()?,
-                        }));
-                    }
+            if value.format_primitive_into(self.float_format, &mut buf)? {
+                continue;
+            }
 
-                    let value = fmt::Result::from_value(self.stack.pop()?)?;
+            let b = Shared::new(std::mem::take(&mut buf));
 
-                    if let Err(fmt::Error) = value {
-                        return Err(VmError::from(VmErrorKind::FormatError));
-                    }
+            if !self.call_instance_fn(&value, crate::STRING_DISPLAY, (Value::String(b.clone()),))? {
+                return Err(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: crate::STRING_DISPLAY,
+                    actual: value.type_info()?,
+                }));
+            }
 
-                    buf = b.take()?;
-                }
+            let result = fmt::Result::from_value(self.stack.pop()?)?;
+
+            if let Err(fmt::Error) = result {
+                return Err(VmError::from(VmErrorKind::FormatError));
             }
+
+            buf = b.take()?;
         }
 
         self.stack.push(buf);
         Ok(())
     }
 
+    /// Append values to the string in the given frame offset in place, like
+    /// a template string which is added to it with `+=`.
+    fn op_string_concat_assign(
+        &mut self,
+        offset: usize,
+        len: usize,
+        size_hint: usize,
+    ) -> Result<(), VmError> {
+        self.check_offset(offset)?;
+
+        let buf = match self.stack.at_offset(offset)? {
+            Value::String(buf) => buf.clone(),
+            _ => {
+                self.op_string_concat(len, size_hint)?;
+                return self.op_add_assign(offset);
+            }
+        };
+
+        let mut values = self.stack.drain_stack_top(len)?.collect::<Vec<_>>();
+
+        // NB: the string might be part of the template that is appended to it,
+        // in which case its content from before appending is used.
+        for value in &mut values {
+            if let Value::String(string) = value {
+                if Shared::ptr_eq(string, &buf) {
+                    *value = Value::from(string.borrow_ref()?.clone());
+                }
+            }
+        }
+
+        buf.borrow_mut()?.reserve(size_hint);
+
+        for value in values {
+            if value.format_primitive_into(self.float_format, &mut *buf.borrow_mut()?)? {
+                continue;
+            }
+
+            if !self.call_instance_fn(
+                &value,
+                crate::STRING_DISPLAY,
+                (Value::String(buf.clone()),),
+            )? {
+                return Err(VmError::from(VmErrorKind::MissingProtocol {
+                    protocol: crate::STRING_DISPLAY,
+                    actual: value.type_info()?,
+                }));
+            }
+
+            let result = fmt::Result::from_value(self.stack.pop()?)?;
+
+            if let Err(fmt::Error) = result {
+                return Err(VmError::from(VmErrorKind::FormatError));
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn op_unwrap(&mut self) -> Result<(), VmError> {
         let value = self.stack.pop()?;
@@ -1492,6 +2282,18 @@ impl Vm {
         Ok(())
     }
 
+    #[inline]
+    fn op_eq_float(&mut self, float: f64) -> Result<(), VmError> {
+        let value = self.stack.pop()?;
+
+        self.stack.push(match value {
+            Value::Float(actual) => actual == float,
+            _ => false,
+        });
+
+        Ok(())
+    }
+
     /// Test if the top of stack is equal to the string at the given static
     /// string location.
     #[inline]
@@ -1667,10 +2469,52 @@ impl Vm {
         Ok(None)
     }
 
+    /// Construct a new virtual machine with the given stack, which inherits
+    /// the configuration of this one.
+    ///
+    /// Child virtual machines run generators, streams, async functions, and
+    /// functions called from native code. They inherit every setting of this
+    /// one except for breakpoints, and share its instance cache if they run
+    /// the same unit in the same context.
+    pub(crate) fn new_child(&self, stack: Stack) -> Self {
+        self.new_child_with(self.context.clone(), self.unit.clone(), stack)
+    }
+
+    /// Construct a new virtual machine with the given context, unit, and stack,
+    /// which inherits the configuration of this one.
+    pub(crate) fn new_child_with(
+        &self,
+        context: Arc<Context>,
+        unit: Arc<Unit>,
+        stack: Stack,
+    ) -> Self {
+        let mut vm = Self::new_with_stack(context, unit, stack);
+        vm.float_format = self.float_format;
+        vm.error_hook = self.error_hook.clone();
+        vm.trace_hook = self.trace_hook.clone();
+        vm.output = self.output.clone();
+        vm.object_iteration_order = self.object_iteration_order;
+        vm.rounding_mode = self.rounding_mode;
+        vm.float_mode = self.float_mode;
+        vm.max_call_frames = self.max_call_frames;
+        vm.await_timeout = self.await_timeout;
+        vm.instance_caching = self.instance_caching;
+
+        if Arc::ptr_eq(&vm.unit, &self.unit) && Arc::ptr_eq(&vm.context, &self.context) {
+            vm.instance_cache = self.instance_cache.clone();
+        }
+
+        vm.loop_detector = self
+            .loop_detector
+            .as_ref()
+            .map(|loop_detector| Box::new(loop_detector.reset()));
+        vm
+    }
+
     /// Construct a future from calling an async function.
     fn call_generator_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
-        let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        let mut vm = self.new_child(stack);
         vm.ip = offset;
         self.stack.push(Generator::new(vm));
         Ok(())
@@ -1679,7 +2523,7 @@ impl Vm {
     /// Construct a stream from calling a function.
     fn call_stream_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
-        let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        let mut vm = self.new_child(stack);
         vm.ip = offset;
         self.stack.push(Stream::new(vm));
         Ok(())
@@ -1688,7 +2532,7 @@ impl Vm {
     /// Construct a future from calling a function.
     fn call_async_fn(&mut self, offset: usize, args: usize) -> Result<(), VmError> {
         let stack = self.stack.drain_stack_top(args)?.collect::<Stack>();
-        let mut vm = Self::new_with_stack(self.context.clone(), self.unit.clone(), stack);
+        let mut vm = self.new_child(stack);
         vm.ip = offset;
         self.stack.push(Future::new(vm.async_complete()));
         Ok(())
@@ -1818,44 +2662,66 @@ impl Vm {
     }
 
     #[inline]
-    fn op_call_instance<H>(&mut self, hash: H, args: usize) -> Result<(), VmError>
+    fn op_call_instance<H>(&mut self, hash: H, args: usize, slot: usize) -> Result<(), VmError>
     where
         H: IntoHash,
     {
         // NB: +1 to include the instance itself.
         let args = args + 1;
-        let instance = self.stack.at_offset_from_top(args)?;
-        let value_type = instance.value_type()?;
-        let hash = Hash::instance_function(value_type, hash);
-
-        match self.unit.lookup(hash) {
-            Some(info) => match info {
-                UnitFn::Offset {
-                    offset,
-                    call,
-                    args: expected,
-                } => {
-                    Self::check_args(args, expected)?;
-                    self.call_offset_fn(offset, call, args)?;
-                }
-                _ => {
-                    return Err(VmError::from(VmErrorKind::MissingInstanceFunction {
-                        instance: instance.type_info()?,
-                        hash,
-                    }));
-                }
-            },
-            None => {
-                let handler = match self.context.lookup(hash) {
-                    Some(handler) => handler,
-                    None => {
-                        return Err(VmError::from(VmErrorKind::MissingInstanceFunction {
-                            instance: instance.type_info()?,
-                            hash,
-                        }));
+        let value_type = self.stack.at_offset_from_top(args)?.value_type()?;
+
+        // NB: most call sites only ever see one type of instance, so the last
+        // resolved function is cached per call site. Dispatching from the
+        // cache only copies the offset or borrows the handler.
+        let cached = self
+            .instance_cache
+            .as_ref()
+            .and_then(|cache| cache.get(slot));
+
+        if let Some(Some((ty, target))) = cached {
+            if *ty == value_type {
+                match *target {
+                    InstanceTarget::Offset {
+                        offset,
+                        call,
+                        args: expected,
+                    } => {
+                        Self::check_args(args, expected)?;
+                        self.call_offset_fn(offset, call, args)?;
                     }
-                };
+                    InstanceTarget::Handler(ref handler) => {
+                        handler(&mut self.stack, args)?;
+                    }
+                }
 
+                return Ok(());
+            }
+        }
+
+        let target = self.resolve_instance_fn(value_type, hash.into_hash(), args)?;
+
+        if self.instance_caching {
+            let slots = self.unit.instance_call_slots();
+
+            let cache = self
+                .instance_cache
+                .get_or_insert_with(|| (0..slots).map(|_| None).collect());
+
+            if let Some(entry) = cache.get_mut(slot) {
+                *entry = Some((value_type, target.clone()));
+            }
+        }
+
+        match target {
+            InstanceTarget::Offset {
+                offset,
+                call,
+                args: expected,
+            } => {
+                Self::check_args(args, expected)?;
+                self.call_offset_fn(offset, call, args)?;
+            }
+            InstanceTarget::Handler(handler) => {
                 handler(&mut self.stack, args)?;
             }
         }
@@ -1863,6 +2729,34 @@ impl Vm {
         Ok(())
     }
 
+    /// Resolve the instance function with the given name for the given type
+    /// of instance, which is `args` from the top of the stack.
+    fn resolve_instance_fn(
+        &self,
+        value_type: Type,
+        name: Hash,
+        args: usize,
+    ) -> Result<InstanceTarget, VmError> {
+        let hash = Hash::instance_function(value_type, name);
+
+        match self.unit.lookup(hash) {
+            Some(UnitFn::Offset { offset, call, args }) => {
+                return Ok(InstanceTarget::Offset { offset, call, args });
+            }
+            Some(..) => (),
+            None => {
+                if let Some(handler) = Self::lookup_instance_handler(&self.context, hash, name) {
+                    return Ok(InstanceTarget::Handler(handler.clone()));
+                }
+            }
+        }
+
+        Err(VmError::from(VmErrorKind::MissingInstanceFunction {
+            instance: self.stack.at_offset_from_top(args)?.type_info()?,
+            hash,
+        }))
+    }
+
     fn op_call_fn(&mut self, args: usize) -> Result<Option<VmHalt>, VmError> {
         let function = self.stack.pop()?;
 
@@ -1889,9 +2783,65 @@ impl Vm {
         self.ip = self.ip.overflowing_add(1).0;
     }
 
+    /// Run the virtual machine for the given number of instructions, or until
+    /// it halts.
+    ///
+    /// The limit is decremented for every instruction executed, so what
+    /// remains of it can be carried over to subsequent runs.
+    pub(crate) fn run_for(&mut self, limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
+        let _guard = Ambient {
+            output: self.output.clone(),
+            context: Some(self.context.clone()),
+            iteration_order: self.object_iteration_order,
+            rounding_mode: self.rounding_mode,
+            float_mode: self.float_mode,
+            float_format: self.float_format,
+        }
+        .enter();
+
+        match self.run_for_inner(limit) {
+            Ok(halt) => Ok(halt),
+            Err(error) => {
+                if let Some(hook) = &self.error_hook {
+                    if !error.is_unwound() {
+                        (hook.0.borrow_mut())(&error);
+                    }
+                }
+
+                Err(error.with_trace(|| self.trace()))
+            }
+        }
+    }
+
+    /// Capture the current instruction pointer and the call frames leading up
+    /// to it, starting with the innermost frame.
+    fn trace(&self) -> Vec<FrameInfo> {
+        let mut trace = Vec::with_capacity(self.call_frames.len() + 1);
+        trace.push(FrameInfo::new(self.ip));
+        trace.extend(self.call_frames.iter().rev().map(|f| FrameInfo::new(f.ip)));
+        trace
+    }
+
     /// Evaluate a single instruction.
-    pub(crate) fn run_for(&mut self, mut limit: Option<usize>) -> Result<VmHalt, VmError> {
+    #[inline]
+    fn run_for_inner(&mut self, mut limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
         loop {
+            if let Some(breakpoints) = &self.breakpoints {
+                // NB: skip the breakpoint we're resuming from.
+                if !mem::take(&mut self.at_breakpoint) && breakpoints.contains(&self.ip) {
+                    self.at_breakpoint = true;
+                    return Ok(VmHalt::Breakpoint { ip: self.ip });
+                }
+            }
+
+            if let Some(limit) = &mut limit {
+                if **limit == 0 {
+                    return Ok(VmHalt::Limited);
+                }
+
+                **limit -= 1;
+            }
+
             let inst = *self
                 .unit
                 .instruction_at(self.ip)
@@ -1899,10 +2849,33 @@ impl Vm {
 
             log::trace!("{}: {}", self.ip, inst);
 
+            if let Some(hook) = &self.trace_hook {
+                (hook.0.borrow_mut())(self.ip, &inst);
+            }
+
+            if let Some(loop_detector) = &mut self.loop_detector {
+                if let Some((start, end, repeats)) =
+                    loop_detector.visit(self.ip, self.call_frames.len())
+                {
+                    if let Some(hook) = &self.error_hook {
+                        let error = VmError::from(VmErrorKind::LikelyInfiniteLoop {
+                            start,
+                            end,
+                            repeats,
+                        });
+
+                        (hook.0.borrow_mut())(&error);
+                    }
+                }
+            }
+
             match inst {
                 Inst::Not => {
                     self.op_not()?;
                 }
+                Inst::Neg => {
+                    self.op_neg()?;
+                }
                 Inst::Add => {
                     self.op_add()?;
                 }
@@ -1942,8 +2915,8 @@ impl Vm {
                 Inst::Call { hash, args } => {
                     self.op_call(hash, args)?;
                 }
-                Inst::CallInstance { hash, args } => {
-                    self.op_call_instance(hash, args)?;
+                Inst::CallInstance { hash, args, slot } => {
+                    self.op_call_instance(hash, args, slot)?;
                 }
                 Inst::CallFn { args } => {
                     if let Some(reason) = self.op_call_fn(args)? {
@@ -1965,6 +2938,12 @@ impl Vm {
                 Inst::TupleIndexGetAt { offset, index } => {
                     self.op_tuple_index_get_at(offset, index)?;
                 }
+                Inst::TupleIndexGetBackAt { offset, index } => {
+                    self.op_tuple_index_get_back_at(offset, index)?;
+                }
+                Inst::TupleSliceAt { offset, start, end } => {
+                    self.op_tuple_slice_at(offset, start, end)?;
+                }
                 Inst::ObjectSlotIndexGet { slot } => {
                     self.op_object_slot_index_get(slot)?;
                 }
@@ -1997,6 +2976,9 @@ impl Vm {
                         return Ok(VmHalt::Awaited(Awaited::Select(select)));
                     }
                 }
+                Inst::SelectDefault { len } => {
+                    self.op_select_default(len)?;
+                }
                 Inst::Pop => {
                     self.stack.pop()?;
                 }
@@ -2013,7 +2995,13 @@ impl Vm {
                     self.stack.push(Value::Integer(number));
                 }
                 Inst::Float { number } => {
-                    self.stack.push(Value::Float(number));
+                    // NB: the constant might have been folded by the host,
+                    // which is free to produce any NaN.
+                    self.stack
+                        .push(Value::Float(self.float_mode.canonicalize(number)));
+                }
+                Inst::BigInt { slot } => {
+                    self.op_big_int(slot)?;
                 }
                 Inst::Copy { offset } => {
                     self.op_copy(offset)?;
@@ -2027,6 +3015,9 @@ impl Vm {
                 Inst::Replace { offset } => {
                     self.op_replace(offset)?;
                 }
+                Inst::Move { from, to } => {
+                    self.op_move(from, to)?;
+                }
                 Inst::Gt => {
                     self.op_gt()?;
                 }
@@ -2069,6 +3060,13 @@ impl Vm {
                 Inst::Tuple { count } => {
                     self.op_tuple(count)?;
                 }
+                Inst::Range {
+                    from,
+                    to,
+                    inclusive,
+                } => {
+                    self.op_range(from, to, inclusive)?;
+                }
                 Inst::PushTuple => {
                     self.op_push_tuple()?;
                 }
@@ -2103,6 +3101,13 @@ impl Vm {
                 Inst::StringConcat { len, size_hint } => {
                     self.op_string_concat(len, size_hint)?;
                 }
+                Inst::StringConcatAssign {
+                    offset,
+                    len,
+                    size_hint,
+                } => {
+                    self.op_string_concat_assign(offset, len, size_hint)?;
+                }
                 Inst::Is => {
                     self.op_is()?;
                 }
@@ -2163,6 +3168,9 @@ impl Vm {
                 Inst::EqInteger { integer } => {
                     self.op_eq_integer(integer)?;
                 }
+                Inst::EqFloat { float } => {
+                    self.op_eq_float(float)?;
+                }
                 Inst::EqStaticString { slot } => {
                     self.op_eq_static_string(slot)?;
                 }
@@ -2197,14 +3205,6 @@ impl Vm {
             }
 
             self.advance();
-
-            if let Some(limit) = &mut limit {
-                if *limit <= 1 {
-                    return Ok(VmHalt::Limited);
-                }
-
-                *limit -= 1;
-            }
         }
     }
 
@@ -2223,6 +3223,7 @@ impl Vm {
         I: FnOnce(i64, i64) -> Option<i64>,
         F: FnOnce(f64, f64) -> f64,
     {
+        let float_mode = self.float_mode;
         let rhs = self.stack.pop()?;
         let lhs = self.stack.at_offset_mut(offset)?;
 
@@ -2233,7 +3234,7 @@ impl Vm {
                 return Ok(());
             }
             (Value::Float(lhs), Value::Float(rhs)) => {
-                let out = float_op(*lhs, rhs);
+                let out = float_mode.canonicalize(float_op(*lhs, rhs));
                 *lhs = out;
                 return Ok(());
             }
@@ -2276,7 +3277,8 @@ impl Vm {
                 return Ok(());
             }
             (Value::Float(lhs), Value::Float(rhs)) => {
-                self.stack.push(float_op(lhs, rhs));
+                let out = self.float_mode.canonicalize(float_op(lhs, rhs));
+                self.stack.push(out);
                 return Ok(());
             }
             (lhs, rhs) => (lhs.clone(), rhs),
@@ -2293,16 +3295,19 @@ impl Vm {
         Ok(())
     }
 
-    /// Internal impl of a numeric operation.
-    fn internal_infallible_bitwise<H, I>(
+    /// Internal implementation of bitwise operations which are also defined
+    /// as logical operations over booleans.
+    fn internal_boolean_bitwise<H, I, B>(
         &mut self,
         hash: H,
         integer_op: I,
+        bool_op: B,
         op: &'static str,
     ) -> Result<(), VmError>
     where
         H: IntoHash,
         I: FnOnce(i64, i64) -> i64,
+        B: FnOnce(bool, bool) -> bool,
     {
         let rhs = self.stack.pop()?;
         let lhs = self.stack.pop()?;
@@ -2312,6 +3317,10 @@ impl Vm {
                 self.stack.push(integer_op(lhs, rhs));
                 return Ok(());
             }
+            (Value::Bool(lhs), Value::Bool(rhs)) => {
+                self.stack.push(bool_op(lhs, rhs));
+                return Ok(());
+            }
             (lhs, rhs) => (lhs.clone(), rhs),
         };
 
@@ -2326,16 +3335,18 @@ impl Vm {
         Ok(())
     }
 
-    fn internal_infallible_bitwise_assign<H, I>(
+    fn internal_boolean_bitwise_assign<H, I, B>(
         &mut self,
         offset: usize,
         hash: H,
         integer_op: I,
+        bool_op: B,
         op: &'static str,
     ) -> Result<(), VmError>
     where
         H: IntoHash,
         I: FnOnce(&mut i64, i64),
+        B: FnOnce(&mut bool, bool),
     {
         let rhs = self.stack.pop()?;
         let lhs = self.stack.at_offset_mut(offset)?;
@@ -2345,6 +3356,10 @@ impl Vm {
                 integer_op(lhs, rhs);
                 return Ok(());
             }
+            (Value::Bool(lhs), Value::Bool(rhs)) => {
+                bool_op(lhs, rhs);
+                return Ok(());
+            }
             (lhs, rhs) => (lhs.clone(), rhs),
         };
 
@@ -2430,6 +3445,37 @@ impl Vm {
         Ok(())
     }
 
+    /// Look up the native handler for an instance function, falling back to
+    /// one registered for [ANY_TYPE][crate::ANY_TYPE] with the same name.
+    pub(crate) fn lookup_instance_handler(
+        context: &Context,
+        hash: Hash,
+        name: Hash,
+    ) -> Option<&Arc<Handler>> {
+        if let Some(handler) = context.lookup(hash) {
+            return Some(handler);
+        }
+
+        context.lookup(Hash::instance_function(
+            crate::Type::StaticType(crate::ANY_TYPE),
+            name,
+        ))
+    }
+
+    /// Check that the given offset is inside of the current call frame.
+    fn check_offset(&self, offset: usize) -> Result<(), VmError> {
+        let frame_size = self.stack.len().saturating_sub(self.stack.stack_bottom());
+
+        if offset >= frame_size {
+            return Err(VmError::from(VmErrorKind::InvalidStackOffset {
+                offset,
+                frame_size,
+            }));
+        }
+
+        Ok(())
+    }
+
     /// Check that arguments matches expected or raise the appropriate error.
     fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
         if args != args {
@@ -2443,6 +3489,77 @@ impl Vm {
     }
 }
 
+/// An error hook which is shared with any child virtual machines.
+#[derive(Clone)]
+struct SharedErrorHook(Rc<RefCell<ErrorHook>>);
+
+impl fmt::Debug for SharedErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ErrorHook")
+    }
+}
+
+/// A trace hook which is shared with any child virtual machines.
+#[derive(Clone)]
+struct SharedTraceHook(Rc<RefCell<TraceHook>>);
+
+impl fmt::Debug for SharedTraceHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TraceHook")
+    }
+}
+
+/// An entry in the inline cache of instance calls, as the type of the instance
+/// and the function it was resolved to.
+type InstanceCacheEntry = (Type, InstanceTarget);
+
+/// The function an instance call was resolved to.
+#[derive(Clone)]
+enum InstanceTarget {
+    /// A function in the unit.
+    Offset {
+        /// The offset of the function.
+        offset: usize,
+        /// The way the function is called.
+        call: Call,
+        /// The number of arguments the function takes.
+        args: usize,
+    },
+    /// A native function in the context.
+    Handler(Arc<Handler>),
+}
+
+impl fmt::Debug for InstanceTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Offset { offset, call, args } => f
+                .debug_struct("Offset")
+                .field("offset", offset)
+                .field("call", call)
+                .field("args", args)
+                .finish(),
+            Self::Handler(..) => write!(f, "Handler"),
+        }
+    }
+}
+
+/// A string borrowed from a value, which is either shared or static.
+enum StringRef<'a> {
+    Shared(BorrowRef<'a, String>),
+    Static(&'a String),
+}
+
+impl std::ops::Deref for StringRef<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Self::Shared(string) => string,
+            Self::Static(string) => string,
+        }
+    }
+}
+
 /// A call frame.
 ///
 /// This is used to store the return point after an instruction has been run.
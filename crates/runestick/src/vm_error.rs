@@ -1,16 +1,42 @@
 use crate::panic::BoxedPanic;
 use crate::{
-    AccessError, Hash, Integer, Panic, Protocol, StackError, TypeInfo, Unit, Value, ValueType,
-    VmHaltInfo,
+    AccessError, Hash, HashKey, Integer, Panic, Protocol, Range, StackError, TypeInfo, Unit, Value,
+    ValueType, VmHaltInfo,
 };
+use std::error;
+use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors raised by the execution of the virtual machine.
-#[derive(Error, Debug)]
-#[error(transparent)]
+#[derive(Debug)]
 pub struct VmError {
     kind: Box<VmErrorKind>,
+    trace: Option<Box<[FrameInfo]>>,
+}
+
+/// Information on a single call frame which was active when an error was
+/// raised, see [VmError::trace].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo {
+    ip: usize,
+}
+
+impl FrameInfo {
+    /// Construct frame information for the given instruction pointer.
+    pub(crate) fn new(ip: usize) -> Self {
+        Self { ip }
+    }
+
+    /// Get the instruction pointer of the frame.
+    ///
+    /// For the innermost frame this is the instruction which raised the
+    /// error, for every other frame it's the call instruction which is
+    /// waiting for the frame above it to return.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
 }
 
 impl VmError {
@@ -57,27 +83,68 @@ impl VmError {
         &*self.kind
     }
 
+    /// Get the stack trace of the virtual machine at the point where the
+    /// error was raised, starting with the innermost frame.
+    ///
+    /// This is only captured for errors raised while running a virtual
+    /// machine, and can be used together with the debug information of the
+    /// unit to map instruction pointers back to source spans. If the error was
+    /// raised in a nested virtual machine, like one running a function
+    /// pointer, the trace covers that virtual machine.
+    pub fn trace(&self) -> Option<&[FrameInfo]> {
+        self.trace.as_deref()
+    }
+
+    /// Attach the given stack trace to the error, unless it already has one.
+    pub(crate) fn with_trace<T>(mut self, trace: T) -> Self
+    where
+        T: FnOnce() -> Vec<FrameInfo>,
+    {
+        if self.trace.is_none() {
+            self.trace = Some(trace().into_boxed_slice());
+        }
+
+        self
+    }
+
     /// Convert into an unwinded vm error.
     pub fn into_unwinded(self, unit: &Arc<Unit>, ip: usize) -> Self {
         if let VmErrorKind::Unwound { .. } = &*self.kind {
             return self;
         }
 
-        Self::from(VmErrorKind::Unwound {
-            kind: self.kind,
-            unit: unit.clone(),
-            ip,
-        })
+        Self {
+            kind: Box::new(VmErrorKind::Unwound {
+                kind: self.kind,
+                unit: unit.clone(),
+                ip,
+            }),
+            trace: self.trace,
+        }
+    }
+
+    /// Test if the error has been unwound, meaning that it has been
+    /// propagated from a virtual machine.
+    pub fn is_unwound(&self) -> bool {
+        matches!(&*self.kind, VmErrorKind::Unwound { .. })
     }
 
     /// Unpack an unwinded error, if it is present.
     pub fn into_unwound(self) -> (Self, Option<(Arc<Unit>, usize)>) {
+        let trace = self.trace;
+
         match *self.kind {
             VmErrorKind::Unwound { kind, unit, ip } => {
-                let error = Self { kind };
+                let error = Self { kind, trace };
                 (error, Some((unit, ip)))
             }
-            kind => (Self::from(kind), None),
+            kind => (
+                Self {
+                    kind: Box::new(kind),
+                    trace,
+                },
+                None,
+            ),
         }
     }
 
@@ -103,6 +170,18 @@ impl VmError {
     }
 }
 
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.kind, f)
+    }
+}
+
+impl error::Error for VmError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.kind.source()
+    }
+}
+
 impl<E> From<E> for VmError
 where
     VmErrorKind: From<E>,
@@ -110,6 +189,7 @@ where
     fn from(err: E) -> Self {
         Self {
             kind: Box::new(VmErrorKind::from(err)),
+            trace: None,
         }
     }
 }
@@ -145,6 +225,51 @@ pub enum VmErrorKind {
         /// The reason why the virtual machine stopped.
         halt: VmHaltInfo,
     },
+    /// Calling a function would exceed the maximum number of call frames.
+    #[error("call frame overflow, exceeded the maximum depth of {depth}")]
+    CallFrameOverflow {
+        /// The maximum number of call frames.
+        depth: usize,
+    },
+    /// The static string at the given slot is not a valid big integer.
+    #[error("static string slot `{slot}` does not contain a valid big integer")]
+    BadBigInt {
+        /// The slot of the static string.
+        slot: usize,
+    },
+    /// The execution ran for longer than it was permitted to.
+    #[error("execution timed out after {timeout:?}")]
+    Timeout {
+        /// The timeout which was exceeded.
+        timeout: Duration,
+    },
+    /// An await blocked for longer than it was permitted to.
+    #[error("await timed out after {timeout:?}")]
+    AwaitTimeout {
+        /// The timeout which was exceeded.
+        timeout: Duration,
+    },
+    /// The same window of instructions has been repeated many times, which is
+    /// a sign of a likely infinite loop.
+    ///
+    /// This is only reported to the error hook, and does not stop execution.
+    /// See [Vm::set_loop_detector][crate::Vm::set_loop_detector].
+    #[error("likely infinite loop, instructions {start} to {end} repeated {repeats} times")]
+    LikelyInfiniteLoop {
+        /// The instruction pointer the window starts at.
+        start: usize,
+        /// The instruction pointer the window ends at.
+        end: usize,
+        /// The number of times the window was repeated.
+        repeats: usize,
+    },
+    /// A float operation whose results differ between platforms was used while
+    /// the virtual machine is in [FloatMode::Deterministic][crate::FloatMode].
+    #[error("float operation `{op}` is not deterministic")]
+    NonDeterministicFloatOperation {
+        /// The name of the operation.
+        op: &'static str,
+    },
     /// Error raised when external format function results in error.
     #[error("failed to format argument")]
     FormatError,
@@ -181,12 +306,24 @@ pub enum VmErrorKind {
     /// Instruction pointer went out-of-bounds.
     #[error("instruction pointer is out-of-bounds")]
     IpOutOfBounds,
+    /// Tried to access a stack offset outside of the current call frame.
+    #[error("stack offset `{offset}` is out of bounds for call frame of size `{frame_size}`")]
+    InvalidStackOffset {
+        /// The offset being accessed.
+        offset: usize,
+        /// The number of values in the current call frame.
+        frame_size: usize,
+    },
     /// Tried to await something on the stack which can't be await:ed.
     #[error("unsupported target for .await `{actual}`")]
     UnsupportedAwait {
         /// The actual target.
         actual: TypeInfo,
     },
+    /// Tried to race futures where none of them can produce a value, either
+    /// because there are none or because they have all completed.
+    #[error("no pending futures to race")]
+    NoPendingFutures,
     /// Unsupported binary operation.
     #[error("unsupported vm operation `{lhs} {op} {rhs}`")]
     UnsupportedBinaryOperation {
@@ -243,6 +380,16 @@ pub enum VmErrorKind {
         /// The argument type we got.
         actual: TypeInfo,
     },
+    /// An element of a collection had an unexpected type.
+    #[error("bad element #{index}, expected `{expected}` but got `{actual}`")]
+    BadElementType {
+        /// The index of the element.
+        index: usize,
+        /// The element type we expected.
+        expected: TypeInfo,
+        /// The element type we got.
+        actual: TypeInfo,
+    },
     /// Failure to convert from one type to another.
     #[error("bad argument #{arg} (expected `{to}`): {error}")]
     BadArgument {
@@ -273,6 +420,27 @@ pub enum VmErrorKind {
         /// The value to set.
         value: TypeInfo,
     },
+    /// Tried to use a value which can't be hashed as the key of a map.
+    #[error("value of type `{actual}` can't be used as a key")]
+    UnsupportedHashKey {
+        /// The type of the value.
+        actual: TypeInfo,
+    },
+    /// Tried to get a key which is missing from a map.
+    #[error("missing key `{key}` in map")]
+    MissingKey {
+        /// The key which was missing.
+        key: HashKey,
+    },
+    /// Tried to store a value inside of itself with an index set operation.
+    ///
+    /// This is rejected since it would create a reference cycle, which would
+    /// never be freed.
+    #[error("cannot store `{target}` inside of itself")]
+    IndexSetCycle {
+        /// The target which was being stored into itself.
+        target: TypeInfo,
+    },
     /// An index get operation that is not supported.
     #[error("the index get operation `{target}[{index}]` is not supported")]
     UnsupportedIndexGet {
@@ -319,13 +487,52 @@ pub enum VmErrorKind {
         /// The static string slot corresponding to the index that is missing.
         slot: usize,
     },
-    /// Tried to access an index that was missing on a type.
-    #[error("missing index `{}` on `{target}`")]
-    MissingIndex {
-        /// Type where field did not exist.
-        target: TypeInfo,
+    /// Tried to access an index which is out of bounds of a tuple-like type.
+    #[error("index `{index}` is out of bounds for length `{len}`")]
+    IndexOutOfBounds {
         /// Index that we tried to access.
         index: Integer,
+        /// The length of the value being indexed.
+        len: usize,
+    },
+    /// Tried to access a range which is out of bounds.
+    #[error("range `{start}..{end}` is out of bounds for length `{len}`")]
+    OutOfRange {
+        /// The start of the range.
+        start: usize,
+        /// The end of the range.
+        end: usize,
+        /// The length of the collection being accessed.
+        len: usize,
+    },
+    /// Tried to index a sequence with a range which is out of bounds.
+    #[error("range `{range}` is out of bounds for length `{len}`")]
+    IndexRangeOutOfBounds {
+        /// The range used as an index.
+        range: Range,
+        /// The length of the collection being accessed.
+        len: usize,
+    },
+    /// Tried to index a string by a character which is out of bounds.
+    #[error("index `{index}` is out of bounds for string with `{len}` characters")]
+    StringIndexOutOfBounds {
+        /// The index of the character.
+        index: i64,
+        /// The number of characters in the string.
+        len: usize,
+    },
+    /// Tried to slice a string with a range which doesn't fall on character
+    /// boundaries.
+    #[error("range `{range}` does not fall on character boundaries")]
+    StringSliceNotCharBoundary {
+        /// The range used to slice the string.
+        range: Range,
+    },
+    /// Tried to iterate over a range without a start.
+    #[error("cannot iterate over the range `{range}` since it has no start")]
+    UnsupportedRangeIter {
+        /// The range we tried to iterate over.
+        range: Range,
     },
     /// Missing a struct field.
     #[error("missing field `{field}` on `{target}`")]
@@ -408,6 +615,44 @@ pub enum VmErrorKind {
     /// Internal error that happens when we run out of items in a list.
     #[error("unexpectedly ran out of items to iterate over")]
     IterationError,
+    /// A replayed execution visited a different instruction than the one
+    /// recorded.
+    #[error("execution diverged from recording at step `{position}`, expected instruction `{expected}` but found `{actual}`")]
+    ReplayDivergence {
+        /// The step in the recording where the divergence happened.
+        position: usize,
+        /// The recorded instruction pointer.
+        expected: usize,
+        /// The instruction pointer that was actually visited.
+        actual: usize,
+    },
+    /// A replayed execution ran for a different number of steps than the
+    /// recording.
+    #[error("execution ran for `{actual}` steps, but the recording has `{expected}`")]
+    ReplayLengthMismatch {
+        /// The number of recorded steps.
+        expected: usize,
+        /// The number of steps visited by the execution.
+        actual: usize,
+    },
+    /// A value on the stack can't be saved as part of the state of a virtual
+    /// machine.
+    #[error("value of type `{actual}` can't be saved as part of the vm state")]
+    UnsupportedVmStateValue {
+        /// The type of the value.
+        actual: TypeInfo,
+    },
+    /// A value is nested too deeply to be saved as part of the state of a
+    /// virtual machine, or to be copied into a snapshot of it.
+    #[error("value nested deeper than {max} levels can't be saved or copied")]
+    ValueTooDeep {
+        /// The maximum depth permitted.
+        max: usize,
+    },
+    /// A saved virtual machine state is malformed, or doesn't match the unit
+    /// it is being restored into.
+    #[error("bad vm state")]
+    BadVmState,
 }
 
 impl VmErrorKind {
@@ -1,14 +1,55 @@
-use crate::{GeneratorState, Value, Vm, VmError, VmErrorKind, VmHalt, VmHaltInfo};
+use crate::vm_recording::Recorder;
+use crate::{
+    Awaited, BudgetOutcome, GeneratorState, Recording, Value, Vm, VmError, VmErrorKind,
+    VmExecutionState, VmHalt, VmHaltInfo,
+};
+use std::time::{Duration, Instant};
+
+/// The number of instructions to run in between checking if an execution has
+/// timed out.
+const TIMEOUT_CHECK_INTERVAL: usize = 1024;
 
 /// The execution environment for a virtual machine.
 pub struct VmExecution {
     vms: Vec<Vm>,
+    recorder: Option<Recorder>,
 }
 
 impl VmExecution {
     /// Construct an execution from a virtual machine.
     pub(crate) fn new(vm: Vm) -> Self {
-        Self { vms: vec![vm] }
+        Self {
+            vms: vec![vm],
+            recorder: None,
+        }
+    }
+
+    /// Start recording every instruction executed from this point on.
+    ///
+    /// The recording can be retrieved with [take_recording][Self::take_recording].
+    pub fn record(&mut self) {
+        self.recorder = Some(Recorder::Record(Recording::new()));
+    }
+
+    /// Validate that every instruction executed from this point on follows the
+    /// given recording.
+    ///
+    /// If the execution diverges from the recording, a
+    /// [ReplayDivergence][VmErrorKind::ReplayDivergence] error is raised. If it
+    /// runs for a different number of steps, a
+    /// [ReplayLengthMismatch][VmErrorKind::ReplayLengthMismatch] error is
+    /// raised.
+    pub fn replay(&mut self, recording: Recording) {
+        self.recorder = Some(Recorder::Replay {
+            recording,
+            position: 0,
+        });
+    }
+
+    /// Take the current recording, if one is present, and stop recording or
+    /// replaying.
+    pub fn take_recording(&mut self) -> Option<Recording> {
+        Some(self.recorder.take()?.into_recording())
     }
 
     /// Get the current virtual machine.
@@ -56,23 +97,148 @@ impl VmExecution {
         }
     }
 
+    /// Wait for the given awaited value into the current virtual machine.
+    ///
+    /// If the await times out, every virtual machine in the execution is
+    /// cleared so that the futures awaited are never polled again.
+    async fn await_into_vm(&mut self, awaited: Awaited) -> Result<(), VmError> {
+        match awaited.into_vm(self.vm_mut()?).await {
+            Err(error) if matches!(error.kind(), VmErrorKind::AwaitTimeout { .. }) => {
+                self.vms.clear();
+                Err(error)
+            }
+            result => result,
+        }
+    }
+
+    /// Complete the current execution without support for async instructions,
+    /// erroring with [Timeout][VmErrorKind::Timeout] if it runs for longer
+    /// than the given `timeout`.
+    ///
+    /// On timeout every virtual machine in the execution is cleared, releasing
+    /// any values they hold. Any further attempts to run the execution will
+    /// error with [NoRunningVm][VmErrorKind::NoRunningVm].
+    pub fn complete_with_timeout(&mut self, timeout: Duration) -> Result<Value, VmError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if Instant::now() >= deadline {
+                self.vms.clear();
+                return Err(VmError::from(VmErrorKind::Timeout { timeout }));
+            }
+
+            let len = self.vms.len();
+            let mut limit = TIMEOUT_CHECK_INTERVAL;
+
+            match self.run_for(Some(&mut limit))? {
+                VmHalt::Exited => (),
+                VmHalt::Limited => continue,
+                VmHalt::VmCall(vm_call) => {
+                    vm_call.into_execution(self)?;
+                    continue;
+                }
+                halt => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: halt.into_info(),
+                    }))
+                }
+            }
+
+            if len == 1 {
+                return self.complete_last();
+            }
+
+            self.pop_vm()?;
+        }
+    }
+
+    /// Run the current execution for at most `budget` instructions without
+    /// support for async instructions.
+    ///
+    /// If the budget is exhausted before the execution completes,
+    /// [BudgetOutcome::Suspended] is returned and the execution can be resumed
+    /// from where it left off by running it again.
+    pub fn run_with_budget(&mut self, budget: usize) -> Result<BudgetOutcome, VmError> {
+        let mut budget = budget;
+
+        loop {
+            let len = self.vms.len();
+
+            match self.run_for(Some(&mut budget))? {
+                VmHalt::Exited => (),
+                VmHalt::Limited => return Ok(BudgetOutcome::Suspended),
+                VmHalt::VmCall(vm_call) => {
+                    vm_call.into_execution(self)?;
+                    continue;
+                }
+                halt => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: halt.into_info(),
+                    }))
+                }
+            }
+
+            if len == 1 {
+                let value = self.complete_last()?;
+                return Ok(BudgetOutcome::Completed(value));
+            }
+
+            self.pop_vm()?;
+        }
+    }
+
+    /// Run the current execution until it completes or reaches a breakpoint
+    /// without support for async instructions.
+    ///
+    /// Breakpoints are set through [Vm::set_breakpoint]. Once a breakpoint is
+    /// reached the current virtual machine can be inspected through
+    /// [vm][Self::vm], and running the execution again resumes it from the
+    /// breakpoint.
+    pub fn run_to_breakpoint(&mut self) -> Result<VmExecutionState, VmError> {
+        loop {
+            let len = self.vms.len();
+
+            match self.run_for(None)? {
+                VmHalt::Exited => (),
+                VmHalt::Breakpoint { ip } => return Ok(VmExecutionState::Breakpoint { ip }),
+                VmHalt::VmCall(vm_call) => {
+                    vm_call.into_execution(self)?;
+                    continue;
+                }
+                halt => {
+                    return Err(VmError::from(VmErrorKind::Halted {
+                        halt: halt.into_info(),
+                    }))
+                }
+            }
+
+            if len == 1 {
+                let value = self.complete_last()?;
+                return Ok(VmExecutionState::Complete(value));
+            }
+
+            self.pop_vm()?;
+        }
+    }
+
     /// Resume the current execution with support for async instructions.
     pub async fn async_resume(&mut self) -> Result<GeneratorState, VmError> {
         loop {
             let len = self.vms.len();
-            let vm = self.vm_mut()?;
 
-            match Self::run_for(vm, None)? {
+            match self.run_for(None)? {
                 VmHalt::Exited => (),
                 VmHalt::Awaited(awaited) => {
-                    awaited.into_vm(vm).await?;
+                    self.await_into_vm(awaited).await?;
                     continue;
                 }
                 VmHalt::VmCall(vm_call) => {
                     vm_call.into_execution(self)?;
                     continue;
                 }
-                VmHalt::Yielded => return Ok(GeneratorState::Yielded(vm.stack_mut().pop()?)),
+                VmHalt::Yielded => {
+                    return Ok(GeneratorState::Yielded(self.vm_mut()?.stack_mut().pop()?))
+                }
                 halt => {
                     return Err(VmError::from(VmErrorKind::Halted {
                         halt: halt.into_info(),
@@ -81,9 +247,7 @@ impl VmExecution {
             }
 
             if len == 1 {
-                let value = vm.stack_mut().pop()?;
-                debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
-                self.vms.clear();
+                let value = self.complete_last()?;
                 return Ok(GeneratorState::Complete(value));
             }
 
@@ -97,15 +261,16 @@ impl VmExecution {
     pub fn resume(&mut self) -> Result<GeneratorState, VmError> {
         loop {
             let len = self.vms.len();
-            let vm = self.vm_mut()?;
 
-            match Self::run_for(vm, None)? {
+            match self.run_for(None)? {
                 VmHalt::Exited => (),
                 VmHalt::VmCall(vm_call) => {
                     vm_call.into_execution(self)?;
                     continue;
                 }
-                VmHalt::Yielded => return Ok(GeneratorState::Yielded(vm.stack_mut().pop()?)),
+                VmHalt::Yielded => {
+                    return Ok(GeneratorState::Yielded(self.vm_mut()?.stack_mut().pop()?))
+                }
                 halt => {
                     return Err(VmError::from(VmErrorKind::Halted {
                         halt: halt.into_info(),
@@ -114,9 +279,7 @@ impl VmExecution {
             }
 
             if len == 1 {
-                let value = vm.stack_mut().pop()?;
-                debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
-                self.vms.clear();
+                let value = self.complete_last()?;
                 return Ok(GeneratorState::Complete(value));
             }
 
@@ -130,9 +293,8 @@ impl VmExecution {
     /// If any async instructions are encountered, this will error.
     pub fn step(&mut self) -> Result<Option<Value>, VmError> {
         let len = self.vms.len();
-        let vm = self.vm_mut()?;
 
-        match Self::run_for(vm, Some(1))? {
+        match self.run_for(Some(&mut 1))? {
             VmHalt::Exited => (),
             VmHalt::VmCall(vm_call) => {
                 vm_call.into_execution(self)?;
@@ -147,8 +309,14 @@ impl VmExecution {
         }
 
         if len == 1 {
+            let vm = self.vm_mut()?;
             let value = vm.stack_mut().pop()?;
             debug_assert!(vm.stack().is_empty(), "final vm stack not clean");
+
+            if let Some(recorder) = &self.recorder {
+                recorder.finish()?;
+            }
+
             return Ok(Some(value));
         }
 
@@ -160,12 +328,11 @@ impl VmExecution {
     /// instructions.
     pub async fn async_step(&mut self) -> Result<Option<Value>, VmError> {
         let len = self.vms.len();
-        let vm = self.vm_mut()?;
 
-        match Self::run_for(vm, Some(1))? {
+        match self.run_for(Some(&mut 1))? {
             VmHalt::Exited => (),
             VmHalt::Awaited(awaited) => {
-                awaited.into_vm(vm).await?;
+                self.await_into_vm(awaited).await?;
                 return Ok(None);
             }
             VmHalt::VmCall(vm_call) => {
@@ -181,8 +348,14 @@ impl VmExecution {
         }
 
         if len == 1 {
+            let vm = self.vm_mut()?;
             let value = vm.stack_mut().pop()?;
             debug_assert!(vm.stack().is_empty(), "final vm stack not clean");
+
+            if let Some(recorder) = &self.recorder {
+                recorder.finish()?;
+            }
+
             return Ok(Some(value));
         }
 
@@ -213,11 +386,61 @@ impl VmExecution {
         Ok(())
     }
 
+    /// Pop the return value of the last virtual machine and clear the
+    /// execution.
+    fn complete_last(&mut self) -> Result<Value, VmError> {
+        let vm = self.vm_mut()?;
+        let value = vm.stack_mut().pop()?;
+        debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
+        self.vms.clear();
+
+        if let Some(recorder) = &self.recorder {
+            recorder.finish()?;
+        }
+
+        Ok(value)
+    }
+
     #[inline]
-    fn run_for(vm: &mut Vm, limit: Option<usize>) -> Result<VmHalt, VmError> {
-        match vm.run_for(limit) {
+    fn run_for(&mut self, limit: Option<&mut usize>) -> Result<VmHalt, VmError> {
+        let vm = match self.vms.last_mut() {
+            Some(vm) => vm,
+            None => return Err(VmError::from(VmErrorKind::NoRunningVm)),
+        };
+
+        let result = match &mut self.recorder {
+            Some(recorder) => Self::run_recorded(vm, recorder, limit),
+            None => vm.run_for(limit),
+        };
+
+        match result {
             Ok(reason) => Ok(reason),
             Err(error) => Err(error.into_unwinded(vm.unit(), vm.ip())),
         }
     }
+
+    /// Run the virtual machine one instruction at a time, passing every
+    /// visited instruction pointer through the recorder.
+    fn run_recorded(
+        vm: &mut Vm,
+        recorder: &mut Recorder,
+        mut limit: Option<&mut usize>,
+    ) -> Result<VmHalt, VmError> {
+        loop {
+            if let Some(limit) = &mut limit {
+                if **limit == 0 {
+                    return Ok(VmHalt::Limited);
+                }
+
+                **limit -= 1;
+            }
+
+            recorder.visit(vm.ip())?;
+
+            match vm.run_for(Some(&mut 1))? {
+                VmHalt::Limited => (),
+                halt => return Ok(halt),
+            }
+        }
+    }
 }
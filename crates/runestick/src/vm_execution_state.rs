@@ -0,0 +1,29 @@
+use crate::Value;
+
+/// The state of an execution which was run until it reached a breakpoint.
+///
+/// See [VmExecution::run_to_breakpoint][crate::VmExecution::run_to_breakpoint].
+#[derive(Debug)]
+pub enum VmExecutionState {
+    /// The execution completed with the given value.
+    Complete(Value),
+    /// The execution reached a breakpoint at the given instruction pointer,
+    /// before executing the instruction there. The execution can be resumed
+    /// by running it again.
+    Breakpoint {
+        /// The instruction pointer of the breakpoint.
+        ip: usize,
+    },
+}
+
+impl VmExecutionState {
+    /// Test if the execution completed.
+    pub fn is_complete(&self) -> bool {
+        matches!(self, Self::Complete(..))
+    }
+
+    /// Test if the execution reached a breakpoint.
+    pub fn is_breakpoint(&self) -> bool {
+        matches!(self, Self::Breakpoint { .. })
+    }
+}
@@ -13,7 +13,13 @@ pub enum VmHalt {
     /// The virtual machine awaited on the given future.
     Awaited(Awaited),
     /// Call into a new virtual machine.
-    VmCall(VmCall),
+    VmCall(Box<VmCall>),
+    /// The virtual machine reached a breakpoint at the given instruction
+    /// pointer, before executing the instruction there.
+    Breakpoint {
+        /// The instruction pointer of the breakpoint.
+        ip: usize,
+    },
 }
 
 impl VmHalt {
@@ -25,6 +31,7 @@ impl VmHalt {
             Self::Yielded => VmHaltInfo::Yielded,
             Self::Awaited(..) => VmHaltInfo::Awaited,
             Self::VmCall(..) => VmHaltInfo::VmCall,
+            Self::Breakpoint { ip } => VmHaltInfo::Breakpoint { ip },
         }
     }
 }
@@ -42,6 +49,11 @@ pub enum VmHaltInfo {
     Awaited,
     /// Received instruction to push the inner virtual machine.
     VmCall,
+    /// The virtual machine reached a breakpoint.
+    Breakpoint {
+        /// The instruction pointer of the breakpoint.
+        ip: usize,
+    },
 }
 
 impl fmt::Display for VmHaltInfo {
@@ -52,6 +64,7 @@ impl fmt::Display for VmHaltInfo {
             Self::Yielded => write!(f, "yielded"),
             Self::Awaited => write!(f, "awaited"),
             Self::VmCall => write!(f, "calling into other vm"),
+            Self::Breakpoint { ip } => write!(f, "breakpoint at {}", ip),
         }
     }
 }
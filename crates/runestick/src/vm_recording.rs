@@ -0,0 +1,116 @@
+use crate::{VmError, VmErrorKind};
+
+/// A recording of every instruction pointer visited by an execution.
+///
+/// Can be constructed by calling [VmExecution::record][crate::VmExecution::record]
+/// and later used to validate that an execution follows the exact same path
+/// through [VmExecution::replay][crate::VmExecution::replay].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Recording {
+    ips: Vec<usize>,
+}
+
+impl Recording {
+    /// Construct a new empty recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Construct a recording from a sequence of instruction pointers.
+    pub fn from_ips(ips: Vec<usize>) -> Self {
+        Self { ips }
+    }
+
+    /// Get the recorded instruction pointers, in the order they were executed.
+    pub fn ips(&self) -> &[usize] {
+        &self.ips
+    }
+
+    /// Get the number of recorded instructions.
+    pub fn len(&self) -> usize {
+        self.ips.len()
+    }
+
+    /// Test if the recording is empty.
+    pub fn is_empty(&self) -> bool {
+        self.ips.is_empty()
+    }
+}
+
+/// The recording state of an execution.
+#[derive(Debug)]
+pub(crate) enum Recorder {
+    /// Record every visited instruction.
+    Record(Recording),
+    /// Validate every visited instruction against an existing recording.
+    Replay {
+        /// The recording being replayed.
+        recording: Recording,
+        /// The next position in the recording to validate.
+        position: usize,
+    },
+}
+
+impl Recorder {
+    /// Visit the instruction at the given instruction pointer.
+    pub(crate) fn visit(&mut self, ip: usize) -> Result<(), VmError> {
+        match self {
+            Self::Record(recording) => {
+                recording.ips.push(ip);
+            }
+            Self::Replay {
+                recording,
+                position,
+            } => {
+                let expected = match recording.ips.get(*position) {
+                    Some(expected) => *expected,
+                    None => {
+                        return Err(VmError::from(VmErrorKind::ReplayLengthMismatch {
+                            expected: recording.len(),
+                            actual: *position + 1,
+                        }));
+                    }
+                };
+
+                if expected != ip {
+                    return Err(VmError::from(VmErrorKind::ReplayDivergence {
+                        position: *position,
+                        expected,
+                        actual: ip,
+                    }));
+                }
+
+                *position += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the execution has run to the end of the recording being
+    /// replayed.
+    pub(crate) fn finish(&self) -> Result<(), VmError> {
+        if let Self::Replay {
+            recording,
+            position,
+        } = self
+        {
+            if *position != recording.len() {
+                return Err(VmError::from(VmErrorKind::ReplayLengthMismatch {
+                    expected: recording.len(),
+                    actual: *position,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convert into the underlying recording.
+    pub(crate) fn into_recording(self) -> Recording {
+        match self {
+            Self::Record(recording) => recording,
+            Self::Replay { recording, .. } => recording,
+        }
+    }
+}
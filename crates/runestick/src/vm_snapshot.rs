@@ -0,0 +1,231 @@
+//! Capturing and restoring the execution state of a virtual machine in memory.
+
+use crate::collections::HashMap;
+use crate::value::MAX_VALUE_DEPTH;
+use crate::{
+    Bytes, CallFrame, Map, Object, Range, Shared, Stack, Tuple, TupleVariant, TypedObject,
+    TypedTuple, Value, VariantObject, VmError, VmErrorKind,
+};
+
+/// A snapshot of the execution state of a virtual machine, taken with
+/// [Vm::snapshot][crate::Vm::snapshot] and rolled back to with
+/// [Vm::restore][crate::Vm::restore].
+#[derive(Debug)]
+pub struct VmSnapshot {
+    /// The instruction pointer.
+    pub(crate) ip: usize,
+    /// A copy of the stack.
+    pub(crate) stack: Stack,
+    /// The call frames.
+    pub(crate) call_frames: Vec<CallFrame>,
+}
+
+impl VmSnapshot {
+    /// Get the instruction pointer the snapshot was taken at.
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+}
+
+/// Copy the given stack, so that modifications to values on it don't affect
+/// the copy.
+pub(crate) fn copy_stack(stack: &Stack) -> Result<Stack, VmError> {
+    let mut copier = Copier {
+        copies: HashMap::new(),
+        depth: 0,
+    };
+
+    let values = stack
+        .iter()
+        .map(|value| copier.value(value))
+        .collect::<Result<Vec<_>, VmError>>()?;
+
+    Ok(Stack::from_parts(values, stack.stack_bottom()))
+}
+
+struct Copier {
+    /// Shared values which have already been copied, by address.
+    copies: HashMap<*const (), Value>,
+    /// The depth of the value currently being copied.
+    depth: usize,
+}
+
+impl Copier {
+    fn values(&mut self, values: &[Value]) -> Result<Vec<Value>, VmError> {
+        values.iter().map(|value| self.value(value)).collect()
+    }
+
+    fn object(&mut self, object: &Object<Value>) -> Result<Object<Value>, VmError> {
+        let mut copy = Object::with_capacity(object.len());
+
+        for (key, value) in object {
+            copy.insert(key.clone(), self.value(value)?);
+        }
+
+        Ok(copy)
+    }
+
+    fn map(&mut self, map: &Map<Value>) -> Result<Map<Value>, VmError> {
+        let mut copy = Map::with_capacity(map.len());
+
+        for (key, value) in map {
+            copy.insert(key.clone(), self.value(value)?);
+        }
+
+        Ok(copy)
+    }
+
+    /// Copy a shared value, or reuse the copy if it has already been copied.
+    /// This preserves values which are shared in multiple places, and values
+    /// which contain themselves.
+    fn shared<T>(
+        &mut self,
+        shared: &Shared<T>,
+        initial: T,
+        wrap: fn(Shared<T>) -> Value,
+        f: impl FnOnce(&mut Self, &T) -> Result<T, VmError>,
+    ) -> Result<Value, VmError> {
+        let ptr = Shared::as_ptr(shared);
+
+        if let Some(copy) = self.copies.get(&ptr) {
+            return Ok(copy.clone());
+        }
+
+        let copy = Shared::new(initial);
+        self.copies.insert(ptr, wrap(copy.clone()));
+        let data = f(self, &*shared.borrow_ref()?)?;
+        *copy.borrow_mut()? = data;
+        Ok(wrap(copy))
+    }
+
+    fn value(&mut self, value: &Value) -> Result<Value, VmError> {
+        if self.depth == MAX_VALUE_DEPTH {
+            return Err(VmError::from(VmErrorKind::ValueTooDeep {
+                max: MAX_VALUE_DEPTH,
+            }));
+        }
+
+        self.depth += 1;
+        let copy = self.value_inner(value);
+        self.depth -= 1;
+        copy
+    }
+
+    fn value_inner(&mut self, value: &Value) -> Result<Value, VmError> {
+        Ok(match value {
+            Value::String(string) => {
+                self.shared(string, String::new(), Value::String, |_, string| {
+                    Ok(string.clone())
+                })?
+            }
+            Value::Bytes(bytes) => self.shared(bytes, Bytes::new(), Value::Bytes, |_, bytes| {
+                Ok(bytes.clone())
+            })?,
+            Value::Vec(vec) => {
+                self.shared(vec, Vec::new(), Value::Vec, |this, vec| this.values(vec))?
+            }
+            Value::Tuple(tuple) => self.shared(
+                tuple,
+                Tuple::from(Vec::new()),
+                Value::Tuple,
+                |this, tuple| Ok(Tuple::from(this.values(tuple)?)),
+            )?,
+            Value::Object(object) => {
+                self.shared(object, Object::new(), Value::Object, |this, object| {
+                    this.object(object)
+                })?
+            }
+            Value::Map(map) => {
+                self.shared(map, Map::new(), Value::Map, |this, map| this.map(map))?
+            }
+            Value::Range(range) => self.shared(
+                range,
+                Range::new(None, None, false),
+                Value::Range,
+                |_, range| Ok(*range),
+            )?,
+            Value::Option(option) => {
+                self.shared(option, None, Value::Option, |this, option| match option {
+                    Some(value) => Ok(Some(this.value(value)?)),
+                    None => Ok(None),
+                })?
+            }
+            Value::Result(result) => {
+                self.shared(result, Ok(Value::Unit), Value::Result, |this, result| {
+                    Ok(match result {
+                        Ok(value) => Ok(this.value(value)?),
+                        Err(value) => Err(this.value(value)?),
+                    })
+                })?
+            }
+            Value::TypedTuple(tuple) => {
+                let initial = TypedTuple {
+                    hash: tuple.borrow_ref()?.hash,
+                    tuple: Box::new([]),
+                };
+
+                self.shared(tuple, initial, Value::TypedTuple, |this, tuple| {
+                    Ok(TypedTuple {
+                        hash: tuple.hash,
+                        tuple: this.values(&tuple.tuple)?.into(),
+                    })
+                })?
+            }
+            Value::TupleVariant(tuple) => {
+                let initial = {
+                    let tuple = tuple.borrow_ref()?;
+
+                    TupleVariant {
+                        enum_hash: tuple.enum_hash,
+                        hash: tuple.hash,
+                        tuple: Box::new([]),
+                    }
+                };
+
+                self.shared(tuple, initial, Value::TupleVariant, |this, tuple| {
+                    Ok(TupleVariant {
+                        enum_hash: tuple.enum_hash,
+                        hash: tuple.hash,
+                        tuple: this.values(&tuple.tuple)?.into(),
+                    })
+                })?
+            }
+            Value::TypedObject(object) => {
+                let initial = TypedObject {
+                    hash: object.borrow_ref()?.hash,
+                    object: Object::new(),
+                };
+
+                self.shared(object, initial, Value::TypedObject, |this, object| {
+                    Ok(TypedObject {
+                        hash: object.hash,
+                        object: this.object(&object.object)?,
+                    })
+                })?
+            }
+            Value::VariantObject(object) => {
+                let initial = {
+                    let object = object.borrow_ref()?;
+
+                    VariantObject {
+                        enum_hash: object.enum_hash,
+                        hash: object.hash,
+                        object: Object::new(),
+                    }
+                };
+
+                self.shared(object, initial, Value::VariantObject, |this, object| {
+                    Ok(VariantObject {
+                        enum_hash: object.enum_hash,
+                        hash: object.hash,
+                        object: this.object(&object.object)?,
+                    })
+                })?
+            }
+            // NB: primitives are copied as-is, while futures, streams,
+            // generators, functions, and external types can't be copied and
+            // are shared with the snapshot instead.
+            value => value.clone(),
+        })
+    }
+}
@@ -0,0 +1,592 @@
+//! Capturing and restoring the execution state of a virtual machine as bytes.
+
+use crate::collections::HashMap;
+use crate::value::MAX_VALUE_DEPTH;
+use crate::{
+    BigInt, Bytes, Hash, HashKey, Map, Object, Range, Shared, Stack, StaticString, Tuple,
+    TupleVariant, TypedObject, TypedTuple, Value, VariantObject, VmError, VmErrorKind,
+};
+use std::convert::TryFrom as _;
+use std::sync::Arc;
+
+/// Magic prefix used to identify serialized virtual machine states.
+const MAGIC: &[u8; 4] = b"RNVM";
+/// The version of the serialized format.
+const VERSION: u8 = 1;
+
+const UNIT: u8 = 0;
+const BOOL: u8 = 1;
+const BYTE: u8 = 2;
+const CHAR: u8 = 3;
+const INTEGER: u8 = 4;
+const FLOAT: u8 = 5;
+const BIG_INT: u8 = 6;
+const TYPE: u8 = 7;
+const STATIC_STRING: u8 = 8;
+const STRING: u8 = 9;
+const BYTES: u8 = 10;
+const VEC: u8 = 11;
+const TUPLE: u8 = 12;
+const OBJECT: u8 = 13;
+const OPTION: u8 = 14;
+const RESULT: u8 = 15;
+const TYPED_TUPLE: u8 = 16;
+const TUPLE_VARIANT: u8 = 17;
+const TYPED_OBJECT: u8 = 18;
+const VARIANT_OBJECT: u8 = 19;
+/// A reference to a shared value which has already been encoded.
+const REFERENCE: u8 = 20;
+const RANGE: u8 = 21;
+const MAP: u8 = 22;
+
+/// The decoded execution state of a virtual machine.
+pub(crate) struct VmState {
+    /// The instruction pointer.
+    pub(crate) ip: usize,
+    /// The values on the stack.
+    pub(crate) stack: Vec<Value>,
+    /// The bottom of the current stack frame.
+    pub(crate) stack_bottom: usize,
+    /// The instruction pointer and stack bottom of every call frame.
+    pub(crate) call_frames: Vec<(usize, usize)>,
+}
+
+/// Encode the given execution state.
+pub(crate) fn encode(
+    ip: usize,
+    stack: &Stack,
+    call_frames: impl ExactSizeIterator<Item = (usize, usize)>,
+) -> Result<Vec<u8>, VmError> {
+    let mut encoder = Encoder {
+        out: Vec::new(),
+        seen: HashMap::new(),
+        depth: 0,
+    };
+
+    encoder.out.extend_from_slice(MAGIC);
+    encoder.out.push(VERSION);
+    encoder.usize(ip);
+    encoder.usize(stack.stack_bottom());
+
+    encoder.usize(call_frames.len());
+
+    for (ip, stack_bottom) in call_frames {
+        encoder.usize(ip);
+        encoder.usize(stack_bottom);
+    }
+
+    encoder.usize(stack.len());
+
+    for value in stack.iter() {
+        encoder.value(value)?;
+    }
+
+    Ok(encoder.out)
+}
+
+/// Decode an execution state previously encoded with [encode].
+pub(crate) fn decode(bytes: &[u8]) -> Result<VmState, VmError> {
+    let mut decoder = Decoder {
+        bytes,
+        shared: Vec::new(),
+        depth: 0,
+    };
+
+    if decoder.take(MAGIC.len())? != MAGIC || decoder.u8()? != VERSION {
+        return Err(VmError::from(VmErrorKind::BadVmState));
+    }
+
+    let ip = decoder.usize()?;
+    let stack_bottom = decoder.usize()?;
+
+    let mut call_frames = Vec::new();
+
+    for _ in 0..decoder.usize()? {
+        call_frames.push((decoder.usize()?, decoder.usize()?));
+    }
+
+    let mut stack = Vec::new();
+
+    for _ in 0..decoder.usize()? {
+        stack.push(decoder.value()?);
+    }
+
+    if !decoder.bytes.is_empty() {
+        return Err(VmError::from(VmErrorKind::BadVmState));
+    }
+
+    Ok(VmState {
+        ip,
+        stack,
+        stack_bottom,
+        call_frames,
+    })
+}
+
+struct Encoder {
+    out: Vec<u8>,
+    /// Shared values which have already been encoded, by address, mapped to
+    /// the order in which they were encoded.
+    seen: HashMap<*const (), usize>,
+    /// The depth of the value currently being encoded.
+    depth: usize,
+}
+
+impl Encoder {
+    fn u64(&mut self, n: u64) {
+        self.out.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn usize(&mut self, n: usize) {
+        self.u64(n as u64);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.usize(bytes.len());
+        self.out.extend_from_slice(bytes);
+    }
+
+    fn hash(&mut self, hash: Hash) {
+        self.u64(hash.into_inner());
+    }
+
+    fn bound(&mut self, bound: Option<i64>) {
+        match bound {
+            Some(n) => {
+                self.out.push(1);
+                self.u64(n as u64);
+            }
+            None => self.out.push(0),
+        }
+    }
+
+    fn values(&mut self, values: &[Value]) -> Result<(), VmError> {
+        self.usize(values.len());
+
+        for value in values {
+            self.value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn object(&mut self, object: &Object<Value>) -> Result<(), VmError> {
+        self.usize(object.len());
+
+        for (key, value) in object {
+            self.bytes(key.as_bytes());
+            self.value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn map(&mut self, map: &Map<Value>) -> Result<(), VmError> {
+        self.usize(map.len());
+
+        for (key, value) in map {
+            self.value(&key.clone().into_value())?;
+            self.value(value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Encode a shared value, or a reference to it if it has already been
+    /// encoded. This preserves values which are shared in multiple places, and
+    /// values which contain themselves.
+    fn shared<T>(
+        &mut self,
+        tag: u8,
+        shared: &Shared<T>,
+        f: impl FnOnce(&mut Self, &T) -> Result<(), VmError>,
+    ) -> Result<(), VmError> {
+        let ptr = Shared::as_ptr(shared);
+
+        if let Some(index) = self.seen.get(&ptr).copied() {
+            self.out.push(REFERENCE);
+            self.usize(index);
+            return Ok(());
+        }
+
+        self.seen.insert(ptr, self.seen.len());
+        self.out.push(tag);
+        let value = shared.borrow_ref()?;
+        f(self, &*value)
+    }
+
+    fn value(&mut self, value: &Value) -> Result<(), VmError> {
+        if self.depth == MAX_VALUE_DEPTH {
+            return Err(VmError::from(VmErrorKind::ValueTooDeep {
+                max: MAX_VALUE_DEPTH,
+            }));
+        }
+
+        self.depth += 1;
+        let result = self.value_inner(value);
+        self.depth -= 1;
+        result
+    }
+
+    fn value_inner(&mut self, value: &Value) -> Result<(), VmError> {
+        match value {
+            Value::Unit => self.out.push(UNIT),
+            Value::Bool(b) => {
+                self.out.push(BOOL);
+                self.out.push(*b as u8);
+            }
+            Value::Byte(b) => {
+                self.out.push(BYTE);
+                self.out.push(*b);
+            }
+            Value::Char(c) => {
+                self.out.push(CHAR);
+                self.u64(*c as u64);
+            }
+            Value::Integer(integer) => {
+                self.out.push(INTEGER);
+                self.u64(*integer as u64);
+            }
+            Value::Float(float) => {
+                self.out.push(FLOAT);
+                self.u64(float.to_bits());
+            }
+            Value::BigInt(big_int) => {
+                self.out.push(BIG_INT);
+                self.bytes(&big_int.to_signed_bytes_le());
+            }
+            Value::Type(hash) => {
+                self.out.push(TYPE);
+                self.hash(*hash);
+            }
+            Value::StaticString(string) => {
+                self.out.push(STATIC_STRING);
+                self.bytes(string.as_bytes());
+            }
+            Value::String(string) => {
+                self.shared(STRING, string, |this, string| {
+                    this.bytes(string.as_bytes());
+                    Ok(())
+                })?;
+            }
+            Value::Bytes(bytes) => {
+                self.shared(BYTES, bytes, |this, bytes| {
+                    this.bytes(&bytes.bytes);
+                    Ok(())
+                })?;
+            }
+            Value::Vec(vec) => {
+                self.shared(VEC, vec, |this, vec| this.values(vec))?;
+            }
+            Value::Tuple(tuple) => {
+                self.shared(TUPLE, tuple, |this, tuple| this.values(tuple))?;
+            }
+            Value::Object(object) => {
+                self.shared(OBJECT, object, |this, object| this.object(object))?;
+            }
+            Value::Map(map) => {
+                self.shared(MAP, map, |this, map| this.map(map))?;
+            }
+            Value::Range(range) => {
+                self.shared(RANGE, range, |this, range| {
+                    this.bound(range.start);
+                    this.bound(range.end);
+                    this.out.push(range.inclusive as u8);
+                    Ok(())
+                })?;
+            }
+            Value::Option(option) => {
+                self.shared(OPTION, option, |this, option| match option {
+                    Some(value) => {
+                        this.out.push(1);
+                        this.value(value)
+                    }
+                    None => {
+                        this.out.push(0);
+                        Ok(())
+                    }
+                })?;
+            }
+            Value::Result(result) => {
+                self.shared(RESULT, result, |this, result| match result {
+                    Ok(value) => {
+                        this.out.push(1);
+                        this.value(value)
+                    }
+                    Err(value) => {
+                        this.out.push(0);
+                        this.value(value)
+                    }
+                })?;
+            }
+            Value::TypedTuple(tuple) => {
+                self.shared(TYPED_TUPLE, tuple, |this, tuple| {
+                    this.hash(tuple.hash);
+                    this.values(&tuple.tuple)
+                })?;
+            }
+            Value::TupleVariant(tuple) => {
+                self.shared(TUPLE_VARIANT, tuple, |this, tuple| {
+                    this.hash(tuple.enum_hash);
+                    this.hash(tuple.hash);
+                    this.values(&tuple.tuple)
+                })?;
+            }
+            Value::TypedObject(object) => {
+                self.shared(TYPED_OBJECT, object, |this, object| {
+                    this.hash(object.hash);
+                    this.object(&object.object)
+                })?;
+            }
+            Value::VariantObject(object) => {
+                self.shared(VARIANT_OBJECT, object, |this, object| {
+                    this.hash(object.enum_hash);
+                    this.hash(object.hash);
+                    this.object(&object.object)
+                })?;
+            }
+            actual => {
+                return Err(VmError::from(VmErrorKind::UnsupportedVmStateValue {
+                    actual: actual.type_info()?,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    /// Shared values decoded so far, in the order they were encoded.
+    shared: Vec<Value>,
+    /// The depth of the value currently being decoded.
+    depth: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], VmError> {
+        if self.bytes.len() < n {
+            return Err(VmError::from(VmErrorKind::BadVmState));
+        }
+
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, VmError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, VmError> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn usize(&mut self) -> Result<usize, VmError> {
+        usize::try_from(self.u64()?).map_err(|_| VmError::from(VmErrorKind::BadVmState))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], VmError> {
+        let len = self.usize()?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String, VmError> {
+        let bytes = self.bytes()?.to_vec();
+        String::from_utf8(bytes).map_err(|_| VmError::from(VmErrorKind::BadVmState))
+    }
+
+    fn hash(&mut self) -> Result<Hash, VmError> {
+        Ok(Hash::new(self.u64()?))
+    }
+
+    fn bound(&mut self) -> Result<Option<i64>, VmError> {
+        Ok(match self.u8()? {
+            0 => None,
+            _ => Some(self.u64()? as i64),
+        })
+    }
+
+    fn values(&mut self) -> Result<Vec<Value>, VmError> {
+        let mut values = Vec::new();
+
+        for _ in 0..self.usize()? {
+            values.push(self.value()?);
+        }
+
+        Ok(values)
+    }
+
+    fn object(&mut self) -> Result<Object<Value>, VmError> {
+        let mut object = Object::new();
+
+        for _ in 0..self.usize()? {
+            let key = self.string()?;
+            object.insert(key, self.value()?);
+        }
+
+        Ok(object)
+    }
+
+    fn map(&mut self) -> Result<Map<Value>, VmError> {
+        let mut map = Map::new();
+
+        for _ in 0..self.usize()? {
+            let key = HashKey::from_value(&self.value()?)
+                .map_err(|_| VmError::from(VmErrorKind::BadVmState))?;
+            map.insert(key, self.value()?);
+        }
+
+        Ok(map)
+    }
+
+    /// Decode a shared value.
+    ///
+    /// The value is registered before its content is decoded, so that
+    /// references to it from within its own content resolve to it.
+    fn shared<T>(
+        &mut self,
+        initial: T,
+        wrap: fn(Shared<T>) -> Value,
+        f: impl FnOnce(&mut Self) -> Result<T, VmError>,
+    ) -> Result<Value, VmError> {
+        let shared = Shared::new(initial);
+        self.shared.push(wrap(shared.clone()));
+        let data = f(self)?;
+        *shared.borrow_mut()? = data;
+        Ok(wrap(shared))
+    }
+
+    fn value(&mut self) -> Result<Value, VmError> {
+        if self.depth == MAX_VALUE_DEPTH {
+            return Err(VmError::from(VmErrorKind::BadVmState));
+        }
+
+        self.depth += 1;
+        let value = self.value_inner();
+        self.depth -= 1;
+        value
+    }
+
+    fn value_inner(&mut self) -> Result<Value, VmError> {
+        Ok(match self.u8()? {
+            UNIT => Value::Unit,
+            BOOL => Value::Bool(self.u8()? != 0),
+            BYTE => Value::Byte(self.u8()?),
+            CHAR => {
+                let c = u32::try_from(self.u64()?)
+                    .ok()
+                    .and_then(std::char::from_u32);
+                Value::Char(c.ok_or_else(|| VmError::from(VmErrorKind::BadVmState))?)
+            }
+            INTEGER => Value::Integer(self.u64()? as i64),
+            FLOAT => Value::Float(f64::from_bits(self.u64()?)),
+            BIG_INT => Value::BigInt(Arc::new(BigInt::from_signed_bytes_le(self.bytes()?))),
+            TYPE => Value::Type(self.hash()?),
+            STATIC_STRING => Value::StaticString(Arc::new(StaticString::new(self.string()?))),
+            STRING => self.shared(String::new(), Value::String, Self::string)?,
+            BYTES => self.shared(Bytes::new(), Value::Bytes, |this| {
+                Ok(Bytes::from_vec(this.bytes()?.to_vec()))
+            })?,
+            VEC => self.shared(Vec::new(), Value::Vec, Self::values)?,
+            TUPLE => self.shared(Tuple::from(Vec::new()), Value::Tuple, |this| {
+                Ok(Tuple::from(this.values()?))
+            })?,
+            OBJECT => self.shared(Object::new(), Value::Object, Self::object)?,
+            MAP => self.shared(Map::new(), Value::Map, Self::map)?,
+            RANGE => self.shared(Range::new(None, None, false), Value::Range, |this| {
+                Ok(Range::new(this.bound()?, this.bound()?, this.u8()? != 0))
+            })?,
+            OPTION => self.shared(None, Value::Option, |this| {
+                Ok(match this.u8()? {
+                    0 => None,
+                    _ => Some(this.value()?),
+                })
+            })?,
+            RESULT => self.shared(Ok(Value::Unit), Value::Result, |this| {
+                Ok(match this.u8()? {
+                    0 => Err(this.value()?),
+                    _ => Ok(this.value()?),
+                })
+            })?,
+            TYPED_TUPLE => {
+                let hash = self.hash()?;
+
+                let initial = TypedTuple {
+                    hash,
+                    tuple: Box::new([]),
+                };
+
+                self.shared(initial, Value::TypedTuple, |this| {
+                    Ok(TypedTuple {
+                        hash,
+                        tuple: this.values()?.into_boxed_slice(),
+                    })
+                })?
+            }
+            TUPLE_VARIANT => {
+                let enum_hash = self.hash()?;
+                let hash = self.hash()?;
+
+                let initial = TupleVariant {
+                    enum_hash,
+                    hash,
+                    tuple: Box::new([]),
+                };
+
+                self.shared(initial, Value::TupleVariant, |this| {
+                    Ok(TupleVariant {
+                        enum_hash,
+                        hash,
+                        tuple: this.values()?.into_boxed_slice(),
+                    })
+                })?
+            }
+            TYPED_OBJECT => {
+                let hash = self.hash()?;
+
+                let initial = TypedObject {
+                    hash,
+                    object: Object::new(),
+                };
+
+                self.shared(initial, Value::TypedObject, |this| {
+                    Ok(TypedObject {
+                        hash,
+                        object: this.object()?,
+                    })
+                })?
+            }
+            VARIANT_OBJECT => {
+                let enum_hash = self.hash()?;
+                let hash = self.hash()?;
+
+                let initial = VariantObject {
+                    enum_hash,
+                    hash,
+                    object: Object::new(),
+                };
+
+                self.shared(initial, Value::VariantObject, |this| {
+                    Ok(VariantObject {
+                        enum_hash,
+                        hash,
+                        object: this.object()?,
+                    })
+                })?
+            }
+            REFERENCE => {
+                let index = self.usize()?;
+
+                self.shared
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| VmError::from(VmErrorKind::BadVmState))?
+            }
+            _ => return Err(VmError::from(VmErrorKind::BadVmState)),
+        })
+    }
+}